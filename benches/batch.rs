@@ -0,0 +1,108 @@
+//! Compares the array-of-structs path (`Vec<Vector3D>`) against the
+//! structure-of-arrays path (`Vector3DBatch`) for bulk operations.
+//!
+//! Run with `cargo bench --bench batch`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use game_maths::maths::{Matrix3D, Vector3D, Vector3DBatch};
+
+const COUNT: usize = 100_000;
+const ITERATIONS: u32 = 50;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    // Warm up once so both paths start with hot caches
+    f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn report(name: &str, aos: Duration, soa: Duration) {
+    println!(
+        "{:<12} aos: {:>10.3?}  soa: {:>10.3?}  speedup: {:.2}x",
+        name,
+        aos,
+        soa,
+        aos.as_secs_f64() / soa.as_secs_f64()
+    );
+}
+
+fn main() {
+    let vectors: Vec<Vector3D> = (0..COUNT)
+        .map(|i| {
+            let f = i as f64;
+            Vector3D::new(f.sin(), f.cos(), f * 1e-3 + 1.0)
+        })
+        .collect();
+    let others: Vec<Vector3D> = vectors.iter().map(|v| Vector3D::new(v.z, v.x, v.y)).collect();
+    let m = Matrix3D::new(0.36, 0.48, -0.8,
+                          -0.8, 0.6, 0.0,
+                          0.48, 0.64, 0.6);
+
+    let batch = Vector3DBatch::from(vectors.clone());
+    let other_batch = Vector3DBatch::from(others.clone());
+
+    // In-place paths work on scratch copies so allocation stays out of the timing
+    let mut aos_scratch = vectors.clone();
+    let mut soa_scratch = batch.clone();
+
+    let aos = time(|| {
+        for v in aos_scratch.iter_mut() {
+            *v = m * *v;
+        }
+        black_box(&aos_scratch);
+    });
+    let soa = time(|| {
+        soa_scratch.transform(&m);
+        black_box(&soa_scratch);
+    });
+    report("transform", aos, soa);
+
+    let aos = time(|| {
+        let out: Vec<f64> = vectors.iter().zip(&others).map(|(a, b)| a.dot(b)).collect();
+        black_box(out);
+    });
+    let soa = time(|| {
+        black_box(batch.dot(&other_batch));
+    });
+    report("dot", aos, soa);
+
+    let aos = time(|| {
+        let out: Vec<Vector3D> = vectors.iter().zip(&others).map(|(a, b)| a.cross(b)).collect();
+        black_box(out);
+    });
+    let soa = time(|| {
+        black_box(batch.cross(&other_batch));
+    });
+    report("cross", aos, soa);
+
+    let aos = time(|| {
+        for v in aos_scratch.iter_mut() {
+            *v = v.normalise();
+        }
+        black_box(&aos_scratch);
+    });
+    let soa = time(|| {
+        soa_scratch.normalise();
+        black_box(&soa_scratch);
+    });
+    report("normalise", aos, soa);
+
+    let aos = time(|| {
+        let mut min = Vector3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut max = -min;
+        for v in &vectors {
+            min = Vector3D::new(min.x.min(v.x), min.y.min(v.y), min.z.min(v.z));
+            max = Vector3D::new(max.x.max(v.x), max.y.max(v.y), max.z.max(v.z));
+        }
+        black_box((min, max));
+    });
+    let soa = time(|| {
+        black_box(batch.bounds());
+    });
+    report("bounds", aos, soa);
+}
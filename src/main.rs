@@ -2,7 +2,7 @@
 
 use std::fs::File;
 
-use game_maths::maths::{Maths, self};
+use game_maths::maths;
 use log::{LevelFilter, info};
 use simplelog::{ColorChoice, CombinedLogger, Config, TermLogger, TerminalMode, WriteLogger};
 fn main() {
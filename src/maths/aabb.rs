@@ -0,0 +1,429 @@
+use super::{Matrix3D, Ray, RayHit, Vector3D};
+
+/// Axis-aligned bounding box stored as its `min` and `max` corners
+///
+/// A box with `min > max` on any axis is empty. `Aabb::empty()` uses
+/// `+inf`/`-inf` corners so that growing it by a point or taking a union just
+/// works, and every query below checks `is_empty` first so empty or inverted
+/// boxes give zero sizes, no containment and infinite distances instead of NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3D,
+    pub max: Vector3D,
+}
+
+impl Default for Aabb {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Build an Aabb around every point of an iterator
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{Aabb, Vector3D};
+///
+/// let aabb: Aabb = vec![Vector3D::new(1.0, -2.0, 3.0), Vector3D::new(-1.0, 2.0, 0.0)].into_iter().collect();
+/// assert_eq!(aabb.min, Vector3D::new(-1.0, -2.0, 0.0));
+/// assert_eq!(aabb.max, Vector3D::new(1.0, 2.0, 3.0));
+/// ```
+impl FromIterator<Vector3D> for Aabb {
+    fn from_iter<I: IntoIterator<Item = Vector3D>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::empty(), |aabb, p| aabb.expand_to_point(&p))
+    }
+}
+
+impl Aabb {
+    /// Creates a new Aabb from its corners
+    ///
+    /// # Arguments
+    /// - `min` - The corner with the smallest coordinates
+    /// - `max` - The corner with the largest coordinates
+    pub fn new(min: Vector3D, max: Vector3D) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates an empty Aabb that contains nothing
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let aabb = Aabb::empty();
+    /// assert!(aabb.is_empty());
+    /// assert_eq!(aabb.volume(), 0.0);
+    /// assert!(!aabb.contains_point(&Vector3D::default()));
+    /// ```
+    pub fn empty() -> Self {
+        Self {
+            min: Vector3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Vector3D::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    /// Creates a new Aabb from its centre and half extents
+    pub fn from_center_half_extents(center: Vector3D, half_extents: Vector3D) -> Self {
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// Creates the tightest Aabb around a set of points, empty if there are none
+    pub fn from_points<I: IntoIterator<Item = Vector3D>>(points: I) -> Self {
+        points.into_iter().collect()
+    }
+
+    /// Returns true if the box contains no points (`min > max` on any axis)
+    pub fn is_empty(&self) -> bool {
+        // Written so that NaN corners also count as empty
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z)
+    }
+
+    /// Returns the smallest box containing both boxes
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let a = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+    /// let b = Aabb::new(Vector3D::new(2.0, -1.0, 0.5), Vector3D::new(3.0, 0.5, 0.75));
+    /// let union = a.union(&b);
+    /// assert_eq!(union.min, Vector3D::new(0.0, -1.0, 0.0));
+    /// assert_eq!(union.max, Vector3D::new(3.0, 1.0, 1.0));
+    /// assert_eq!(a.union(&Aabb::empty()), a);
+    /// ```
+    pub fn union(&self, other: &Aabb) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Self {
+            min: self.min.min(&other.min),
+            max: self.max.max(&other.max),
+        }
+    }
+
+    /// Returns the overlap of two boxes, `Aabb::empty()` if they do not overlap
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let a = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 2.0, 2.0));
+    /// let b = Aabb::new(Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(3.0, 3.0, 3.0));
+    /// assert_eq!(a.intersection(&b), Aabb::new(Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(2.0, 2.0, 2.0)));
+    /// ```
+    pub fn intersection(&self, other: &Aabb) -> Self {
+        let overlap = Self {
+            min: self.min.max(&other.min),
+            max: self.max.min(&other.max),
+        };
+        if overlap.is_empty() {
+            Self::empty()
+        } else {
+            overlap
+        }
+    }
+
+    /// Returns true if the two boxes overlap, touching counts
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns true if `point` is inside the box or on its boundary
+    pub fn contains_point(&self, point: &Vector3D) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x
+            && self.min.y <= point.y && point.y <= self.max.y
+            && self.min.z <= point.z && point.z <= self.max.z
+    }
+
+    /// Returns true if `other` lies entirely inside this box
+    ///
+    /// The empty box is contained by every box, and contains only other
+    /// empty boxes.
+    pub fn contains(&self, other: &Aabb) -> bool {
+        if other.is_empty() {
+            return true;
+        }
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Returns the box grown by `margin` on every side
+    ///
+    /// A negative margin shrinks the box and may leave it empty. Empty boxes
+    /// stay empty.
+    pub fn expand(&self, margin: f64) -> Self {
+        if self.is_empty() {
+            return Self::empty();
+        }
+        let margin = Vector3D::new(margin, margin, margin);
+        let expanded = Self {
+            min: self.min - margin,
+            max: self.max + margin,
+        };
+        if expanded.is_empty() {
+            Self::empty()
+        } else {
+            expanded
+        }
+    }
+
+    /// Returns the smallest box containing this box and `point`
+    pub fn expand_to_point(&self, point: &Vector3D) -> Self {
+        if self.is_empty() {
+            return Self::new(*point, *point);
+        }
+        Self {
+            min: self.min.min(point),
+            max: self.max.max(point),
+        }
+    }
+
+    /// Returns the centre of the box, the origin if the box is empty
+    pub fn center(&self) -> Vector3D {
+        if self.is_empty() {
+            return Vector3D::default();
+        }
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the size of the box along each axis, zero if the box is empty
+    pub fn extents(&self) -> Vector3D {
+        if self.is_empty() {
+            return Vector3D::default();
+        }
+        self.max - self.min
+    }
+
+    /// Returns half the size of the box along each axis, zero if the box is empty
+    pub fn half_extents(&self) -> Vector3D {
+        self.extents() * 0.5
+    }
+
+    /// Returns the surface area of the box
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 2.0, 3.0));
+    /// assert_eq!(aabb.surface_area(), 22.0);
+    /// assert_eq!(aabb.volume(), 6.0);
+    /// ```
+    pub fn surface_area(&self) -> f64 {
+        let e = self.extents();
+        2.0 * (e.x * e.y + e.y * e.z + e.z * e.x)
+    }
+
+    /// Returns the volume of the box
+    pub fn volume(&self) -> f64 {
+        let e = self.extents();
+        e.x * e.y * e.z
+    }
+
+    /// Returns the point in the box closest to `point`, or `None` if the box is empty
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+    /// assert_eq!(aabb.closest_point(&Vector3D::new(2.0, 0.5, -1.0)), Some(Vector3D::new(1.0, 0.5, 0.0)));
+    /// assert_eq!(Aabb::empty().closest_point(&Vector3D::default()), None);
+    /// ```
+    pub fn closest_point(&self, point: &Vector3D) -> Option<Vector3D> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(point.max(&self.min).min(&self.max))
+    }
+
+    /// Returns the squared distance from `point` to the box, zero inside and
+    /// infinite if the box is empty
+    pub fn distance_squared(&self, point: &Vector3D) -> f64 {
+        self.closest_point(point)
+            .map_or(f64::INFINITY, |closest| (closest - *point).magnitude_squared())
+    }
+
+    /// Returns the distance from `point` to the box, zero inside and infinite
+    /// if the box is empty
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Vector3D};
+    ///
+    /// let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+    /// assert_eq!(aabb.distance(&Vector3D::new(4.0, 5.0, 0.5)), 5.0);
+    /// assert_eq!(aabb.distance(&Vector3D::new(0.5, 0.5, 0.5)), 0.0);
+    /// ```
+    pub fn distance(&self, point: &Vector3D) -> f64 {
+        self.distance_squared(point).sqrt()
+    }
+
+    /// Returns the tightest Aabb around this box after `p -> m * p + translation`
+    ///
+    /// Uses Arvo's method: each output axis takes the smaller (or larger) of
+    /// the two products per matrix element, which is exactly the bound of the
+    /// eight transformed corners without transforming them.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Matrix3D, Vector3D};
+    ///
+    /// // 90 degree rotation about z
+    /// let m = Matrix3D::new(0.0, -1.0, 0.0,
+    ///                       1.0, 0.0, 0.0,
+    ///                       0.0, 0.0, 1.0);
+    /// let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 1.0, 1.0));
+    /// let moved = aabb.transform(&m, &Vector3D::new(10.0, 0.0, 0.0));
+    /// assert_eq!(moved.min, Vector3D::new(9.0, 0.0, 0.0));
+    /// assert_eq!(moved.max, Vector3D::new(10.0, 2.0, 1.0));
+    /// ```
+    pub fn transform(&self, m: &Matrix3D, translation: &Vector3D) -> Self {
+        if self.is_empty() {
+            return Self::empty();
+        }
+        let mut min = *translation;
+        let mut max = *translation;
+        for i in 0..3 {
+            for j in 0..3 {
+                let a = m[(i, j)] * self.min[j];
+                let b = m[(i, j)] * self.max[j];
+                min[i] += a.min(b);
+                max[i] += a.max(b);
+            }
+        }
+        Self { min, max }
+    }
+
+    /// Intersects a ray with the box, see `Ray::intersect_aabb`
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<RayHit> {
+        if self.is_empty() {
+            return None;
+        }
+        ray.intersect_aabb(&self.min, &self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit() -> Aabb {
+        Aabb::new(Vector3D::new(-1.0, -1.0, -1.0), Vector3D::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn test_aabb_from_points() {
+        let points = [
+            Vector3D::new(1.0, 2.0, 3.0),
+            Vector3D::new(-4.0, 0.5, 2.0),
+            Vector3D::new(0.0, -1.0, 7.0),
+        ];
+        let aabb = Aabb::from_points(points);
+        assert_eq!(aabb.min, Vector3D::new(-4.0, -1.0, 2.0));
+        assert_eq!(aabb.max, Vector3D::new(1.0, 2.0, 7.0));
+        for p in &points {
+            assert!(aabb.contains_point(p));
+        }
+
+        let single = Aabb::from_points([Vector3D::new(1.0, 2.0, 3.0)]);
+        assert!(!single.is_empty());
+        assert_eq!(single.volume(), 0.0);
+
+        assert!(Aabb::from_points(Vec::new()).is_empty());
+        assert_eq!(Aabb::default(), Aabb::empty());
+    }
+
+    #[test]
+    fn test_aabb_set_operations() {
+        let a = unit();
+        let b = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(3.0, 3.0, 3.0));
+        let far = Aabb::new(Vector3D::new(5.0, 5.0, 5.0), Vector3D::new(6.0, 6.0, 6.0));
+
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&far));
+        assert!(a.intersection(&far).is_empty());
+        assert_eq!(a.intersection(&b).volume(), 1.0);
+
+        let union = a.union(&b);
+        assert!(union.contains(&a) && union.contains(&b));
+        assert!(!a.contains(&b));
+        assert!(a.contains(&Aabb::empty()));
+        assert!(!Aabb::empty().contains(&a));
+
+        // Touching boxes overlap
+        let touching = Aabb::new(Vector3D::new(1.0, -1.0, -1.0), Vector3D::new(2.0, 1.0, 1.0));
+        assert!(a.intersects(&touching));
+    }
+
+    #[test]
+    fn test_aabb_measures() {
+        let a = Aabb::new(Vector3D::new(1.0, 2.0, 3.0), Vector3D::new(3.0, 6.0, 9.0));
+        assert_eq!(a.center(), Vector3D::new(2.0, 4.0, 6.0));
+        assert_eq!(a.extents(), Vector3D::new(2.0, 4.0, 6.0));
+        assert_eq!(a.half_extents(), Vector3D::new(1.0, 2.0, 3.0));
+        assert_eq!(a.volume(), 48.0);
+        assert_eq!(a.surface_area(), 2.0 * (8.0 + 24.0 + 12.0));
+
+        let grown = a.expand(1.0);
+        assert_eq!(grown.extents(), Vector3D::new(4.0, 6.0, 8.0));
+        assert!(a.expand(-5.0).is_empty());
+
+        let outside = Vector3D::new(0.0, 0.0, 3.0);
+        assert_eq!(a.closest_point(&outside), Some(Vector3D::new(1.0, 2.0, 3.0)));
+        assert_eq!(a.distance_squared(&outside), 5.0);
+    }
+
+    #[test]
+    fn test_aabb_empty_and_inverted() {
+        let inverted = Aabb::new(Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(-1.0, 1.0, 1.0));
+        for aabb in [Aabb::empty(), inverted] {
+            assert!(aabb.is_empty());
+            assert_eq!(aabb.center(), Vector3D::default());
+            assert_eq!(aabb.extents(), Vector3D::default());
+            assert_eq!(aabb.surface_area(), 0.0);
+            assert_eq!(aabb.volume(), 0.0);
+            assert_eq!(aabb.distance(&Vector3D::default()), f64::INFINITY);
+            assert!(aabb.expand(1.0).is_empty());
+            assert!(aabb.transform(&Matrix3D::identity(), &Vector3D::default()).is_empty());
+            assert_eq!(aabb.union(&unit()), unit());
+            assert!(!aabb.intersects(&unit()));
+            assert_eq!(aabb.expand_to_point(&Vector3D::default()), Aabb::new(Vector3D::default(), Vector3D::default()));
+        }
+    }
+
+    #[test]
+    fn test_aabb_transform_is_tight() {
+        let aabb = Aabb::new(Vector3D::new(-1.0, 0.0, 2.0), Vector3D::new(2.0, 1.0, 4.0));
+        let m = Matrix3D::new(0.36, 0.48, -0.8,
+                              -0.8, 0.6, 0.0,
+                              0.48, 0.64, 0.6);
+        let t = Vector3D::new(5.0, -3.0, 1.0);
+        let moved = aabb.transform(&m, &t);
+
+        // Same as bounding the eight transformed corners
+        let corners = (0..8).map(|i| {
+            let corner = Vector3D::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            );
+            m * corner + t
+        });
+        let expected = Aabb::from_points(corners);
+        assert!((moved.min - expected.min).magnitude() < 1e-12);
+        assert!((moved.max - expected.max).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_aabb_ray() {
+        let ray = Ray::new(Vector3D::new(-5.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(unit().intersect_ray(&ray).unwrap().distance, 4.0);
+        assert!(Aabb::empty().intersect_ray(&ray).is_none());
+    }
+}
@@ -0,0 +1,318 @@
+use super::{Curve, Vector3D};
+
+/// Nodes of 5-point Gauss-Legendre quadrature on `-1..1`
+const GAUSS_NODES: [f64; 5] = [
+    0.0,
+    -0.538_469_310_105_683_1,
+    0.538_469_310_105_683_1,
+    -0.906_179_845_938_664,
+    0.906_179_845_938_664,
+];
+
+/// Weights matching `GAUSS_NODES`
+const GAUSS_WEIGHTS: [f64; 5] = [
+    0.568_888_888_888_888_9,
+    0.478_628_670_499_366_5,
+    0.478_628_670_499_366_5,
+    0.236_926_885_056_189_1,
+    0.236_926_885_056_189_1,
+];
+
+/// Deepest bisection of an interval while integrating
+const MAX_DEPTH: u32 = 24;
+
+/// Returns the length of a curve between two parameters
+///
+/// Integrates the speed with 5-point Gauss-Legendre quadrature, halving
+/// intervals until both halves agree with the whole to within `tolerance`.
+/// The range is split at the curve's breakpoints first, since a jump in
+/// speed can fool the error estimate. The result is negative if
+/// `end < start`.
+///
+/// # Arguments
+/// - `curve` - The curve to measure
+/// - `start` - The parameter to measure from
+/// - `end` - The parameter to measure to
+/// - `tolerance` - The absolute error allowed
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{arc_length, CubicBezier, Vector3D};
+///
+/// // A straight line with uneven control points still measures its chord
+/// let line = CubicBezier::new(
+///     Vector3D::new(0.0, 0.0, 0.0),
+///     Vector3D::new(0.5, 0.0, 0.0),
+///     Vector3D::new(0.6, 0.0, 0.0),
+///     Vector3D::new(5.0, 0.0, 0.0),
+/// );
+/// assert!((arc_length(&line, 0.0, 1.0, 1e-12) - 5.0).abs() < 1e-12);
+/// ```
+pub fn arc_length<C: Curve + ?Sized>(curve: &C, start: f64, end: f64, tolerance: f64) -> f64 {
+    if end < start {
+        return -arc_length(curve, end, start, tolerance);
+    }
+    let mut bounds = vec![start];
+    bounds.extend(curve.breakpoints().into_iter().filter(|&b| b > start && b < end));
+    bounds.push(end);
+    let tolerance = tolerance.max(f64::EPSILON) / (bounds.len() - 1) as f64;
+    bounds
+        .windows(2)
+        .map(|pair| adaptive(curve, pair[0], pair[1], gauss_legendre(curve, pair[0], pair[1]), tolerance, 0))
+        .sum()
+}
+
+fn gauss_legendre<C: Curve + ?Sized>(curve: &C, start: f64, end: f64) -> f64 {
+    let half = (end - start) * 0.5;
+    let middle = (end + start) * 0.5;
+    GAUSS_NODES
+        .iter()
+        .zip(GAUSS_WEIGHTS)
+        .map(|(x, w)| w * curve.derivative(middle + half * x).magnitude())
+        .sum::<f64>()
+        * half
+}
+
+fn adaptive<C: Curve + ?Sized>(curve: &C, start: f64, end: f64, whole: f64, tolerance: f64, depth: u32) -> f64 {
+    let middle = (start + end) * 0.5;
+    let left = gauss_legendre(curve, start, middle);
+    let right = gauss_legendre(curve, middle, end);
+    if depth >= MAX_DEPTH || (left + right - whole).abs() <= tolerance {
+        return left + right;
+    }
+    adaptive(curve, start, middle, left, tolerance * 0.5, depth + 1) + adaptive(curve, middle, end, right, tolerance * 0.5, depth + 1)
+}
+
+/// Cumulative arc lengths of a curve for constant-speed traversal
+///
+/// The table stores the length up to evenly spaced parameters across the
+/// curve's domain. Lookups from distance to parameter start from the
+/// table and refine with safeguarded Newton iterations on the exact
+/// integral, so they are accurate to the table's tolerance however coarse
+/// the table is; more samples just mean fewer iterations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable<C: Curve> {
+    curve: C,
+    parameters: Vec<f64>,
+    lengths: Vec<f64>,
+    tolerance: f64,
+}
+
+impl<C: Curve> ArcLengthTable<C> {
+    /// Builds the table for a curve
+    ///
+    /// # Arguments
+    /// - `curve` - The curve, which can be a reference
+    /// - `samples` - The number of intervals the domain is split into, at least one
+    /// - `tolerance` - The absolute length error allowed over the whole curve
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{ArcLengthTable, CubicBezier, Curve, Vector3D};
+    ///
+    /// let curve = CubicBezier::new(
+    ///     Vector3D::new(0.0, 0.0, 0.0),
+    ///     Vector3D::new(0.0, 4.0, 0.0),
+    ///     Vector3D::new(8.0, 4.0, 0.0),
+    ///     Vector3D::new(8.0, 0.0, 0.0),
+    /// );
+    /// let table = ArcLengthTable::new(&curve, 16, 1e-10);
+    /// // The curve is symmetric, so half the length is reached half way
+    /// let t = table.parameter_at(table.length() * 0.5);
+    /// assert!((t - 0.5).abs() < 1e-9);
+    /// let points = table.resample(5);
+    /// assert_eq!(points[0], curve.position(0.0));
+    /// ```
+    pub fn new(curve: C, samples: usize, tolerance: f64) -> Self {
+        let samples = samples.max(1);
+        let (start, end) = curve.domain();
+        let parameters: Vec<f64> = (0..=samples).map(|i| start + (end - start) * i as f64 / samples as f64).collect();
+        let tolerance = tolerance.max(f64::EPSILON);
+        let mut lengths = Vec::with_capacity(parameters.len());
+        lengths.push(0.0);
+        for pair in parameters.windows(2) {
+            let step = arc_length(&curve, pair[0], pair[1], tolerance / samples as f64);
+            lengths.push(lengths[lengths.len() - 1] + step);
+        }
+        Self { curve, parameters, lengths, tolerance }
+    }
+
+    /// Returns the curve the table measures
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the total length of the curve
+    pub fn length(&self) -> f64 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Returns the length from the start of the domain to parameter `t`
+    pub fn length_at(&self, t: f64) -> f64 {
+        let (start, end) = self.curve.domain();
+        let t = t.clamp(start, end);
+        let index = self.interval_of(&self.parameters, t);
+        self.lengths[index] + arc_length(&self.curve, self.parameters[index], t, self.tolerance / self.lengths.len() as f64)
+    }
+
+    /// Returns the parameter at `distance` along the curve
+    ///
+    /// Distances outside `0..length()` clamp to the ends of the domain.
+    pub fn parameter_at(&self, distance: f64) -> f64 {
+        let (start, end) = self.curve.domain();
+        if distance <= 0.0 {
+            return start;
+        }
+        if distance >= self.length() {
+            return end;
+        }
+        let index = self.interval_of(&self.lengths, distance);
+        let (mut low, mut high) = (self.parameters[index], self.parameters[index + 1]);
+        let base = self.lengths[index];
+        let span = self.lengths[index + 1] - base;
+        let target = distance - base;
+
+        // Newton from the linear guess, falling back to bisection whenever
+        // a step leaves the bracket or the curve stalls
+        let mut t = if span > 0.0 { low + (high - low) * target / span } else { low };
+        let tolerance = self.tolerance / self.lengths.len() as f64;
+        for _ in 0..64 {
+            let error = arc_length(&self.curve, self.parameters[index], t, tolerance) - target;
+            if error.abs() <= self.tolerance {
+                break;
+            }
+            if error > 0.0 {
+                high = t;
+            } else {
+                low = t;
+            }
+            let speed = self.curve.derivative(t).magnitude();
+            let newton = t - error / speed;
+            t = if speed > 0.0 && newton > low && newton < high { newton } else { (low + high) * 0.5 };
+        }
+        t
+    }
+
+    /// Returns the point at `distance` along the curve
+    pub fn position_at(&self, distance: f64) -> Vector3D {
+        self.curve.position(self.parameter_at(distance))
+    }
+
+    /// Returns `count` points spaced evenly by arc length, including both ends
+    pub fn resample(&self, count: usize) -> Vec<Vector3D> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![self.curve.position(self.curve.domain().0)],
+            _ => (0..count).map(|i| self.position_at(self.length() * i as f64 / (count - 1) as f64)).collect(),
+        }
+    }
+
+    /// The interval of a sorted table containing `value`
+    fn interval_of(&self, table: &[f64], value: f64) -> usize {
+        table.partition_point(|&v| v <= value).clamp(1, table.len() - 1) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::curves::testing::Helix;
+    use crate::maths::{CatmullRom, CatmullRomKind};
+    use std::f64::consts::PI;
+
+    /// A unit circle traced with speed growing along it
+    struct AcceleratingCircle;
+
+    impl Curve for AcceleratingCircle {
+        fn position(&self, t: f64) -> Vector3D {
+            let angle = PI * t * t;
+            Vector3D::new(angle.cos(), angle.sin(), 0.0)
+        }
+
+        fn derivative(&self, t: f64) -> Vector3D {
+            let angle = PI * t * t;
+            Vector3D::new(-angle.sin(), angle.cos(), 0.0) * (2.0 * PI * t)
+        }
+
+        fn second_derivative(&self, t: f64) -> Vector3D {
+            let angle = PI * t * t;
+            let w = 2.0 * PI * t;
+            Vector3D::new(-angle.sin(), angle.cos(), 0.0) * (2.0 * PI) - Vector3D::new(angle.cos(), angle.sin(), 0.0) * (w * w)
+        }
+
+        fn domain(&self) -> (f64, f64) {
+            (0.0, 2.0)
+        }
+    }
+
+    #[test]
+    fn test_arc_length_error_bound() {
+        // Constant speed sqrt(radius^2 + 1) over four turns
+        let helix_length = 8.0 * PI * 5.0_f64.sqrt();
+        for tolerance in [1e-3, 1e-6, 1e-10] {
+            assert!((arc_length(&AcceleratingCircle, 0.0, 2.0, tolerance) - 4.0 * PI).abs() <= tolerance);
+            assert!((arc_length(&Helix, 0.0, 8.0 * PI, tolerance) - helix_length).abs() <= tolerance);
+            for samples in [1, 7, 64] {
+                let table = ArcLengthTable::new(AcceleratingCircle, samples, tolerance);
+                assert!((table.length() - 4.0 * PI).abs() <= tolerance);
+            }
+        }
+        assert!((arc_length(&Helix, 8.0 * PI, 0.0, 1e-10) + helix_length).abs() <= 1e-10);
+    }
+
+    #[test]
+    fn test_arc_length_inverse_lookup() {
+        let table = ArcLengthTable::new(AcceleratingCircle, 8, 1e-10);
+        for i in 0..=40 {
+            let distance = 4.0 * PI * i as f64 / 40.0;
+            let t = table.parameter_at(distance);
+            // The angle travelled is the distance on a unit circle
+            assert!((PI * t * t - distance).abs() < 1e-9, "distance {}", distance);
+            assert!((table.length_at(t) - distance).abs() < 1e-9);
+        }
+        assert_eq!(table.parameter_at(-1.0), 0.0);
+        assert_eq!(table.parameter_at(100.0), 2.0);
+
+        // Stalls at the start, where the speed is zero, are handled
+        let t = table.parameter_at(1e-6);
+        assert!((PI * t * t - 1e-6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_length_resample() {
+        let table = ArcLengthTable::new(&Helix, 32, 1e-10);
+        let points = table.resample(101);
+        assert_eq!(points.len(), 101);
+        assert_eq!(points[0], Helix.position(0.0));
+        assert!((points[100] - Helix.position(8.0 * PI)).magnitude() < 1e-12);
+        // Equal arcs of a helix have equal chords
+        let chord = (points[1] - points[0]).magnitude();
+        for pair in points.windows(2) {
+            assert!(((pair[1] - pair[0]).magnitude() - chord).abs() < 1e-9);
+        }
+
+        // A spline with very uneven spacing still resamples evenly
+        let spline = CatmullRom::new(
+            vec![
+                Vector3D::new(0.0, 0.0, 0.0),
+                Vector3D::new(1.0, 0.0, 0.0),
+                Vector3D::new(1.1, 0.2, 0.0),
+                Vector3D::new(6.0, 3.0, 1.0),
+                Vector3D::new(7.0, 3.0, 1.0),
+            ],
+            CatmullRomKind::Centripetal,
+        )
+        .unwrap();
+        let table = ArcLengthTable::new(&spline, 7, 1e-9);
+        let step = table.length() / 20.0;
+        let mut previous = table.parameter_at(0.0);
+        for i in 1..=20 {
+            let t = table.parameter_at(step * i as f64);
+            assert!(t > previous);
+            assert!((arc_length(&spline, previous, t, 1e-10) - step).abs() < 1e-8);
+            previous = t;
+        }
+        assert!(table.resample(0).is_empty());
+        assert_eq!(table.resample(1), vec![spline.position(0.0)]);
+    }
+}
@@ -0,0 +1,418 @@
+use super::{Aabb, Matrix3D, Vector3D};
+
+/// Structure-of-arrays container for many Vector3Ds
+///
+/// Each component is stored in its own contiguous array so that bulk
+/// operations walk three linear streams of `f64` instead of striding over
+/// `Vector3D` structs. The loops below are written over zipped slices with
+/// no branches so the compiler can auto-vectorise them.
+///
+/// ```md
+/// x: [ x0 x1 x2 ... ]
+/// y: [ y0 y1 y2 ... ]
+/// z: [ z0 z1 z2 ... ]
+/// ```
+///
+/// The arrays are private so they always have the same length, use the
+/// `x`, `y` and `z` accessors to read them as slices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vector3DBatch {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<f64>,
+}
+
+/// Convert from a Vec of Vector3Ds to a Vector3DBatch
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{Vector3D, Vector3DBatch};
+///
+/// let batch = Vector3DBatch::from(vec![Vector3D::new(1.0, 2.0, 3.0)]);
+/// assert_eq!(batch.x(), &[1.0]);
+/// assert_eq!(batch.y(), &[2.0]);
+/// assert_eq!(batch.z(), &[3.0]);
+/// ```
+impl From<Vec<Vector3D>> for Vector3DBatch {
+    fn from(vectors: Vec<Vector3D>) -> Self {
+        Self::from_slice(&vectors)
+    }
+}
+
+impl From<&[Vector3D]> for Vector3DBatch {
+    fn from(vectors: &[Vector3D]) -> Self {
+        Self::from_slice(vectors)
+    }
+}
+
+/// Convert from a Vector3DBatch back to a Vec of Vector3Ds
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{Vector3D, Vector3DBatch};
+///
+/// let vectors = vec![Vector3D::new(1.0, 2.0, 3.0), Vector3D::new(4.0, 5.0, 6.0)];
+/// let batch = Vector3DBatch::from(vectors.clone());
+/// let back: Vec<Vector3D> = batch.into();
+/// assert_eq!(back, vectors);
+/// ```
+impl From<Vector3DBatch> for Vec<Vector3D> {
+    fn from(batch: Vector3DBatch) -> Self {
+        batch.to_vec()
+    }
+}
+
+impl FromIterator<Vector3D> for Vector3DBatch {
+    fn from_iter<I: IntoIterator<Item = Vector3D>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        for v in iter {
+            batch.push(v);
+        }
+        batch
+    }
+}
+
+impl Vector3DBatch {
+    /// Creates a new empty Vector3DBatch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty Vector3DBatch with room for `capacity` vectors
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            x: Vec::with_capacity(capacity),
+            y: Vec::with_capacity(capacity),
+            z: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a new Vector3DBatch from a slice of Vector3Ds
+    ///
+    /// # Arguments
+    /// - `vectors` - The vectors to split into component arrays
+    pub fn from_slice(vectors: &[Vector3D]) -> Self {
+        Self {
+            x: vectors.iter().map(|v| v.x).collect(),
+            y: vectors.iter().map(|v| v.y).collect(),
+            z: vectors.iter().map(|v| v.z).collect(),
+        }
+    }
+
+    /// Returns the batch as a Vec of Vector3Ds
+    pub fn to_vec(&self) -> Vec<Vector3D> {
+        self.x
+            .iter()
+            .zip(&self.y)
+            .zip(&self.z)
+            .map(|((&x, &y), &z)| Vector3D::new(x, y, z))
+            .collect()
+    }
+
+    /// Returns the x components of every vector
+    pub fn x(&self) -> &[f64] {
+        &self.x
+    }
+
+    /// Returns the y components of every vector
+    pub fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    /// Returns the z components of every vector
+    pub fn z(&self) -> &[f64] {
+        &self.z
+    }
+
+    /// Returns the number of vectors in the batch
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Returns true if the batch holds no vectors
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    /// Appends a Vector3D to the end of the batch
+    pub fn push(&mut self, v: Vector3D) {
+        self.x.push(v.x);
+        self.y.push(v.y);
+        self.z.push(v.z);
+    }
+
+    /// Returns the Vector3D at `index`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Vector3D, Vector3DBatch};
+    ///
+    /// let batch = Vector3DBatch::from(vec![Vector3D::new(1.0, 2.0, 3.0)]);
+    /// assert_eq!(batch.get(0), Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn get(&self, index: usize) -> Vector3D {
+        Vector3D::new(self.x[index], self.y[index], self.z[index])
+    }
+
+    /// Overwrites the Vector3D at `index`
+    pub fn set(&mut self, index: usize, v: Vector3D) {
+        self.x[index] = v.x;
+        self.y[index] = v.y;
+        self.z[index] = v.z;
+    }
+
+    /// Transforms every vector in place by a Matrix3D (`v = m * v`)
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Vector3D, Vector3DBatch};
+    ///
+    /// let m = Matrix3D::index_test();
+    /// let mut batch = Vector3DBatch::from(vec![Vector3D::new(1.0, 2.0, 3.0)]);
+    /// batch.transform(&m);
+    /// assert_eq!(batch.get(0), m * Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn transform(&mut self, m: &Matrix3D) {
+        // Pull the matrix into locals so the loop body is pure arithmetic
+        let (m00, m01, m02) = (m[(0, 0)], m[(0, 1)], m[(0, 2)]);
+        let (m10, m11, m12) = (m[(1, 0)], m[(1, 1)], m[(1, 2)]);
+        let (m20, m21, m22) = (m[(2, 0)], m[(2, 1)], m[(2, 2)]);
+
+        for ((x, y), z) in self.x.iter_mut().zip(self.y.iter_mut()).zip(self.z.iter_mut()) {
+            let (vx, vy, vz) = (*x, *y, *z);
+            *x = m00 * vx + m01 * vy + m02 * vz;
+            *y = m10 * vx + m11 * vy + m12 * vz;
+            *z = m20 * vx + m21 * vy + m22 * vz;
+        }
+    }
+
+    /// Returns a copy of the batch transformed by a Matrix3D
+    pub fn transformed(&self, m: &Matrix3D) -> Self {
+        let mut batch = self.clone();
+        batch.transform(m);
+        batch
+    }
+
+    /// Returns the element-wise dot products of two batches
+    ///
+    /// # Panics
+    /// Panics if the batches have different lengths
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Vector3D, Vector3DBatch};
+    ///
+    /// let a = Vector3DBatch::from(vec![Vector3D::new(1.0, 2.0, 3.0)]);
+    /// let b = Vector3DBatch::from(vec![Vector3D::new(1.0, 2.0, 3.0)]);
+    /// assert_eq!(a.dot(&b), vec![14.0]);
+    /// ```
+    pub fn dot(&self, other: &Self) -> Vec<f64> {
+        assert_eq!(self.len(), other.len(), "Vector3DBatch length mismatch");
+        let n = self.len();
+        // Re-slice everything to `n` so the bounds checks are hoisted out of the loop
+        let (ax, ay, az) = (&self.x[..n], &self.y[..n], &self.z[..n]);
+        let (bx, by, bz) = (&other.x[..n], &other.y[..n], &other.z[..n]);
+        let mut out = vec![0.0; n];
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = ax[i] * bx[i] + ay[i] * by[i] + az[i] * bz[i];
+        }
+        out
+    }
+
+    /// Returns the element-wise cross products of two batches
+    ///
+    /// # Panics
+    /// Panics if the batches have different lengths
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Vector3D, Vector3DBatch};
+    ///
+    /// let a = Vector3DBatch::from(vec![Vector3D::new(1.0, 0.0, 0.0)]);
+    /// let b = Vector3DBatch::from(vec![Vector3D::new(0.0, 1.0, 0.0)]);
+    /// assert_eq!(a.cross(&b).get(0), Vector3D::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn cross(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len(), "Vector3DBatch length mismatch");
+        let n = self.len();
+        let (ax, ay, az) = (&self.x[..n], &self.y[..n], &self.z[..n]);
+        let (bx, by, bz) = (&other.x[..n], &other.y[..n], &other.z[..n]);
+        // One pass per output component keeps each loop to four input streams
+        Self {
+            x: cross_component(ay, bz, az, by),
+            y: cross_component(az, bx, ax, bz),
+            z: cross_component(ax, by, ay, bx),
+        }
+    }
+
+    /// Returns the magnitude of every vector in the batch
+    pub fn magnitudes(&self) -> Vec<f64> {
+        self.x
+            .iter()
+            .zip(&self.y)
+            .zip(&self.z)
+            .map(|((x, y), z)| (x * x + y * y + z * z).sqrt())
+            .collect()
+    }
+
+    /// Normalises every vector in place
+    ///
+    /// Matches `Vector3D::normalise`, so zero length vectors become NaN.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Vector3D, Vector3DBatch};
+    ///
+    /// let mut batch = Vector3DBatch::from(vec![Vector3D::new(2.0, 2.0, 1.0)]);
+    /// batch.normalise();
+    /// assert_eq!(batch.get(0), Vector3D::new(2.0, 2.0, 1.0).normalise());
+    /// ```
+    pub fn normalise(&mut self) {
+        let magnitudes = self.magnitudes();
+        for (x, m) in self.x.iter_mut().zip(&magnitudes) {
+            *x /= m;
+        }
+        for (y, m) in self.y.iter_mut().zip(&magnitudes) {
+            *y /= m;
+        }
+        for (z, m) in self.z.iter_mut().zip(&magnitudes) {
+            *z /= m;
+        }
+    }
+
+    /// Returns the component-wise minimum and maximum of the batch, or `None`
+    /// if the batch is empty
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Vector3D, Vector3DBatch};
+    ///
+    /// let batch = Vector3DBatch::from(vec![
+    ///     Vector3D::new(1.0, -2.0, 3.0),
+    ///     Vector3D::new(-1.0, 2.0, 0.0),
+    /// ]);
+    /// let (min, max) = batch.bounds().unwrap();
+    /// assert_eq!(min, Vector3D::new(-1.0, -2.0, 0.0));
+    /// assert_eq!(max, Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn bounds(&self) -> Option<(Vector3D, Vector3D)> {
+        if self.is_empty() {
+            return None;
+        }
+        let (min_x, max_x) = min_max(&self.x);
+        let (min_y, max_y) = min_max(&self.y);
+        let (min_z, max_z) = min_max(&self.z);
+        Some((
+            Vector3D::new(min_x, min_y, min_z),
+            Vector3D::new(max_x, max_y, max_z),
+        ))
+    }
+
+    /// Returns the Aabb around every vector in the batch (empty for an empty batch)
+    pub fn aabb(&self) -> Aabb {
+        match self.bounds() {
+            Some((min, max)) => Aabb::new(min, max),
+            None => Aabb::empty(),
+        }
+    }
+}
+
+/// Computes `a * b - c * d` element-wise, one component of a batched cross product
+fn cross_component(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> Vec<f64> {
+    a.iter()
+        .zip(b)
+        .zip(c.iter().zip(d))
+        .map(|((a, b), (c, d))| a * b - c * d)
+        .collect()
+}
+
+/// Min / max reduction over a single component stream
+fn min_max(values: &[f64]) -> (f64, f64) {
+    values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_vectors(n: usize) -> Vec<Vector3D> {
+        (0..n)
+            .map(|i| {
+                let f = i as f64;
+                Vector3D::new(f.sin() * 3.0, f.cos() * 2.0 + 1.0, f * 0.25 - 4.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let vectors = sample_vectors(37);
+        let batch = Vector3DBatch::from(vectors.clone());
+        assert_eq!(batch.len(), 37);
+        assert_eq!(batch.to_vec(), vectors);
+
+        let collected: Vector3DBatch = vectors.iter().copied().collect();
+        assert_eq!(collected, batch);
+
+        let empty = Vector3DBatch::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.bounds(), None);
+        assert!(empty.aabb().is_empty());
+    }
+
+    #[test]
+    fn test_batch_transform_matches_aos() {
+        let vectors = sample_vectors(100);
+        let m = Matrix3D::new(0.5, -1.0, 2.0,
+                              3.0, 0.25, -0.5,
+                              1.5, 2.0, 1.0);
+        let batch = Vector3DBatch::from(vectors.clone()).transformed(&m);
+        for (i, v) in vectors.iter().enumerate() {
+            assert_eq!(batch.get(i), m * *v);
+        }
+    }
+
+    #[test]
+    fn test_batch_dot_cross_normalise() {
+        let a = sample_vectors(50);
+        let b: Vec<Vector3D> = a.iter().map(|v| Vector3D::new(v.z, v.x, v.y)).collect();
+        let batch_a = Vector3DBatch::from(a.clone());
+        let batch_b = Vector3DBatch::from(b.clone());
+
+        let dots = batch_a.dot(&batch_b);
+        let crosses = batch_a.cross(&batch_b);
+        for i in 0..a.len() {
+            assert_eq!(dots[i], a[i].dot(&b[i]));
+            assert_eq!(crosses.get(i), a[i].cross(&b[i]));
+        }
+
+        let mut normalised = batch_a.clone();
+        normalised.normalise();
+        for (i, v) in a.iter().enumerate() {
+            assert_eq!(normalised.get(i), v.normalise());
+            assert_eq!(batch_a.magnitudes()[i], v.magnitude());
+        }
+    }
+
+    #[test]
+    fn test_batch_bounds() {
+        let vectors = sample_vectors(64);
+        let batch = Vector3DBatch::from(vectors.clone());
+        let (min, max) = batch.bounds().unwrap();
+        for v in &vectors {
+            assert!(v.x >= min.x && v.y >= min.y && v.z >= min.z);
+            assert!(v.x <= max.x && v.y <= max.y && v.z <= max.z);
+        }
+        assert_eq!(batch.aabb(), Aabb::from_points(vectors.iter().copied()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_length_mismatch() {
+        let a = Vector3DBatch::from(sample_vectors(2));
+        let b = Vector3DBatch::from(sample_vectors(3));
+        a.dot(&b);
+    }
+}
@@ -0,0 +1,219 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::{Matrix3D, Trivector3D, Vector3D};
+
+/// Bivector in 3D Grassmann algebra
+///
+/// Components are on the basis `e23`, `e31`, `e12`, ordered so that
+/// `a.wedge(&b)` has the same components as `a.cross(&b)`. Unlike the cross
+/// product vector, a bivector is an oriented area and transforms with the
+/// cofactor matrix rather than the matrix itself, so it stays correct under
+/// non-uniform scale and shear.
+///
+/// ```md
+/// x -> e23 (yz plane)
+/// y -> e31 (zx plane)
+/// z -> e12 (xy plane)
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bivector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Convert a Bivector3D to the cross-product Vector3D with the same components
+///
+/// # Example
+/// ```
+/// use game_maths::maths::Vector3D;
+///
+/// let a = Vector3D::new(1.0, 0.0, 0.0);
+/// let b = Vector3D::new(0.0, 1.0, 0.0);
+/// let v: Vector3D = a.wedge(&b).into();
+/// assert_eq!(v, a.cross(&b));
+/// ```
+impl From<Bivector3D> for Vector3D {
+    fn from(b: Bivector3D) -> Self {
+        b.complement()
+    }
+}
+
+impl Add for Bivector3D {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Bivector3D {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Bivector3D {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Bivector3D {
+    type Output = Self;
+    fn mul(self, s: f64) -> Self::Output {
+        Self::new(self.x * s, self.y * s, self.z * s)
+    }
+}
+
+impl Bivector3D {
+    /// Creates a new Bivector3D
+    ///
+    /// # Arguments
+    /// - `x` - The `e23` component
+    /// - `y` - The `e31` component
+    /// - `z` - The `e12` component
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the magnitude (area) of the Bivector3D
+    pub fn magnitude(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Returns the complement, the Vector3D perpendicular to the bivector's plane
+    ///
+    /// With the Euclidean metric this is also the Hodge dual.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Bivector3D, Vector3D};
+    ///
+    /// assert_eq!(Bivector3D::new(0.0, 0.0, 1.0).complement(), Vector3D::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn complement(&self) -> Vector3D {
+        Vector3D::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the wedge product with a Vector3D, the signed volume they span
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Vector3D;
+    ///
+    /// let a = Vector3D::new(1.0, 0.0, 0.0);
+    /// let b = Vector3D::new(0.0, 2.0, 0.0);
+    /// let c = Vector3D::new(0.0, 0.0, 3.0);
+    /// assert_eq!(a.wedge(&b).wedge(&c).xyz, 6.0);
+    /// ```
+    pub fn wedge(&self, v: &Vector3D) -> Trivector3D {
+        Trivector3D::new(self.x * v.x + self.y * v.y + self.z * v.z)
+    }
+
+    /// Returns the antiwedge product of two bivectors, the Vector3D along the
+    /// line where their planes meet
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Bivector3D, Vector3D};
+    ///
+    /// let xy = Bivector3D::new(0.0, 0.0, 1.0);
+    /// let zx = Bivector3D::new(0.0, 1.0, 0.0);
+    /// assert_eq!(xy.antiwedge(&zx), Vector3D::new(-1.0, 0.0, 0.0));
+    /// ```
+    pub fn antiwedge(&self, other: &Bivector3D) -> Vector3D {
+        self.complement().cross(&other.complement())
+    }
+
+    /// Returns the antiwedge product with a Vector3D, a scalar that is zero
+    /// when the vector lies in the bivector's plane
+    pub fn antiwedge_vector(&self, v: &Vector3D) -> f64 {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+
+    /// Returns the bivector transformed by a Matrix3D
+    ///
+    /// For `b = u.wedge(&v)` this equals `(m * u).wedge(&(m * v))`, which works
+    /// out to the transposed adjugate (cofactor matrix) applied to `b`.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Vector3D};
+    ///
+    /// let m = Matrix3D::new(2.0, 1.0, 0.0,
+    ///                       0.0, 1.0, 0.0,
+    ///                       0.0, 0.0, 3.0);
+    /// let u = Vector3D::new(1.0, 0.0, 0.0);
+    /// let v = Vector3D::new(0.0, 1.0, 1.0);
+    /// assert_eq!(u.wedge(&v).transform(&m), (m * u).wedge(&(m * v)));
+    /// ```
+    pub fn transform(&self, m: &Matrix3D) -> Self {
+        let v = m.adjugate().transpose() * self.complement();
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bivector_3d_wedge() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let c = Vector3D::new(0.0, -1.0, 2.0);
+
+        // Antisymmetric, and matches the cross product components
+        assert_eq!(a.wedge(&b), -b.wedge(&a));
+        assert_eq!(a.wedge(&a), Bivector3D::default());
+        assert_eq!(Vector3D::from(a.wedge(&b)), a.cross(&b));
+
+        // a ^ b ^ c is the determinant of the three vectors
+        let volume = a.wedge(&b).wedge(&c);
+        assert!((volume.xyz - Matrix3D::new_vectors(a, b, c).determinant()).abs() < 1e-12);
+        assert_eq!(a.wedge_bivector(&b.wedge(&c)), volume);
+    }
+
+    #[test]
+    fn test_bivector_3d_antiwedge() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let c = Vector3D::new(0.0, -1.0, 2.0);
+        let plane_ab = a.wedge(&b);
+        let plane_bc = b.wedge(&c);
+
+        // The planes share the direction b
+        let meet = plane_ab.antiwedge(&plane_bc);
+        assert!(meet.cross(&b).magnitude() < 1e-12);
+
+        // Vectors in the plane antiwedge to zero
+        assert!(plane_ab.antiwedge_vector(&(a * 2.0 - b)).abs() < 1e-12);
+        assert!(plane_ab.antiwedge_vector(&c).abs() > 1e-3);
+
+        // Complement round trip
+        assert_eq!(a.complement().complement(), a);
+    }
+
+    #[test]
+    fn test_bivector_3d_transform() {
+        // Shear plus non-uniform scale, where transforming the cross
+        // product vector directly by m gives the wrong answer
+        let m = Matrix3D::new(2.0, 0.5, 0.0,
+                              0.0, 1.0, -1.0,
+                              0.3, 0.0, 4.0);
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let transformed = a.wedge(&b).transform(&m);
+        let expected = (m * a).wedge(&(m * b));
+        assert!((transformed - expected).magnitude() < 1e-12);
+        assert!((Vector3D::from(expected) - m * a.cross(&b)).magnitude() > 1e-3);
+
+        // Trivectors scale by the determinant
+        let c = Vector3D::new(0.0, -1.0, 2.0);
+        let volume = a.wedge(&b).wedge(&c).transform(&m);
+        let expected = (m * a).wedge(&(m * b)).wedge(&(m * c));
+        assert!((volume.xyz - expected.xyz).abs() < 1e-10);
+    }
+}
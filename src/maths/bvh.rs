@@ -0,0 +1,629 @@
+use super::{Aabb, Ray, Sphere, Vector3D};
+
+/// Most primitives a leaf built by `Bvh::new` holds
+pub const BVH_MAX_LEAF_SIZE: usize = 4;
+
+/// Number of centroid bins the SAH builder evaluates per split
+const BIN_COUNT: usize = 12;
+
+/// Cost of visiting a node relative to testing one primitive
+const TRAVERSAL_COST: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Content {
+    Leaf(Vec<usize>),
+    Branch([usize; 2]),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    aabb: Aabb,
+    parent: Option<usize>,
+    content: Content,
+}
+
+/// Bounding volume hierarchy over primitive Aabbs
+///
+/// The tree only stores boxes and primitive ids, so the primitives
+/// themselves stay with the caller and ray queries test them through a
+/// callback. Ids are the index into the slice given to `new`, then whatever
+/// `insert` returns; the id of a removed primitive is never reused.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    /// Per primitive id, its box and the leaf holding it, None once removed
+    primitives: Vec<Option<(Aabb, usize)>>,
+}
+
+impl Bvh {
+    /// Builds a Bvh top-down with a binned surface area heuristic
+    ///
+    /// Empty boxes are kept as primitives but can never be found by a query.
+    ///
+    /// # Arguments
+    /// - `aabbs` - The bounds of every primitive, the index is its id
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Bvh, Ray, Vector3D};
+    ///
+    /// let boxes: Vec<Aabb> = (0..10)
+    ///     .map(|i| Aabb::from_center_half_extents(Vector3D::new(i as f64 * 3.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0)))
+    ///     .collect();
+    /// let bvh = Bvh::new(&boxes);
+    /// let ray = Ray::new(Vector3D::new(100.0, 0.0, 0.0), Vector3D::new(-1.0, 0.0, 0.0)).unwrap();
+    /// let (id, distance) = bvh.ray_nearest(&ray, |id| boxes[id].intersect_ray(&ray).map(|hit| hit.distance)).unwrap();
+    /// assert_eq!(id, 9);
+    /// assert_eq!(distance, 72.0);
+    /// ```
+    pub fn new(aabbs: &[Aabb]) -> Self {
+        let mut bvh = Self {
+            primitives: aabbs.iter().map(|aabb| Some((*aabb, 0))).collect(),
+            ..Self::default()
+        };
+        if !aabbs.is_empty() {
+            let mut ids: Vec<usize> = (0..aabbs.len()).collect();
+            let root = bvh.build(aabbs, &mut ids, None);
+            bvh.root = Some(root);
+        }
+        bvh
+    }
+
+    /// Returns the number of primitives in the tree
+    pub fn len(&self) -> usize {
+        self.primitives.iter().filter(|p| p.is_some()).count()
+    }
+
+    /// Returns true if the tree holds no primitives
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the bounds of everything in the tree
+    pub fn bounds(&self) -> Aabb {
+        self.root.map_or_else(Aabb::empty, |root| self.nodes[root].aabb)
+    }
+
+    /// Returns the stored box of a primitive, None if it was removed
+    pub fn aabb(&self, id: usize) -> Option<Aabb> {
+        self.primitives.get(id).copied().flatten().map(|(aabb, _)| aabb)
+    }
+
+    /// Finds the closest primitive hit by a ray
+    ///
+    /// Nodes are visited front to back and skipped once they start beyond
+    /// the best hit so far.
+    ///
+    /// # Arguments
+    /// - `ray` - The ray to cast
+    /// - `intersect` - Returns the hit distance of a primitive by id, if any
+    pub fn ray_nearest<F: FnMut(usize) -> Option<f64>>(&self, ray: &Ray, mut intersect: F) -> Option<(usize, f64)> {
+        let root = self.root?;
+        let inverse = inverse_direction(ray);
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack = vec![(root, ray_entry(ray, &inverse, &self.nodes[root].aabb, f64::INFINITY)?)];
+        while let Some((node, entry)) = stack.pop() {
+            let limit = best.map_or(f64::INFINITY, |(_, d)| d);
+            if entry > limit {
+                continue;
+            }
+            match &self.nodes[node].content {
+                Content::Leaf(ids) => {
+                    for &id in ids {
+                        if let Some(distance) = intersect(id) {
+                            if distance >= 0.0 && best.is_none_or(|(_, d)| distance < d) {
+                                best = Some((id, distance));
+                            }
+                        }
+                    }
+                }
+                Content::Branch(children) => {
+                    let mut hits: Vec<(usize, f64)> = children
+                        .iter()
+                        .filter_map(|&c| ray_entry(ray, &inverse, &self.nodes[c].aabb, limit).map(|t| (c, t)))
+                        .collect();
+                    // Push the far child first so the near one is popped next
+                    hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+                    stack.extend(hits);
+                }
+            }
+        }
+        best
+    }
+
+    /// Finds every primitive hit by a ray, sorted by distance
+    ///
+    /// # Arguments
+    /// - `ray` - The ray to cast
+    /// - `intersect` - Returns the hit distance of a primitive by id, if any
+    pub fn ray_all<F: FnMut(usize) -> Option<f64>>(&self, ray: &Ray, mut intersect: F) -> Vec<(usize, f64)> {
+        let inverse = inverse_direction(ray);
+        let mut hits = Vec::new();
+        self.visit(
+            |aabb| ray_entry(ray, &inverse, aabb, f64::INFINITY).is_some(),
+            |id| {
+                if let Some(distance) = intersect(id).filter(|d| *d >= 0.0) {
+                    hits.push((id, distance));
+                }
+            },
+        );
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        hits
+    }
+
+    /// Returns the ids of every primitive whose box overlaps `aabb`
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(|node| node.intersects(aabb), |id| {
+            if self.primitives[id].is_some_and(|(p, _)| p.intersects(aabb)) {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Returns the ids of every primitive whose box overlaps `sphere`
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(|node| sphere.intersects_aabb(node), |id| {
+            if self.primitives[id].is_some_and(|(p, _)| sphere.intersects_aabb(&p)) {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Adds a primitive and returns its id
+    ///
+    /// The new leaf goes next to the node that grows the total surface area
+    /// the least, so the tree stays reasonable without a rebuild.
+    ///
+    /// # Arguments
+    /// - `aabb` - The bounds of the new primitive
+    pub fn insert(&mut self, aabb: Aabb) -> usize {
+        let id = self.primitives.len();
+        let leaf = self.allocate(Node { aabb, parent: None, content: Content::Leaf(vec![id]) });
+        self.primitives.push(Some((aabb, leaf)));
+
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            return id;
+        };
+
+        // Branch and bound descent on the area added to the ancestors
+        let mut sibling = root;
+        let mut inherited = 0.0;
+        while let Content::Branch(children) = self.nodes[sibling].content {
+            let area = self.nodes[sibling].aabb.surface_area();
+            let combined = self.nodes[sibling].aabb.union(&aabb).surface_area();
+            let here = combined + inherited;
+            inherited += combined - area;
+            let descend = |child: usize| {
+                let node = &self.nodes[child];
+                let grown = node.aabb.union(&aabb).surface_area();
+                match node.content {
+                    Content::Leaf(_) => inherited + grown,
+                    Content::Branch(_) => inherited + grown - node.aabb.surface_area(),
+                }
+            };
+            let costs = children.map(descend);
+            let best = if costs[0] <= costs[1] { 0 } else { 1 };
+            if here < costs[best] {
+                break;
+            }
+            sibling = children[best];
+        }
+
+        let parent = self.nodes[sibling].parent;
+        let branch = self.allocate(Node {
+            aabb: self.nodes[sibling].aabb.union(&aabb),
+            parent,
+            content: Content::Branch([sibling, leaf]),
+        });
+        self.nodes[sibling].parent = Some(branch);
+        self.nodes[leaf].parent = Some(branch);
+        self.replace_child(parent, sibling, branch);
+        self.refit_from(parent);
+        id
+    }
+
+    /// Removes a primitive, returning false if the id is not in the tree
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some((_, leaf)) = self.primitives.get_mut(id).and_then(Option::take) else {
+            return false;
+        };
+        let Content::Leaf(ids) = &mut self.nodes[leaf].content else {
+            unreachable!("primitives always point at leaves");
+        };
+        ids.retain(|&i| i != id);
+        if !ids.is_empty() {
+            self.refit_from(Some(leaf));
+            return true;
+        }
+
+        // Drop the empty leaf and let its sibling take the parent's place
+        let parent = self.nodes[leaf].parent;
+        self.free(leaf);
+        let Some(parent) = parent else {
+            self.root = None;
+            return true;
+        };
+        let Content::Branch(children) = self.nodes[parent].content else {
+            unreachable!("parents are always branches");
+        };
+        let sibling = if children[0] == leaf { children[1] } else { children[0] };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.replace_child(grandparent, parent, sibling);
+        self.free(parent);
+        self.refit_from(grandparent);
+        true
+    }
+
+    /// Moves a primitive to new bounds and refits the nodes above it
+    ///
+    /// Returns false if the id is not in the tree. The tree shape is kept,
+    /// so after large motions a rebuild with `new` gives faster queries.
+    pub fn update(&mut self, id: usize, aabb: Aabb) -> bool {
+        let Some(Some((stored, leaf))) = self.primitives.get_mut(id) else {
+            return false;
+        };
+        *stored = aabb;
+        let leaf = *leaf;
+        self.refit_from(Some(leaf));
+        true
+    }
+
+    /// Replaces every primitive's box and refits the whole tree bottom up
+    ///
+    /// # Arguments
+    /// - `aabb_of` - Returns the new box of a live primitive by id
+    pub fn refit<F: FnMut(usize) -> Aabb>(&mut self, mut aabb_of: F) {
+        for (id, primitive) in self.primitives.iter_mut().enumerate() {
+            if let Some((aabb, _)) = primitive {
+                *aabb = aabb_of(id);
+            }
+        }
+        if let Some(root) = self.root {
+            self.refit_node(root);
+        }
+    }
+
+    /// Builds the subtree over `ids` and returns its node index
+    fn build(&mut self, aabbs: &[Aabb], ids: &mut [usize], parent: Option<usize>) -> usize {
+        let bounds = ids.iter().fold(Aabb::empty(), |b, &id| b.union(&aabbs[id]));
+        let node = self.allocate(Node { aabb: bounds, parent, content: Content::Leaf(Vec::new()) });
+
+        let split = if ids.len() > 1 { sah_split(aabbs, ids, &bounds) } else { None };
+        match split {
+            Some(mid) => {
+                let (left, right) = ids.split_at_mut(mid);
+                let left = self.build(aabbs, left, Some(node));
+                let right = self.build(aabbs, right, Some(node));
+                self.nodes[node].content = Content::Branch([left, right]);
+            }
+            None => {
+                for &id in ids.iter() {
+                    self.primitives[id] = Some((aabbs[id], node));
+                }
+                self.nodes[node].content = Content::Leaf(ids.to_vec());
+            }
+        }
+        node
+    }
+
+    /// Walks every node whose box passes `enter` and reports its primitives
+    fn visit<E: FnMut(&Aabb) -> bool, V: FnMut(usize)>(&self, mut enter: E, mut found: V) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !enter(&node.aabb) {
+                continue;
+            }
+            match &node.content {
+                Content::Leaf(ids) => ids.iter().for_each(|&id| found(id)),
+                Content::Branch(children) => stack.extend(children),
+            }
+        }
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn free(&mut self, index: usize) {
+        self.nodes[index] = Node { aabb: Aabb::empty(), parent: None, content: Content::Leaf(Vec::new()) };
+        self.free_nodes.push(index);
+    }
+
+    /// Points `parent` at `new` instead of `old`, or makes `new` the root
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        match parent {
+            Some(parent) => {
+                if let Content::Branch(children) = &mut self.nodes[parent].content {
+                    for child in children.iter_mut().filter(|c| **c == old) {
+                        *child = new;
+                    }
+                }
+            }
+            None => self.root = Some(new),
+        }
+    }
+
+    /// Recomputes the boxes of `node` and all of its ancestors
+    fn refit_from(&mut self, mut node: Option<usize>) {
+        while let Some(index) = node {
+            self.nodes[index].aabb = self.node_bounds(index);
+            node = self.nodes[index].parent;
+        }
+    }
+
+    /// Recomputes the boxes of a whole subtree
+    fn refit_node(&mut self, index: usize) -> Aabb {
+        if let Content::Branch(children) = self.nodes[index].content {
+            for child in children {
+                self.refit_node(child);
+            }
+        }
+        self.nodes[index].aabb = self.node_bounds(index);
+        self.nodes[index].aabb
+    }
+
+    /// The union of a node's children or primitives
+    fn node_bounds(&self, index: usize) -> Aabb {
+        match &self.nodes[index].content {
+            Content::Leaf(ids) => ids
+                .iter()
+                .filter_map(|&id| self.primitives[id])
+                .fold(Aabb::empty(), |b, (aabb, _)| b.union(&aabb)),
+            Content::Branch(children) => self.nodes[children[0]].aabb.union(&self.nodes[children[1]].aabb),
+        }
+    }
+}
+
+/// Partitions `ids` at the cheapest binned SAH split and returns the split
+/// position, or None if a leaf is cheaper
+fn sah_split(aabbs: &[Aabb], ids: &mut [usize], bounds: &Aabb) -> Option<usize> {
+    let centroids = ids.iter().fold(Aabb::empty(), |b, &id| b.expand_to_point(&aabbs[id].center()));
+    let extents = centroids.extents();
+    let axis = (0..3).max_by(|&a, &b| extents[a].total_cmp(&extents[b])).unwrap();
+    let leaf_cost = ids.len() as f64;
+
+    if extents[axis] <= 0.0 {
+        // All centroids coincide so no plane separates them; split evenly
+        // once the leaf is too big to keep
+        return (ids.len() > BVH_MAX_LEAF_SIZE).then_some(ids.len() / 2);
+    }
+
+    let bin_of = |id: usize| {
+        let offset = (aabbs[id].center()[axis] - centroids.min[axis]) / extents[axis];
+        ((offset * BIN_COUNT as f64) as usize).min(BIN_COUNT - 1)
+    };
+    let mut bins = [(Aabb::empty(), 0usize); BIN_COUNT];
+    for &id in ids.iter() {
+        let bin = &mut bins[bin_of(id)];
+        bin.0 = bin.0.union(&aabbs[id]);
+        bin.1 += 1;
+    }
+
+    // Sweep from the right to get the area and count of every right side
+    let mut right = [(0.0, 0usize); BIN_COUNT];
+    let mut accumulated = (Aabb::empty(), 0);
+    for bin in (1..BIN_COUNT).rev() {
+        accumulated = (accumulated.0.union(&bins[bin].0), accumulated.1 + bins[bin].1);
+        right[bin] = (accumulated.0.surface_area(), accumulated.1);
+    }
+    let parent_area = bounds.surface_area();
+    let mut best: Option<(usize, f64)> = None;
+    let mut left = (Aabb::empty(), 0);
+    for bin in 1..BIN_COUNT {
+        left = (left.0.union(&bins[bin - 1].0), left.1 + bins[bin - 1].1);
+        if left.1 == 0 || right[bin].1 == 0 {
+            continue;
+        }
+        let cost = if parent_area > 0.0 {
+            TRAVERSAL_COST + (left.0.surface_area() * left.1 as f64 + right[bin].0 * right[bin].1 as f64) / parent_area
+        } else {
+            TRAVERSAL_COST + leaf_cost * 0.5
+        };
+        if best.is_none_or(|(_, c)| cost < c) {
+            best = Some((bin, cost));
+        }
+    }
+
+    let (bin, cost) = best?;
+    if cost >= leaf_cost && ids.len() <= BVH_MAX_LEAF_SIZE {
+        return None;
+    }
+    let mut mid = 0;
+    for i in 0..ids.len() {
+        if bin_of(ids[i]) < bin {
+            ids.swap(i, mid);
+            mid += 1;
+        }
+    }
+    Some(mid)
+}
+
+/// Per-axis inverse ray direction, infinite on axes the ray is parallel to
+fn inverse_direction(ray: &Ray) -> Vector3D {
+    Vector3D::new(1.0 / ray.direction.x, 1.0 / ray.direction.y, 1.0 / ray.direction.z)
+}
+
+/// Distance at which a ray enters a box, zero if it starts inside, None if
+/// it misses or enters beyond `limit`
+fn ray_entry(ray: &Ray, inverse: &Vector3D, aabb: &Aabb, limit: f64) -> Option<f64> {
+    if aabb.is_empty() {
+        return None;
+    }
+    let mut t_enter = 0.0_f64;
+    let mut t_exit = limit;
+    for axis in 0..3 {
+        let origin = ray.origin[axis];
+        if ray.direction[axis] == 0.0 {
+            if origin < aabb.min[axis] || origin > aabb.max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (aabb.min[axis] - origin) * inverse[axis];
+        let b = (aabb.max[axis] - origin) * inverse[axis];
+        t_enter = t_enter.max(a.min(b));
+        t_exit = t_exit.min(a.max(b));
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::TestRng;
+
+    fn random_spheres(count: usize, seed: u64) -> Vec<Sphere> {
+        let mut rng = TestRng::new(seed);
+        (0..count)
+            .map(|_| {
+                let center = Vector3D::new(rng.unit(), rng.unit(), rng.unit()) * 100.0 - Vector3D::new(50.0, 50.0, 50.0);
+                Sphere::new(center, 0.5 + rng.unit() * 3.0)
+            })
+            .collect()
+    }
+
+    /// Checks every node bounds its children and every primitive sits in its leaf
+    fn assert_valid(bvh: &Bvh) {
+        let mut seen = 0;
+        let mut stack: Vec<(usize, Option<usize>)> = bvh.root.map(|r| (r, None)).into_iter().collect();
+        while let Some((node, parent)) = stack.pop() {
+            assert_eq!(bvh.nodes[node].parent, parent);
+            let aabb = bvh.nodes[node].aabb;
+            match &bvh.nodes[node].content {
+                Content::Leaf(ids) => {
+                    assert!(!ids.is_empty());
+                    for &id in ids {
+                        let (primitive, leaf) = bvh.primitives[id].unwrap();
+                        assert_eq!(leaf, node);
+                        assert!(aabb.contains(&primitive));
+                        seen += 1;
+                    }
+                }
+                Content::Branch(children) => {
+                    for &child in children {
+                        assert!(aabb.contains(&bvh.nodes[child].aabb));
+                        stack.push((child, Some(node)));
+                    }
+                }
+            }
+        }
+        assert_eq!(seen, bvh.len());
+    }
+
+    fn check_queries(bvh: &Bvh, spheres: &[Option<Sphere>], seed: u64) {
+        let mut rng = TestRng::new(seed);
+        let live = |id: usize| spheres[id];
+        for _ in 0..100 {
+            let origin = Vector3D::new(rng.unit(), rng.unit(), rng.unit()) * 160.0 - Vector3D::new(80.0, 80.0, 80.0);
+            let target = Vector3D::new(rng.unit(), rng.unit(), rng.unit()) * 40.0 - Vector3D::new(20.0, 20.0, 20.0);
+            let ray = Ray::new(origin, target - origin).unwrap();
+            let intersect = |id: usize| live(id).and_then(|s| s.intersect_ray(&ray)).map(|h| h.distance);
+
+            let mut brute: Vec<(usize, f64)> = (0..spheres.len()).filter_map(|id| intersect(id).map(|d| (id, d))).collect();
+            brute.sort_by(|a, b| a.1.total_cmp(&b.1));
+            let nearest = bvh.ray_nearest(&ray, intersect);
+            assert_eq!(nearest.map(|n| n.1), brute.first().map(|b| b.1));
+            assert_eq!(bvh.ray_all(&ray, intersect), brute);
+
+            let query = Aabb::from_center_half_extents(target, Vector3D::new(5.0, 8.0, 3.0));
+            let mut found = bvh.query_aabb(&query);
+            found.sort();
+            let expected: Vec<usize> = (0..spheres.len()).filter(|&id| live(id).is_some_and(|s| s.aabb().intersects(&query))).collect();
+            assert_eq!(found, expected);
+
+            let sphere = Sphere::new(target, 10.0);
+            let mut found = bvh.query_sphere(&sphere);
+            found.sort();
+            let expected: Vec<usize> = (0..spheres.len()).filter(|&id| live(id).is_some_and(|s| sphere.intersects_aabb(&s.aabb()))).collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_bvh_build_and_query() {
+        let spheres = random_spheres(2000, 1);
+        let aabbs: Vec<Aabb> = spheres.iter().map(Sphere::aabb).collect();
+        let bvh = Bvh::new(&aabbs);
+        assert_valid(&bvh);
+        assert_eq!(bvh.len(), 2000);
+        assert!(bvh.bounds().contains(&aabbs.iter().fold(Aabb::empty(), |b, a| b.union(a))));
+        let spheres: Vec<Option<Sphere>> = spheres.into_iter().map(Some).collect();
+        check_queries(&bvh, &spheres, 2);
+
+        // Coincident primitives still end up in small leaves
+        let same = vec![Aabb::from_center_half_extents(Vector3D::default(), Vector3D::new(1.0, 1.0, 1.0)); 100];
+        let bvh = Bvh::new(&same);
+        assert_valid(&bvh);
+        assert_eq!(bvh.query_aabb(&same[0]).len(), 100);
+        assert!(Bvh::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_bvh_refit_insert_remove() {
+        let mut spheres: Vec<Option<Sphere>> = random_spheres(500, 3).into_iter().map(Some).collect();
+        let aabbs: Vec<Aabb> = spheres.iter().map(|s| s.unwrap().aabb()).collect();
+        let mut bvh = Bvh::new(&aabbs);
+
+        // Move everything, refit in bulk, then move a few one at a time
+        let offset = Vector3D::new(3.0, -7.0, 11.0);
+        for s in spheres.iter_mut().flatten() {
+            s.center = s.center + offset;
+        }
+        bvh.refit(|id| spheres[id].unwrap().aabb());
+        assert_valid(&bvh);
+        check_queries(&bvh, &spheres, 4);
+        for id in (0..500).step_by(7) {
+            let s = spheres[id].as_mut().unwrap();
+            s.center = -s.center;
+            assert!(bvh.update(id, s.aabb()));
+        }
+        assert_valid(&bvh);
+        check_queries(&bvh, &spheres, 5);
+
+        for id in (0..500).step_by(3) {
+            assert!(bvh.remove(id));
+            spheres[id] = None;
+        }
+        assert!(!bvh.remove(0));
+        assert!(!bvh.update(0, Aabb::empty()));
+        assert_valid(&bvh);
+        check_queries(&bvh, &spheres, 6);
+
+        for s in random_spheres(300, 7) {
+            assert_eq!(bvh.insert(s.aabb()), spheres.len());
+            spheres.push(Some(s));
+        }
+        assert_valid(&bvh);
+        assert_eq!(bvh.len(), spheres.iter().flatten().count());
+        check_queries(&bvh, &spheres, 8);
+
+        for id in 0..spheres.len() {
+            bvh.remove(id);
+        }
+        assert!(bvh.is_empty());
+        assert!(bvh.bounds().is_empty());
+        let id = bvh.insert(Aabb::from_center_half_extents(Vector3D::default(), Vector3D::new(1.0, 1.0, 1.0)));
+        assert_eq!(bvh.query_sphere(&Sphere::new(Vector3D::default(), 0.5)), vec![id]);
+    }
+}
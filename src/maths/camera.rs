@@ -0,0 +1,381 @@
+use super::{DepthRange, Frustum, Handedness, Matrix3D, Matrix4D, ProjectionOptions, Ray, Vector3D, EPSILON};
+
+/// Region of the render target that NDC is mapped to
+///
+/// Screen coordinates are in pixels and use a y-down convention, with the
+/// origin at the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub min_depth: f64,
+    pub max_depth: f64,
+}
+
+impl Viewport {
+    /// Creates a new Viewport with a 0..1 depth range
+    ///
+    /// # Arguments
+    /// - `x` - The left edge in pixels
+    /// - `y` - The top edge in pixels
+    /// - `width` - The width in pixels
+    /// - `height` - The height in pixels
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self { x, y, width, height, min_depth: 0.0, max_depth: 1.0 }
+    }
+
+    /// Returns width divided by height
+    pub fn aspect(&self) -> f64 {
+        self.width / self.height
+    }
+
+    /// Maps a point from NDC to screen space
+    ///
+    /// # Arguments
+    /// - `ndc` - The point after the perspective divide
+    /// - `depth` - The depth range of the projection that produced `ndc`
+    pub fn to_screen(&self, ndc: &Vector3D, depth: DepthRange) -> Vector3D {
+        let (lo, hi) = ProjectionOptions { depth, ..Default::default() }.depths();
+        Vector3D::new(
+            self.x + (ndc.x + 1.0) * 0.5 * self.width,
+            self.y + (1.0 - ndc.y) * 0.5 * self.height,
+            self.min_depth + (ndc.z - lo) / (hi - lo) * (self.max_depth - self.min_depth),
+        )
+    }
+
+    /// Maps a point from screen space back to NDC, the inverse of `to_screen`
+    pub fn to_ndc(&self, screen: &Vector3D, depth: DepthRange) -> Vector3D {
+        let (lo, hi) = ProjectionOptions { depth, ..Default::default() }.depths();
+        Vector3D::new(
+            (screen.x - self.x) / self.width * 2.0 - 1.0,
+            1.0 - (screen.y - self.y) / self.height * 2.0,
+            lo + (screen.z - self.min_depth) / (self.max_depth - self.min_depth) * (hi - lo),
+        )
+    }
+}
+
+/// Returns the camera-to-world rotation for a camera at `eye` facing `target`
+///
+/// The columns are the camera's right, up and back axes for a right-handed
+/// camera (looking down its local -z), or right, up and forward for a
+/// left-handed one (looking down +z). The camera's up axis is `up` made
+/// perpendicular to the view direction.
+///
+/// If `up` is parallel to the view direction, for example looking straight
+/// down with +y up, the world axis least aligned with the view direction is
+/// used as up instead, so the result is always a valid rotation. Returns
+/// `None` only if `eye` and `target` coincide.
+///
+/// # Arguments
+/// - `eye` - The camera position
+/// - `target` - The point to look at
+/// - `up` - The approximate up direction, need not be unit length
+/// - `handedness` - Which way the camera looks down its z axis
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{look_at, Handedness, Vector3D};
+///
+/// let rotation = look_at(&Vector3D::new(0.0, 0.0, 5.0), &Vector3D::default(),
+///                        &Vector3D::new(0.0, 1.0, 0.0), Handedness::Right).unwrap();
+/// // Looking down world -z is the identity for a right-handed camera
+/// assert_eq!(rotation.column(2), Vector3D::new(0.0, 0.0, 1.0));
+/// assert_eq!(rotation.column(0), Vector3D::new(1.0, 0.0, 0.0));
+/// ```
+pub fn look_at(eye: &Vector3D, target: &Vector3D, up: &Vector3D, handedness: Handedness) -> Option<Matrix3D> {
+    let offset = *target - *eye;
+    let length = offset.magnitude();
+    if length <= EPSILON {
+        return None;
+    }
+    let forward = offset / length;
+
+    let mut right = forward.cross(up);
+    if right.magnitude_squared() <= EPSILON * EPSILON * up.magnitude_squared() {
+        let axes = [Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(0.0, 0.0, 1.0)];
+        let fallback = axes
+            .into_iter()
+            .min_by(|a, b| a.dot(&forward).abs().total_cmp(&b.dot(&forward).abs()))
+            .unwrap();
+        right = forward.cross(&fallback);
+    }
+    let right = right.normalise();
+    let up = right.cross(&forward);
+
+    Some(match handedness {
+        Handedness::Right => Matrix3D::new_vectors(right, up, -forward),
+        // The usual left-handed look-at, with x = up cross forward
+        Handedness::Left => Matrix3D::new_vectors(-right, up, forward),
+    })
+}
+
+/// Returns the view matrix taking world space into a camera's view space
+///
+/// # Arguments
+/// - `position` - The camera position
+/// - `orientation` - The camera-to-world rotation, e.g. from `look_at`
+pub fn view_matrix(position: &Vector3D, orientation: &Matrix3D) -> Matrix4D {
+    let inverse = orientation.transpose();
+    Matrix4D::from_affine(&inverse, &-(inverse * *position))
+}
+
+/// A positioned camera with a projection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub position: Vector3D,
+    /// The camera-to-world rotation
+    pub orientation: Matrix3D,
+    pub projection: Matrix4D,
+    /// The options `projection` was built with
+    pub options: ProjectionOptions,
+}
+
+impl Camera {
+    /// Creates a new Camera
+    ///
+    /// # Arguments
+    /// - `position` - The camera position
+    /// - `orientation` - The camera-to-world rotation
+    /// - `projection` - The projection matrix
+    /// - `options` - The options `projection` was built with
+    pub fn new(position: Vector3D, orientation: Matrix3D, projection: Matrix4D, options: ProjectionOptions) -> Self {
+        Self { position, orientation, projection, options }
+    }
+
+    /// Creates a Camera at `eye` facing `target`, see `look_at`
+    pub fn look_at(
+        eye: &Vector3D,
+        target: &Vector3D,
+        up: &Vector3D,
+        projection: Matrix4D,
+        options: ProjectionOptions,
+    ) -> Option<Self> {
+        let orientation = look_at(eye, target, up, options.handedness)?;
+        Some(Self::new(*eye, orientation, projection, options))
+    }
+
+    /// Returns the matrix taking world space into view space
+    pub fn view_matrix(&self) -> Matrix4D {
+        view_matrix(&self.position, &self.orientation)
+    }
+
+    /// Returns `projection * view`, taking world space into clip space
+    pub fn view_projection(&self) -> Matrix4D {
+        self.projection * self.view_matrix()
+    }
+
+    /// Returns the camera's view frustum in world space
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_matrix(&self.view_projection(), &self.options)
+    }
+
+    /// Projects a world point to screen space
+    ///
+    /// The result holds pixel coordinates in x and y and the viewport depth
+    /// in z. Returns `None` for points level with or behind the camera in
+    /// view space, which have no meaningful screen position, whatever the
+    /// projection.
+    ///
+    /// # Arguments
+    /// - `point` - The world-space point
+    /// - `viewport` - The region of the render target
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{perspective, Camera, ProjectionOptions, Vector3D, Viewport};
+    ///
+    /// let options = ProjectionOptions::default();
+    /// let projection = perspective(1.0, 2.0, 0.1, 100.0, &options);
+    /// let camera = Camera::look_at(&Vector3D::new(0.0, 0.0, 10.0), &Vector3D::default(),
+    ///                              &Vector3D::new(0.0, 1.0, 0.0), projection, options).unwrap();
+    /// let screen = camera.world_to_screen(&Vector3D::default(), &Viewport::new(0.0, 0.0, 800.0, 400.0)).unwrap();
+    /// assert!((screen.x - 400.0).abs() < 1e-9 && (screen.y - 200.0).abs() < 1e-9);
+    /// ```
+    pub fn world_to_screen(&self, point: &Vector3D, viewport: &Viewport) -> Option<Vector3D> {
+        // Distance along the view direction, w only tracks it for perspective
+        let depth = self.options.forward() * self.orientation.column(2).dot(&(*point - self.position));
+        if depth <= 0.0 {
+            return None;
+        }
+        let [x, y, z, w] = self.view_projection().transform_homogeneous(&[point.x, point.y, point.z, 1.0]);
+        Some(viewport.to_screen(&Vector3D::new(x / w, y / w, z / w), self.options.depth))
+    }
+
+    /// Returns the world point at a screen position and viewport depth
+    ///
+    /// The inverse of `world_to_screen`. Returns `None` if the view
+    /// projection is singular or the depth maps to a point at infinity.
+    ///
+    /// # Arguments
+    /// - `screen` - Pixel coordinates in x and y, viewport depth in z
+    /// - `viewport` - The region of the render target
+    pub fn screen_to_world(&self, screen: &Vector3D, viewport: &Viewport) -> Option<Vector3D> {
+        let inverse = self.view_projection().inverse()?;
+        unproject(&inverse, &viewport.to_ndc(screen, self.options.depth))
+    }
+
+    /// Returns the picking ray through a screen position
+    ///
+    /// The ray starts on the near plane and points away from the camera,
+    /// so its hits are ordered by depth. Works for orthographic and
+    /// infinite-far projections too.
+    ///
+    /// # Arguments
+    /// - `x` - The horizontal pixel coordinate
+    /// - `y` - The vertical pixel coordinate, growing downwards
+    /// - `viewport` - The region of the render target
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{perspective, Camera, ProjectionOptions, Sphere, Vector3D, Viewport};
+    ///
+    /// let options = ProjectionOptions::default();
+    /// let projection = perspective(1.0, 1.0, 0.1, 100.0, &options);
+    /// let camera = Camera::look_at(&Vector3D::new(0.0, 0.0, 10.0), &Vector3D::default(),
+    ///                              &Vector3D::new(0.0, 1.0, 0.0), projection, options).unwrap();
+    /// let ray = camera.picking_ray(300.0, 300.0, &Viewport::new(0.0, 0.0, 600.0, 600.0)).unwrap();
+    /// let hit = Sphere::new(Vector3D::default(), 1.0).intersect_ray(&ray).unwrap();
+    /// assert!((ray.at(hit.distance) - Vector3D::new(0.0, 0.0, 1.0)).magnitude() < 1e-9);
+    /// ```
+    pub fn picking_ray(&self, x: f64, y: f64, viewport: &Viewport) -> Option<Ray> {
+        let inverse = self.view_projection().inverse()?;
+        let (near_ndc, far_ndc) = self.options.depths();
+        let ndc = viewport.to_ndc(&Vector3D::new(x, y, 0.0), self.options.depth);
+
+        // Halfway through NDC depth is finite even with an infinite far plane
+        let near = unproject(&inverse, &Vector3D::new(ndc.x, ndc.y, near_ndc))?;
+        let middle = unproject(&inverse, &Vector3D::new(ndc.x, ndc.y, 0.5 * (near_ndc + far_ndc)))?;
+        let direction = middle - near;
+        if direction.magnitude() <= EPSILON {
+            return None;
+        }
+        Ray::new(near, direction)
+    }
+}
+
+/// Takes an NDC point back through an inverse view projection
+fn unproject(inverse: &Matrix4D, ndc: &Vector3D) -> Option<Vector3D> {
+    let [x, y, z, w] = inverse.transform_homogeneous(&[ndc.x, ndc.y, ndc.z, 1.0]);
+    if w.abs() <= EPSILON * (x.abs() + y.abs() + z.abs()) || w == 0.0 {
+        return None;
+    }
+    Some(Vector3D::new(x / w, y / w, z / w))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::{orthographic, perspective, Containment};
+
+    fn assert_close(a: Vector3D, b: Vector3D, tolerance: f64) {
+        assert!((a - b).magnitude() < tolerance, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_look_at() {
+        let eye = Vector3D::new(3.0, 4.0, -2.0);
+        let target = Vector3D::new(-1.0, 0.5, 6.0);
+        let up = Vector3D::new(0.0, 1.0, 0.0);
+        let forward = (target - eye).normalise();
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let rotation = look_at(&eye, &target, &up, handedness).unwrap();
+            assert!((rotation.determinant() - 1.0).abs() < 1e-12);
+            for i in 0..3 {
+                assert_close((rotation.transpose() * rotation).column(i), Matrix3D::identity().column(i), 1e-12);
+            }
+            let z = if handedness == Handedness::Right { -forward } else { forward };
+            assert_close(rotation.column(2), z, 1e-12);
+            // Up stays on the same side as the requested up
+            assert!(rotation.column(1).dot(&up) > 0.0);
+        }
+        assert!(look_at(&eye, &eye, &up, Handedness::Right).is_none());
+    }
+
+    #[test]
+    fn test_look_at_parallel_up() {
+        let eye = Vector3D::new(0.0, 10.0, 0.0);
+        for target in [Vector3D::default(), Vector3D::new(0.0, 20.0, 0.0)] {
+            let rotation = look_at(&eye, &target, &Vector3D::new(0.0, 1.0, 0.0), Handedness::Right).unwrap();
+            assert!((rotation.determinant() - 1.0).abs() < 1e-12);
+            for i in 0..3 {
+                assert!((rotation.column(i).magnitude() - 1.0).abs() < 1e-12);
+            }
+            assert_close(-rotation.column(2), (target - eye).normalise(), 1e-12);
+        }
+        // Zero up vector falls back too
+        let rotation = look_at(&eye, &Vector3D::default(), &Vector3D::default(), Handedness::Right).unwrap();
+        assert!((rotation.determinant() - 1.0).abs() < 1e-12);
+    }
+
+    fn cameras() -> Vec<Camera> {
+        let eye = Vector3D::new(4.0, 3.0, 8.0);
+        let target = Vector3D::new(0.0, 1.0, 0.0);
+        let up = Vector3D::new(0.0, 1.0, 0.0);
+        let mut cameras = Vec::new();
+        for depth in [DepthRange::ZeroToOne, DepthRange::NegativeOneToOne] {
+            for reverse_z in [false, true] {
+                for handedness in [Handedness::Right, Handedness::Left] {
+                    let options = ProjectionOptions::new(depth, reverse_z, handedness);
+                    for far in [50.0, f64::INFINITY] {
+                        let projection = perspective(0.9, 1.5, 0.25, far, &options);
+                        cameras.push(Camera::look_at(&eye, &target, &up, projection, options).unwrap());
+                    }
+                    let projection = orthographic(-6.0, 6.0, -4.0, 4.0, 0.5, 40.0, &options).unwrap();
+                    cameras.push(Camera::look_at(&eye, &target, &up, projection, options).unwrap());
+                }
+            }
+        }
+        cameras
+    }
+
+    #[test]
+    fn test_camera_round_trip() {
+        let mut viewport = Viewport::new(10.0, 20.0, 1200.0, 800.0);
+        viewport.min_depth = 0.1;
+        viewport.max_depth = 0.9;
+        let points = [Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(1.5, -0.5, 2.0), Vector3D::new(-3.0, 2.0, -10.0)];
+        for camera in cameras() {
+            for point in points {
+                let screen = camera.world_to_screen(&point, &viewport).unwrap();
+                let back = camera.screen_to_world(&screen, &viewport).unwrap();
+                assert_close(back, point, 1e-6);
+
+                // The picking ray through the pixel passes through the point
+                let ray = camera.picking_ray(screen.x, screen.y, &viewport).unwrap();
+                let along = (point - ray.origin).dot(&ray.direction);
+                assert!(along > 0.0);
+                assert_close(ray.at(along), point, 1e-6);
+            }
+            assert_eq!(camera.frustum().classify_point(&Vector3D::new(0.0, 1.0, 0.0)), Containment::Inside);
+        }
+    }
+
+    #[test]
+    fn test_world_to_screen() {
+        let options = ProjectionOptions::default();
+        let projection = perspective(std::f64::consts::FRAC_PI_2, 1.0, 1.0, 100.0, &options);
+        let camera = Camera::new(Vector3D::default(), Matrix3D::identity(), projection, options);
+        let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+
+        // Top-left of the near plane lands on the top-left pixel
+        let screen = camera.world_to_screen(&Vector3D::new(-1.0, 1.0, -1.0), &viewport).unwrap();
+        assert_close(screen, Vector3D::new(0.0, 0.0, 0.0), 1e-12);
+        let screen = camera.world_to_screen(&Vector3D::new(50.0, -50.0, -50.0), &viewport).unwrap();
+        assert_close(Vector3D::new(screen.x, screen.y, 0.0), Vector3D::new(200.0, 100.0, 0.0), 1e-9);
+        // Behind the camera
+        assert!(camera.world_to_screen(&Vector3D::new(0.0, 0.0, 5.0), &viewport).is_none());
+
+        // An orthographic w is always 1, yet points behind it are still rejected
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let options = ProjectionOptions { handedness, ..Default::default() };
+            let projection = orthographic(-2.0, 2.0, -1.0, 1.0, -10.0, 10.0, &options).unwrap();
+            let camera = Camera::look_at(&Vector3D::new(0.0, 0.0, 5.0), &Vector3D::default(),
+                                         &Vector3D::new(0.0, 1.0, 0.0), projection, options).unwrap();
+            let screen = camera.world_to_screen(&Vector3D::new(0.0, 0.5, 2.0), &viewport).unwrap();
+            assert_close(Vector3D::new(screen.x, screen.y, 0.0), Vector3D::new(100.0, 25.0, 0.0), 1e-9);
+            assert!(camera.world_to_screen(&Vector3D::new(0.0, 0.0, 8.0), &viewport).is_none());
+        }
+    }
+}
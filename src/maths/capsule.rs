@@ -0,0 +1,201 @@
+use super::{closest_point_segment, closest_segment_segment, Aabb, Matrix3D, Obb, Ray, RayHit, Sphere, Vector3D};
+
+/// Capsule: every point within `radius` of the segment `a`-`b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub a: Vector3D,
+    pub b: Vector3D,
+    pub radius: f64,
+}
+
+impl Capsule {
+    /// Creates a new Capsule
+    ///
+    /// # Arguments
+    /// - `a` - One end of the core segment
+    /// - `b` - The other end of the core segment
+    /// - `radius` - The distance from the segment to the surface
+    pub fn new(a: Vector3D, b: Vector3D, radius: f64) -> Self {
+        Self { a, b, radius }
+    }
+
+    /// Returns the point on the core segment closest to `point`
+    pub fn closest_point_on_segment(&self, point: &Vector3D) -> Vector3D {
+        closest_point_segment(point, &self.a, &self.b).point_b
+    }
+
+    /// Returns true if `point` is inside the capsule or on its surface
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Capsule, Vector3D};
+    ///
+    /// let capsule = Capsule::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 4.0), 1.0);
+    /// assert!(capsule.contains_point(&Vector3D::new(1.0, 0.0, 2.0)));
+    /// assert!(capsule.contains_point(&Vector3D::new(0.0, 0.0, 5.0)));
+    /// assert!(!capsule.contains_point(&Vector3D::new(1.0, 0.0, 5.0)));
+    /// ```
+    pub fn contains_point(&self, point: &Vector3D) -> bool {
+        (*point - self.closest_point_on_segment(point)).magnitude_squared() <= self.radius * self.radius
+    }
+
+    /// Returns true if `other` lies entirely inside this capsule
+    pub fn contains_capsule(&self, other: &Capsule) -> bool {
+        // Distance to a segment is convex, so checking both end spheres covers the whole capsule
+        [other.a, other.b].iter().all(|p| {
+            (*p - self.closest_point_on_segment(p)).magnitude() + other.radius <= self.radius
+        })
+    }
+
+    /// Returns true if the capsule overlaps a Sphere
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let radii = self.radius + sphere.radius;
+        (sphere.center - self.closest_point_on_segment(&sphere.center)).magnitude_squared() <= radii * radii
+    }
+
+    /// Returns true if the two capsules overlap, touching counts
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Capsule, Vector3D};
+    ///
+    /// let a = Capsule::new(Vector3D::new(-1.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), 0.5);
+    /// let b = Capsule::new(Vector3D::new(0.0, -1.0, 1.0), Vector3D::new(0.0, 1.0, 1.0), 0.5);
+    /// let c = Capsule::new(Vector3D::new(0.0, -1.0, 1.5), Vector3D::new(0.0, 1.0, 1.5), 0.5);
+    /// assert!(a.intersects_capsule(&b));
+    /// assert!(!a.intersects_capsule(&c));
+    /// ```
+    pub fn intersects_capsule(&self, other: &Capsule) -> bool {
+        let radii = self.radius + other.radius;
+        closest_segment_segment(&self.a, &self.b, &other.a, &other.b).distance_squared <= radii * radii
+    }
+
+    /// Returns true if the capsule overlaps an Obb
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        obb.intersects_capsule(self)
+    }
+
+    /// Returns true if the capsule overlaps an Aabb
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        !aabb.is_empty() && Obb::from_aabb(aabb).intersects_capsule(self)
+    }
+
+    /// Returns the tightest Aabb around the capsule
+    pub fn aabb(&self) -> Aabb {
+        let r = Vector3D::new(self.radius, self.radius, self.radius);
+        Aabb::new(self.a.min(&self.b) - r, self.a.max(&self.b) + r)
+    }
+
+    /// Returns the capsule moved by `p -> rotation * p + translation`
+    ///
+    /// `rotation` is assumed to be a pure rotation, so the radius is unchanged.
+    pub fn transform(&self, rotation: &Matrix3D, translation: &Vector3D) -> Self {
+        Self {
+            a: *rotation * self.a + *translation,
+            b: *rotation * self.b + *translation,
+            radius: self.radius,
+        }
+    }
+
+    /// Returns a capsule containing both capsules
+    ///
+    /// The new core segment joins the two furthest apart endpoints and the
+    /// radius is grown until both inputs fit. This is not the minimal
+    /// enclosing capsule but it is cheap and always encloses.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Capsule, Vector3D};
+    ///
+    /// let a = Capsule::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), 0.5);
+    /// let b = Capsule::new(Vector3D::new(3.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 0.0), 0.5);
+    /// let merged = a.merge(&b);
+    /// assert!(merged.contains_capsule(&a) && merged.contains_capsule(&b));
+    /// assert_eq!(merged.radius, 0.5);
+    /// ```
+    pub fn merge(&self, other: &Capsule) -> Self {
+        let ends = [self.a, self.b, other.a, other.b];
+        let mut axis = (self.a, self.b);
+        let mut longest = -1.0;
+        for i in 0..ends.len() {
+            for j in i + 1..ends.len() {
+                let length = (ends[j] - ends[i]).magnitude_squared();
+                if length > longest {
+                    longest = length;
+                    axis = (ends[i], ends[j]);
+                }
+            }
+        }
+        let mut merged = Self::new(axis.0, axis.1, 0.0);
+        let radius = [(self.a, self.radius), (self.b, self.radius), (other.a, other.radius), (other.b, other.radius)]
+            .iter()
+            .map(|(p, r)| (*p - merged.closest_point_on_segment(p)).magnitude() + r)
+            .fold(0.0, f64::max);
+        merged.radius = radius;
+        merged
+    }
+
+    /// Intersects a ray with the capsule, see `Ray::intersect_capsule`
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<RayHit> {
+        ray.intersect_capsule(&self.a, &self.b, self.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capsule_containment() {
+        let capsule = Capsule::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 4.0), 1.0);
+        assert!(capsule.contains_point(&Vector3D::new(0.0, 0.0, -1.0)));
+        assert!(!capsule.contains_point(&Vector3D::new(0.0, 0.0, -1.1)));
+
+        let inner = Capsule::new(Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(0.0, 0.5, 3.0), 0.5);
+        assert!(capsule.contains_capsule(&inner));
+        assert!(!inner.contains_capsule(&capsule));
+
+        let aabb = capsule.aabb();
+        assert_eq!(aabb.min, Vector3D::new(-1.0, -1.0, -1.0));
+        assert_eq!(aabb.max, Vector3D::new(1.0, 1.0, 5.0));
+    }
+
+    #[test]
+    fn test_capsule_overlaps() {
+        let capsule = Capsule::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 4.0), 1.0);
+        assert!(capsule.intersects_sphere(&Sphere::new(Vector3D::new(2.0, 0.0, 2.0), 1.0)));
+        assert!(!capsule.intersects_sphere(&Sphere::new(Vector3D::new(2.0, 0.0, 6.0), 1.0)));
+
+        // Parallel capsules side by side
+        let beside = Capsule::new(Vector3D::new(2.0, 0.0, 1.0), Vector3D::new(2.0, 0.0, 9.0), 1.0);
+        let apart = Capsule::new(Vector3D::new(2.5, 0.0, 1.0), Vector3D::new(2.5, 0.0, 9.0), 1.0);
+        assert!(capsule.intersects_capsule(&beside));
+        assert!(!capsule.intersects_capsule(&apart));
+
+        // Degenerate capsule is a sphere
+        let point = Capsule::new(Vector3D::new(0.0, 1.5, 2.0), Vector3D::new(0.0, 1.5, 2.0), 0.5);
+        assert!(capsule.intersects_capsule(&point));
+
+        let aabb = Aabb::new(Vector3D::new(1.5, -1.0, 0.0), Vector3D::new(3.0, 1.0, 1.0));
+        assert!(!capsule.intersects_aabb(&aabb));
+        assert!(beside.intersects_aabb(&aabb));
+    }
+
+    #[test]
+    fn test_capsule_merge_transform() {
+        let a = Capsule::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 2.0), 0.5);
+        let b = Capsule::new(Vector3D::new(3.0, 1.0, 0.0), Vector3D::new(4.0, 1.0, 1.0), 1.0);
+        let merged = a.merge(&b);
+        let slack = Capsule::new(merged.a, merged.b, merged.radius + 1e-12);
+        assert!(slack.contains_capsule(&a));
+        assert!(slack.contains_capsule(&b));
+
+        let rotation = Matrix3D::new(0.0, -1.0, 0.0,
+                                     1.0, 0.0, 0.0,
+                                     0.0, 0.0, 1.0);
+        let moved = b.transform(&rotation, &Vector3D::new(0.0, 0.0, 1.0));
+        assert_eq!(moved.a, Vector3D::new(-1.0, 3.0, 1.0));
+        assert_eq!(moved.b, Vector3D::new(-1.0, 4.0, 2.0));
+        assert_eq!(moved.radius, 1.0);
+    }
+}
@@ -0,0 +1,331 @@
+use super::{Frustum, Lerp, Plane, Vector3D};
+
+/// Distance from a plane within which a vertex counts as lying on it, as
+/// a fraction of the polygon's size
+///
+/// Vertices this close are kept as they are instead of creating a new
+/// vertex a hair away, which is what leaves sliver polygons and
+/// near-duplicate vertices behind. Scaling by the diagonal of the
+/// polygon's bounding box keeps it meaningful at any world scale.
+pub const CLIP_TOLERANCE: f64 = 1.0e-7;
+
+/// A polygon vertex that can be clipped
+///
+/// Clipping creates new vertices by interpolating the two ends of a cut
+/// edge, so every attribute is blended with `Lerp`. Positions alone and
+/// `(position, attributes)` pairs already implement it.
+pub trait ClipVertex: Lerp + Clone {
+    /// Returns the position of the vertex
+    fn position(&self) -> Vector3D;
+}
+
+impl ClipVertex for Vector3D {
+    fn position(&self) -> Vector3D {
+        *self
+    }
+}
+
+impl<T: Lerp + Clone> ClipVertex for (Vector3D, T) {
+    fn position(&self) -> Vector3D {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Front,
+    Back,
+    On,
+}
+
+/// Clips a convex polygon to the front of a plane with Sutherland–Hodgman
+///
+/// Returns an empty polygon if nothing is left. A polygon lying in the
+/// plane is kept whole.
+///
+/// # Arguments
+/// - `polygon` - The vertices of a convex polygon in order
+/// - `plane` - The plane, the side its normal faces is kept
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{clip_polygon, Plane, Vector3D};
+///
+/// // A square with a UV at each corner, cut in half along x = 0.5
+/// let square = vec![
+///     (Vector3D::new(0.0, 0.0, 0.0), [0.0, 0.0]),
+///     (Vector3D::new(1.0, 0.0, 0.0), [1.0, 0.0]),
+///     (Vector3D::new(1.0, 1.0, 0.0), [1.0, 1.0]),
+///     (Vector3D::new(0.0, 1.0, 0.0), [0.0, 1.0]),
+/// ];
+/// let plane = Plane::from_point_normal(Vector3D::new(0.5, 0.0, 0.0), Vector3D::new(-1.0, 0.0, 0.0));
+/// let half = clip_polygon(&square, &plane);
+/// assert_eq!(half.len(), 4);
+/// assert!(half.contains(&(Vector3D::new(0.5, 1.0, 0.0), [0.5, 1.0])));
+/// ```
+pub fn clip_polygon<V: ClipVertex>(polygon: &[V], plane: &Plane) -> Vec<V> {
+    split(polygon, plane, tolerance(polygon), true, false).0
+}
+
+/// Clips a convex polygon to the region in front of every plane
+///
+/// # Arguments
+/// - `polygon` - The vertices of a convex polygon in order
+/// - `planes` - The planes, each keeping the side its normal faces
+pub fn clip_polygon_planes<V: ClipVertex>(polygon: &[V], planes: &[Plane]) -> Vec<V> {
+    // Measured on the input so every plane uses the same tolerance
+    let tolerance = tolerance(polygon);
+    let mut clipped = polygon.to_vec();
+    for plane in planes {
+        if clipped.is_empty() {
+            break;
+        }
+        clipped = split(&clipped, plane, tolerance, true, false).0;
+    }
+    clipped
+}
+
+/// Clips a convex polygon to the inside of a frustum
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{clip_polygon_frustum, Frustum, Matrix3D, Vector3D};
+///
+/// let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(), std::f64::consts::FRAC_PI_2, 1.0, 1.0, 10.0);
+/// // A huge triangle across the view at z = -5 is cut down to the 10 x 10 view square
+/// let triangle = [Vector3D::new(-100.0, -100.0, -5.0), Vector3D::new(100.0, -100.0, -5.0), Vector3D::new(0.0, 100.0, -5.0)];
+/// let clipped = clip_polygon_frustum(&triangle, &frustum);
+/// assert_eq!(clipped.len(), 4);
+/// assert!(clipped.iter().all(|v| v.x.abs() <= 5.0 + 1e-9 && v.y.abs() <= 5.0 + 1e-9));
+/// ```
+pub fn clip_polygon_frustum<V: ClipVertex>(polygon: &[V], frustum: &Frustum) -> Vec<V> {
+    clip_polygon_planes(polygon, &frustum.planes)
+}
+
+/// Splits a convex polygon into the parts in front of and behind a plane
+///
+/// Vertices on the plane go to both parts, so the two share the cut edge
+/// exactly. A polygon lying in the plane goes to the front.
+///
+/// # Arguments
+/// - `polygon` - The vertices of a convex polygon in order
+/// - `plane` - The splitting plane
+pub fn split_polygon<V: ClipVertex>(polygon: &[V], plane: &Plane) -> (Vec<V>, Vec<V>) {
+    split(polygon, plane, tolerance(polygon), true, true)
+}
+
+/// Returns the absolute clip tolerance for a polygon
+fn tolerance<V: ClipVertex>(polygon: &[V]) -> f64 {
+    let Some(first) = polygon.first() else {
+        return 0.0;
+    };
+    let (min, max) = polygon.iter().fold((first.position(), first.position()), |(min, max), v| {
+        let p = v.position();
+        (min.min(&p), max.max(&p))
+    });
+    (max - min).magnitude() * CLIP_TOLERANCE
+}
+
+/// Sutherland–Hodgman over one plane, building only the requested sides
+fn split<V: ClipVertex>(polygon: &[V], plane: &Plane, tolerance: f64, want_front: bool, want_back: bool) -> (Vec<V>, Vec<V>) {
+    let distances: Vec<f64> = polygon.iter().map(|v| plane.signed_distance(&v.position())).collect();
+    let sides: Vec<Side> = distances
+        .iter()
+        .map(|&d| {
+            if d > tolerance {
+                Side::Front
+            } else if d < -tolerance {
+                Side::Back
+            } else {
+                Side::On
+            }
+        })
+        .collect();
+
+    // Nothing is cut, so hand the polygon over untouched
+    let keep = |wanted: bool| if wanted { polygon.to_vec() } else { Vec::new() };
+    if !sides.contains(&Side::Back) {
+        return (keep(want_front), Vec::new());
+    }
+    if !sides.contains(&Side::Front) {
+        return (Vec::new(), keep(want_back));
+    }
+
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    for i in 0..polygon.len() {
+        let j = (i + 1) % polygon.len();
+        let (a, side_a, side_b) = (&polygon[i], sides[i], sides[j]);
+        if side_a != Side::Back && want_front {
+            front.push(a.clone());
+        }
+        if side_a != Side::Front && want_back {
+            back.push(a.clone());
+        }
+        let crosses = matches!((side_a, side_b), (Side::Front, Side::Back) | (Side::Back, Side::Front));
+        if crosses {
+            let t = distances[i] / (distances[i] - distances[j]);
+            let cut = a.lerp(&polygon[j], t);
+            if want_front {
+                front.push(cut.clone());
+            }
+            if want_back {
+                back.push(cut);
+            }
+        }
+    }
+    (tidy(front, tolerance), tidy(back, tolerance))
+}
+
+/// Drops repeated vertices and anything too small to be a polygon
+fn tidy<V: ClipVertex>(mut polygon: Vec<V>, tolerance: f64) -> Vec<V> {
+    polygon.dedup_by(|b, a| (b.position() - a.position()).magnitude_squared() <= tolerance * tolerance);
+    while polygon.len() > 1 {
+        let first = polygon[0].position();
+        let last = polygon[polygon.len() - 1].position();
+        if (first - last).magnitude_squared() > tolerance * tolerance {
+            break;
+        }
+        polygon.pop();
+    }
+    if polygon.len() < 3 {
+        polygon.clear();
+    }
+    polygon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::Matrix3D;
+
+    fn area(polygon: &[Vector3D]) -> f64 {
+        let mut sum = Vector3D::default();
+        for i in 0..polygon.len() {
+            sum = sum + polygon[i].cross(&polygon[(i + 1) % polygon.len()]);
+        }
+        sum.magnitude() * 0.5
+    }
+
+    /// An attribute that is a linear function of position, so it can be
+    /// checked at every clipped vertex
+    fn attribute(p: &Vector3D) -> [f64; 2] {
+        [p.x * 2.0 - p.y + 1.0, p.z * 0.5 + p.x]
+    }
+
+    fn hexagon() -> Vec<(Vector3D, [f64; 2])> {
+        (0..6)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / 3.0;
+                let p = Vector3D::new(angle.cos() * 2.0, angle.sin() * 2.0, 0.3 * angle.cos());
+                (p, attribute(&p))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clip_split_polygon() {
+        let polygon = hexagon();
+        let positions: Vec<Vector3D> = polygon.iter().map(|v| v.0).collect();
+        let plane = Plane::from_point_normal(Vector3D::new(0.3, 0.1, 0.0), Vector3D::new(1.0, 0.4, 0.2));
+        let (front, back) = split_polygon(&polygon, &plane);
+        assert!(!front.is_empty() && !back.is_empty());
+        for (p, uv) in front.iter().chain(&back) {
+            let expected = attribute(p);
+            assert!((uv[0] - expected[0]).abs() < 1e-12 && (uv[1] - expected[1]).abs() < 1e-12);
+        }
+        let tolerance = tolerance(&polygon);
+        assert!(front.iter().all(|(p, _)| plane.signed_distance(p) >= -tolerance));
+        assert!(back.iter().all(|(p, _)| plane.signed_distance(p) <= tolerance));
+        let front: Vec<Vector3D> = front.into_iter().map(|v| v.0).collect();
+        let back: Vec<Vector3D> = back.into_iter().map(|v| v.0).collect();
+        assert!((area(&front) + area(&back) - area(&positions)).abs() < 1e-12);
+        assert_eq!(clip_polygon(&positions, &plane), front);
+
+        // Entirely on one side comes back untouched
+        let far = Plane::from_point_normal(Vector3D::new(10.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0));
+        assert_eq!(split_polygon(&positions, &far), (Vec::new(), positions.clone()));
+        assert_eq!(split_polygon(&positions, &far.flipped()), (positions.clone(), Vec::new()));
+    }
+
+    #[test]
+    fn test_clip_tolerance() {
+        let square = [
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(1.0, 1.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+        ];
+        // A plane through the diagonal reuses the corners instead of cutting
+        let diagonal = Plane::from_point_normal(Vector3D::default(), Vector3D::new(1.0, -1.0, 0.0));
+        let (front, back) = split_polygon(&square, &diagonal);
+        assert_eq!(front, vec![square[0], square[1], square[2]]);
+        assert_eq!(back, vec![square[0], square[2], square[3]]);
+
+        // A plane grazing a corner leaves no sliver or duplicate vertex
+        let graze = Plane::from_point_normal(Vector3D::new(1.0 + 1e-9, 0.0, 0.0), Vector3D::new(1.0, -1.0, 0.0));
+        let (front, back) = split_polygon(&square, &graze);
+        assert!(front.is_empty());
+        assert_eq!(back, square.to_vec());
+        let edge = Plane::from_point_normal(Vector3D::new(1.0 - 1e-9, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0));
+        let (front, back) = split_polygon(&square, &edge);
+        assert!(front.is_empty());
+        assert_eq!(back.len(), 4);
+
+        // Coplanar polygons are kept
+        let flat = Plane::from_point_normal(Vector3D::default(), Vector3D::new(0.0, 0.0, 1.0));
+        assert_eq!(clip_polygon(&square, &flat), square.to_vec());
+    }
+
+    #[test]
+    fn test_clip_tolerance_scales() {
+        let square = |size: f64| {
+            [
+                Vector3D::new(0.0, 0.0, 0.0),
+                Vector3D::new(size, 0.0, 0.0),
+                Vector3D::new(size, size, 0.0),
+                Vector3D::new(0.0, size, 0.0),
+            ]
+        };
+        let x_plane = |x: f64| Plane::from_point_normal(Vector3D::new(x, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0));
+
+        // At kilometre scale a micron from an edge is on it, no sliver
+        let large = square(1000.0);
+        let (front, back) = split_polygon(&large, &x_plane(1e-6));
+        assert_eq!(front, large.to_vec());
+        assert!(back.is_empty());
+
+        // At micron scale the same fraction of the polygon is a real cut
+        let small = square(1e-6);
+        let (front, back) = split_polygon(&small, &x_plane(5e-8));
+        assert_eq!(front.len(), 4);
+        assert_eq!(back.len(), 4);
+        assert!((area(&front) - 0.95e-12).abs() < 1e-24);
+        assert!((area(&back) - 0.05e-12).abs() < 1e-24);
+    }
+
+    #[test]
+    fn test_clip_frustum() {
+        let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(), 1.2, 1.5, 0.5, 20.0);
+        let polygon: Vec<(Vector3D, [f64; 2])> = [
+            Vector3D::new(-30.0, -1.0, -2.0),
+            Vector3D::new(30.0, -2.0, -25.0),
+            Vector3D::new(4.0, 20.0, 3.0),
+        ]
+        .into_iter()
+        .map(|p| (p, attribute(&p)))
+        .collect();
+        let clipped = clip_polygon_frustum(&polygon, &frustum);
+        assert!(clipped.len() >= 3);
+        for (p, uv) in &clipped {
+            for plane in &frustum.planes {
+                assert!(plane.signed_distance(p) >= -1e-9);
+            }
+            let expected = attribute(p);
+            assert!((uv[0] - expected[0]).abs() < 1e-9 && (uv[1] - expected[1]).abs() < 1e-9);
+        }
+
+        let behind = [Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(1.0, 0.0, 1.0), Vector3D::new(0.0, 1.0, 1.0)];
+        assert!(clip_polygon_frustum(&behind, &frustum).is_empty());
+    }
+}
@@ -0,0 +1,575 @@
+use super::{Aabb, Obb, Vector3D, EPSILON};
+
+/// The closest pair of points between two primitives
+///
+/// `point_a` lies on the first primitive passed to the query and `point_b`
+/// on the second. Overlapping primitives give a distance of zero with both
+/// points at a shared point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoints {
+    pub point_a: Vector3D,
+    pub point_b: Vector3D,
+    pub distance_squared: f64,
+}
+
+impl ClosestPoints {
+    /// Creates a new ClosestPoints, computing the squared distance
+    ///
+    /// # Arguments
+    /// - `point_a` - The point on the first primitive
+    /// - `point_b` - The point on the second primitive
+    pub fn new(point_a: Vector3D, point_b: Vector3D) -> Self {
+        Self {
+            point_a,
+            point_b,
+            distance_squared: (point_b - point_a).magnitude_squared(),
+        }
+    }
+
+    /// Returns the distance between the two points
+    pub fn distance(&self) -> f64 {
+        self.distance_squared.sqrt()
+    }
+
+    /// Returns the same pair with the roles of the two primitives swapped
+    pub fn swapped(&self) -> Self {
+        Self {
+            point_a: self.point_b,
+            point_b: self.point_a,
+            distance_squared: self.distance_squared,
+        }
+    }
+
+    /// Picks whichever pair is closer, preferring `self` on ties
+    fn nearer(self, other: Self) -> Self {
+        if other.distance_squared < self.distance_squared { other } else { self }
+    }
+}
+
+/// Returns the closest points between `point` and segment `a`-`b`
+///
+/// A zero-length segment is treated as the single point `a`.
+///
+/// # Arguments
+/// - `point` - The query point
+/// - `a` - The start of the segment
+/// - `b` - The end of the segment
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_point_segment, Vector3D};
+///
+/// let closest = closest_point_segment(&Vector3D::new(1.0, 2.0, 0.0),
+///                                     &Vector3D::new(0.0, 0.0, 0.0),
+///                                     &Vector3D::new(4.0, 0.0, 0.0));
+/// assert_eq!(closest.point_b, Vector3D::new(1.0, 0.0, 0.0));
+/// assert_eq!(closest.distance_squared, 4.0);
+/// ```
+pub fn closest_point_segment(point: &Vector3D, a: &Vector3D, b: &Vector3D) -> ClosestPoints {
+    let ab = *b - *a;
+    let length_squared = ab.magnitude_squared();
+    if length_squared <= EPSILON * EPSILON {
+        return ClosestPoints::new(*point, *a);
+    }
+    let t = ((*point - *a).dot(&ab) / length_squared).clamp(0.0, 1.0);
+    ClosestPoints::new(*point, *a + ab * t)
+}
+
+/// Returns the closest points between segments `p1`-`q1` and `p2`-`q2`
+///
+/// Parallel segments have many closest pairs, the one nearest `p1` is
+/// returned. Zero-length segments are treated as points.
+///
+/// # Arguments
+/// - `p1` - The start of the first segment
+/// - `q1` - The end of the first segment
+/// - `p2` - The start of the second segment
+/// - `q2` - The end of the second segment
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_segment_segment, Vector3D};
+///
+/// let closest = closest_segment_segment(&Vector3D::new(-1.0, 0.0, 0.0), &Vector3D::new(1.0, 0.0, 0.0),
+///                                       &Vector3D::new(0.0, -1.0, 2.0), &Vector3D::new(0.0, 1.0, 2.0));
+/// assert_eq!(closest.point_a, Vector3D::new(0.0, 0.0, 0.0));
+/// assert_eq!(closest.point_b, Vector3D::new(0.0, 0.0, 2.0));
+/// ```
+pub fn closest_segment_segment(p1: &Vector3D, q1: &Vector3D, p2: &Vector3D, q2: &Vector3D) -> ClosestPoints {
+    let d1 = *q1 - *p1;
+    let d2 = *q2 - *p2;
+    let r = *p1 - *p2;
+    let a = d1.magnitude_squared();
+    let e = d2.magnitude_squared();
+    let f = d2.dot(&r);
+
+    if a <= EPSILON * EPSILON && e <= EPSILON * EPSILON {
+        return ClosestPoints::new(*p1, *p2);
+    }
+    if a <= EPSILON * EPSILON {
+        return closest_point_segment(p1, p2, q2);
+    }
+    let c = d1.dot(&r);
+    if e <= EPSILON * EPSILON {
+        return closest_point_segment(p2, p1, q1).swapped();
+    }
+
+    let b = d1.dot(&d2);
+    let denominator = a * e - b * b;
+    // Parallel segments have no unique answer, so start from p1
+    let mut s = if denominator > EPSILON * a * e {
+        ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+    ClosestPoints::new(*p1 + d1 * s, *p2 + d2 * t)
+}
+
+/// Returns the closest points between `point` and triangle `a`, `b`, `c`
+///
+/// Uses Ericson's Voronoi region tests. A collinear or collapsed triangle
+/// is treated as its longest edge.
+///
+/// # Arguments
+/// - `point` - The query point
+/// - `a` - The first corner of the triangle
+/// - `b` - The second corner of the triangle
+/// - `c` - The third corner of the triangle
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_point_triangle, Vector3D};
+///
+/// let (a, b, c) = (Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 0.0), Vector3D::new(0.0, 4.0, 0.0));
+/// let above = closest_point_triangle(&Vector3D::new(1.0, 1.0, 3.0), &a, &b, &c);
+/// assert_eq!(above.point_b, Vector3D::new(1.0, 1.0, 0.0));
+/// let beyond = closest_point_triangle(&Vector3D::new(5.0, 5.0, 0.0), &a, &b, &c);
+/// assert_eq!(beyond.point_b, Vector3D::new(2.0, 2.0, 0.0));
+/// ```
+pub fn closest_point_triangle(point: &Vector3D, a: &Vector3D, b: &Vector3D, c: &Vector3D) -> ClosestPoints {
+    let p = *point;
+    let ab = *b - *a;
+    let ac = *c - *a;
+
+    // Vertex region outside a
+    let ap = p - *a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return ClosestPoints::new(p, *a);
+    }
+
+    // Vertex region outside b
+    let bp = p - *b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return ClosestPoints::new(p, *b);
+    }
+
+    // Edge region of ab
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return ClosestPoints::new(p, *a + ab * v);
+    }
+
+    // Vertex region outside c
+    let cp = p - *c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return ClosestPoints::new(p, *c);
+    }
+
+    // Edge region of ac
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return ClosestPoints::new(p, *a + ac * w);
+    }
+
+    // Edge region of bc
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return ClosestPoints::new(p, *b + (*c - *b) * w);
+    }
+
+    // Face region, unless the triangle has no area to project onto
+    let total = va + vb + vc;
+    if total <= EPSILON * EPSILON * ab.magnitude_squared() * ac.magnitude_squared() {
+        return closest_point_segment(&p, a, b)
+            .nearer(closest_point_segment(&p, a, c))
+            .nearer(closest_point_segment(&p, b, c));
+    }
+    let v = vb / total;
+    let w = vc / total;
+    ClosestPoints::new(p, *a + ab * v + ac * w)
+}
+
+/// Returns the closest points between segment `p`-`q` and triangle `a`, `b`, `c`
+///
+/// If the segment passes through the triangle the crossing point is
+/// returned for both.
+///
+/// # Arguments
+/// - `p` - The start of the segment
+/// - `q` - The end of the segment
+/// - `a` - The first corner of the triangle
+/// - `b` - The second corner of the triangle
+/// - `c` - The third corner of the triangle
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_segment_triangle, Vector3D};
+///
+/// let (a, b, c) = (Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 0.0), Vector3D::new(0.0, 4.0, 0.0));
+/// let crossing = closest_segment_triangle(&Vector3D::new(1.0, 1.0, -1.0), &Vector3D::new(1.0, 1.0, 1.0), &a, &b, &c);
+/// assert_eq!(crossing.distance_squared, 0.0);
+/// assert_eq!(crossing.point_a, Vector3D::new(1.0, 1.0, 0.0));
+/// ```
+pub fn closest_segment_triangle(
+    p: &Vector3D,
+    q: &Vector3D,
+    a: &Vector3D,
+    b: &Vector3D,
+    c: &Vector3D,
+) -> ClosestPoints {
+    if let Some(crossing) = segment_triangle_crossing(p, q, a, b, c) {
+        return ClosestPoints::new(crossing, crossing);
+    }
+    // Otherwise an endpoint or a pair of edges holds the closest points
+    closest_point_triangle(p, a, b, c)
+        .nearer(closest_point_triangle(q, a, b, c))
+        .nearer(closest_segment_segment(p, q, a, b))
+        .nearer(closest_segment_segment(p, q, b, c))
+        .nearer(closest_segment_segment(p, q, c, a))
+}
+
+/// Returns the closest points between triangles `a` and `b`
+///
+/// Intersecting triangles give a distance of zero at a point on their
+/// shared region.
+///
+/// # Arguments
+/// - `a` - The corners of the first triangle
+/// - `b` - The corners of the second triangle
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_triangle_triangle, Vector3D};
+///
+/// let floor = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 0.0), Vector3D::new(0.0, 4.0, 0.0)];
+/// let above = floor.map(|v| v + Vector3D::new(1.0, 1.0, 2.0));
+/// assert_eq!(closest_triangle_triangle(&floor, &above).distance_squared, 4.0);
+/// ```
+pub fn closest_triangle_triangle(a: &[Vector3D; 3], b: &[Vector3D; 3]) -> ClosestPoints {
+    // Each candidate pair involves an edge of one triangle, and checking
+    // each edge against the whole of the other triangle finds crossings too
+    let mut best = closest_segment_triangle(&a[0], &a[1], &b[0], &b[1], &b[2]);
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        if i > 0 {
+            best = best.nearer(closest_segment_triangle(&a[i], &a[j], &b[0], &b[1], &b[2]));
+        }
+        best = best.nearer(closest_segment_triangle(&b[i], &b[j], &a[0], &a[1], &a[2]).swapped());
+    }
+    best
+}
+
+/// Returns the closest points between `point` and an Aabb
+///
+/// Points inside the box are their own closest point. Returns None if the
+/// box is empty.
+///
+/// # Arguments
+/// - `point` - The query point
+/// - `aabb` - The box
+pub fn closest_point_aabb(point: &Vector3D, aabb: &Aabb) -> Option<ClosestPoints> {
+    aabb.closest_point(point).map(|closest| ClosestPoints::new(*point, closest))
+}
+
+/// Returns the closest points between segment `a`-`b` and an Aabb
+///
+/// The squared distance along the segment is piecewise quadratic, changing
+/// form only where the segment crosses a face plane of the box, so each
+/// piece is minimised exactly. Returns None if the box is empty.
+///
+/// # Arguments
+/// - `a` - The start of the segment
+/// - `b` - The end of the segment
+/// - `aabb` - The box
+pub(crate) fn closest_segment_aabb(a: &Vector3D, b: &Vector3D, aabb: &Aabb) -> Option<ClosestPoints> {
+    if aabb.is_empty() {
+        return None;
+    }
+    let d = *b - *a;
+    let mut breaks = vec![0.0, 1.0];
+    for i in 0..3 {
+        if d[i] != 0.0 {
+            for face in [aabb.min[i], aabb.max[i]] {
+                let t = (face - a[i]) / d[i];
+                if t > 0.0 && t < 1.0 {
+                    breaks.push(t);
+                }
+            }
+        }
+    }
+    breaks.sort_by(f64::total_cmp);
+
+    let mut best: Option<ClosestPoints> = None;
+    for piece in breaks.windows(2) {
+        let (t0, t1) = (piece[0], piece[1]);
+        // Within a piece each axis is either inside the slab or held to one face
+        let middle = *a + d * ((t0 + t1) * 0.5);
+        let (mut numerator, mut denominator) = (0.0, 0.0);
+        for i in 0..3 {
+            let face = if middle[i] < aabb.min[i] {
+                aabb.min[i]
+            } else if middle[i] > aabb.max[i] {
+                aabb.max[i]
+            } else {
+                continue;
+            };
+            numerator += d[i] * (face - a[i]);
+            denominator += d[i] * d[i];
+        }
+        let t = if denominator > 0.0 { (numerator / denominator).clamp(t0, t1) } else { t0 };
+        let point = *a + d * t;
+        let candidate = ClosestPoints::new(point, aabb.closest_point(&point)?);
+        best = Some(best.map_or(candidate, |best| best.nearer(candidate)));
+    }
+    best
+}
+
+/// Returns the closest points between `point` and an Obb
+///
+/// Points inside the box are their own closest point.
+///
+/// # Arguments
+/// - `point` - The query point
+/// - `obb` - The box
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{closest_point_obb, Matrix3D, Obb, Vector3D};
+///
+/// let obb = Obb::new(Vector3D::new(1.0, 0.0, 0.0), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+/// let closest = closest_point_obb(&Vector3D::new(5.0, 0.5, 0.0), &obb);
+/// assert_eq!(closest.point_b, Vector3D::new(2.0, 0.5, 0.0));
+/// assert_eq!(closest.distance_squared, 9.0);
+/// ```
+pub fn closest_point_obb(point: &Vector3D, obb: &Obb) -> ClosestPoints {
+    ClosestPoints::new(*point, obb.closest_point(point))
+}
+
+/// Returns where segment `p`-`q` crosses triangle `a`, `b`, `c`, if it does
+///
+/// Segments lying in the triangle's plane are left to the edge tests.
+fn segment_triangle_crossing(p: &Vector3D, q: &Vector3D, a: &Vector3D, b: &Vector3D, c: &Vector3D) -> Option<Vector3D> {
+    let ab = *b - *a;
+    let ac = *c - *a;
+    let normal = ab.cross(&ac);
+    let pq = *q - *p;
+    let denominator = normal.dot(&pq);
+    if denominator.abs() <= EPSILON * normal.magnitude() * pq.magnitude() {
+        return None;
+    }
+    let t = normal.dot(&(*a - *p)) / denominator;
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    let hit = *p + pq * t;
+
+    // Barycentric coordinates of the hit, all non-negative inside
+    let ah = hit - *a;
+    let (d00, d01, d11) = (ab.dot(&ab), ab.dot(&ac), ac.dot(&ac));
+    let (d20, d21) = (ah.dot(&ab), ah.dot(&ac));
+    let area = d00 * d11 - d01 * d01;
+    let v = (d11 * d20 - d01 * d21) / area;
+    let w = (d00 * d21 - d01 * d20) / area;
+    (v >= 0.0 && w >= 0.0 && v + w <= 1.0).then_some(hit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::Matrix3D;
+
+    const TRIANGLE: [Vector3D; 3] = [
+        Vector3D { x: 0.0, y: 0.0, z: 0.0 },
+        Vector3D { x: 4.0, y: 0.0, z: 0.0 },
+        Vector3D { x: 0.0, y: 4.0, z: 0.0 },
+    ];
+
+    #[test]
+    fn test_closest_point_segment() {
+        let a = Vector3D::new(1.0, 1.0, 1.0);
+        let b = Vector3D::new(3.0, 1.0, 1.0);
+        assert_eq!(closest_point_segment(&Vector3D::new(0.0, 1.0, 1.0), &a, &b).point_b, a);
+        assert_eq!(closest_point_segment(&Vector3D::new(9.0, 1.0, 1.0), &a, &b).point_b, b);
+
+        // Zero-length segment
+        let closest = closest_point_segment(&Vector3D::new(1.0, 4.0, 5.0), &a, &a);
+        assert_eq!(closest.point_b, a);
+        assert_eq!(closest.distance_squared, 25.0);
+    }
+
+    #[test]
+    fn test_closest_segment_segment() {
+        // Endpoints clamp
+        let closest = closest_segment_segment(
+            &Vector3D::new(0.0, 0.0, 0.0),
+            &Vector3D::new(1.0, 0.0, 0.0),
+            &Vector3D::new(3.0, -1.0, 0.0),
+            &Vector3D::new(3.0, 1.0, 0.0),
+        );
+        assert_eq!(closest.point_a, Vector3D::new(1.0, 0.0, 0.0));
+        assert_eq!(closest.point_b, Vector3D::new(3.0, 0.0, 0.0));
+
+        // Parallel, overlapping along their length
+        let closest = closest_segment_segment(
+            &Vector3D::new(0.0, 0.0, 0.0),
+            &Vector3D::new(4.0, 0.0, 0.0),
+            &Vector3D::new(1.0, 2.0, 0.0),
+            &Vector3D::new(6.0, 2.0, 0.0),
+        );
+        assert_eq!(closest.distance_squared, 4.0);
+
+        // Both zero-length, and one zero-length
+        let p = Vector3D::new(1.0, 2.0, 3.0);
+        let q = Vector3D::new(1.0, 2.0, 5.0);
+        assert_eq!(closest_segment_segment(&p, &p, &q, &q).distance_squared, 4.0);
+        let closest = closest_segment_segment(&p, &q, &Vector3D::new(0.0, 0.0, 4.0), &Vector3D::new(0.0, 0.0, 4.0));
+        assert_eq!(closest.point_a, Vector3D::new(1.0, 2.0, 4.0));
+        assert_eq!(closest.point_b, Vector3D::new(0.0, 0.0, 4.0));
+    }
+
+    #[test]
+    fn test_closest_point_triangle() {
+        let [a, b, c] = TRIANGLE;
+        // Each Voronoi region
+        let cases = [
+            (Vector3D::new(-1.0, -1.0, 0.0), a),
+            (Vector3D::new(6.0, -1.0, 0.0), b),
+            (Vector3D::new(-1.0, 6.0, 0.0), c),
+            (Vector3D::new(2.0, -3.0, 1.0), Vector3D::new(2.0, 0.0, 0.0)),
+            (Vector3D::new(-3.0, 2.0, 1.0), Vector3D::new(0.0, 2.0, 0.0)),
+            (Vector3D::new(3.0, 3.0, 0.0), Vector3D::new(2.0, 2.0, 0.0)),
+            (Vector3D::new(1.0, 2.0, -5.0), Vector3D::new(1.0, 2.0, 0.0)),
+        ];
+        for (point, expected) in cases {
+            assert_eq!(closest_point_triangle(&point, &a, &b, &c).point_b, expected);
+        }
+
+        // Collinear triangle behaves like its longest edge
+        let m = Vector3D::new(2.0, 0.0, 0.0);
+        let closest = closest_point_triangle(&Vector3D::new(3.0, 1.0, 0.0), &a, &m, &b);
+        assert_eq!(closest.point_b, Vector3D::new(3.0, 0.0, 0.0));
+        assert_eq!(closest.distance_squared, 1.0);
+
+        // Fully collapsed triangle is a point
+        let closest = closest_point_triangle(&Vector3D::new(0.0, 0.0, 2.0), &a, &a, &a);
+        assert_eq!(closest.point_b, a);
+    }
+
+    #[test]
+    fn test_closest_segment_triangle() {
+        let [a, b, c] = TRIANGLE;
+        // Parallel above the face
+        let closest = closest_segment_triangle(&Vector3D::new(1.0, 1.0, 2.0), &Vector3D::new(2.0, 1.0, 2.0), &a, &b, &c);
+        assert_eq!(closest.distance_squared, 4.0);
+
+        // Passing outside an edge
+        let closest = closest_segment_triangle(&Vector3D::new(3.0, 3.0, -1.0), &Vector3D::new(3.0, 3.0, 1.0), &a, &b, &c);
+        assert_eq!(closest.point_b, Vector3D::new(2.0, 2.0, 0.0));
+        assert!((closest.distance_squared - 2.0).abs() < 1e-12);
+
+        // Zero-length segment is a point query
+        let p = Vector3D::new(1.0, 1.0, 3.0);
+        assert_eq!(closest_segment_triangle(&p, &p, &a, &b, &c), closest_point_triangle(&p, &a, &b, &c));
+
+        // Segment lying in the plane and crossing an edge
+        let closest = closest_segment_triangle(&Vector3D::new(-1.0, 1.0, 0.0), &Vector3D::new(1.0, 1.0, 0.0), &a, &b, &c);
+        assert_eq!(closest.distance_squared, 0.0);
+    }
+
+    #[test]
+    fn test_closest_triangle_triangle() {
+        // Crossing triangles
+        let upright = [Vector3D::new(1.0, 1.0, -1.0), Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(1.0, -3.0, 0.0)];
+        assert_eq!(closest_triangle_triangle(&TRIANGLE, &upright).distance_squared, 0.0);
+
+        // Edge against edge
+        let beyond = [Vector3D::new(3.0, 3.0, 0.0), Vector3D::new(6.0, 6.0, 1.0), Vector3D::new(6.0, 6.0, -1.0)];
+        let closest = closest_triangle_triangle(&TRIANGLE, &beyond);
+        assert_eq!(closest.point_a, Vector3D::new(2.0, 2.0, 0.0));
+        assert_eq!(closest.point_b, Vector3D::new(3.0, 3.0, 0.0));
+        assert_eq!(closest.swapped(), closest_triangle_triangle(&beyond, &TRIANGLE));
+
+        // Collinear triangles
+        let flat_a = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(2.0, 0.0, 0.0)];
+        let flat_b = flat_a.map(|v| v + Vector3D::new(0.0, 3.0, 4.0));
+        assert_eq!(closest_triangle_triangle(&flat_a, &flat_b).distance(), 5.0);
+    }
+
+    #[test]
+    fn test_closest_point_boxes() {
+        let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+        let closest = closest_point_aabb(&Vector3D::new(2.0, 0.5, 3.0), &aabb).unwrap();
+        assert_eq!(closest.point_b, Vector3D::new(1.0, 0.5, 1.0));
+        assert_eq!(closest.distance_squared, 5.0);
+        assert!(closest_point_aabb(&Vector3D::default(), &Aabb::empty()).is_none());
+
+        let rotation = Matrix3D::new(0.0, -1.0, 0.0,
+                                     1.0, 0.0, 0.0,
+                                     0.0, 0.0, 1.0);
+        let obb = Obb::new(Vector3D::default(), rotation, Vector3D::new(2.0, 1.0, 1.0));
+        let closest = closest_point_obb(&Vector3D::new(0.0, 5.0, 0.0), &obb);
+        assert_eq!(closest.point_b, Vector3D::new(0.0, 2.0, 0.0));
+        // Inside points are their own closest point
+        assert_eq!(closest_point_obb(&Vector3D::new(0.5, 0.5, 0.5), &obb).distance_squared, 0.0);
+    }
+
+    #[test]
+    fn test_closest_segment_aabb() {
+        let aabb = Aabb::new(Vector3D::new(-1.0, -2.0, 0.0), Vector3D::new(1.0, 2.0, 3.0));
+        let segments = [
+            (Vector3D::new(-4.0, 5.0, 1.0), Vector3D::new(3.0, 4.0, 6.0)),
+            (Vector3D::new(2.0, 3.0, -1.0), Vector3D::new(2.5, -3.0, -2.0)),
+            (Vector3D::new(-3.0, 0.0, 1.0), Vector3D::new(3.0, 0.5, 2.0)),
+            (Vector3D::new(5.0, 5.0, 5.0), Vector3D::new(5.0, 5.0, 5.0)),
+            (Vector3D::new(1.5, -6.0, 4.0), Vector3D::new(1.5, 6.0, 4.0)),
+        ];
+        for (a, b) in segments {
+            let closest = closest_segment_aabb(&a, &b, &aabb).unwrap();
+            assert!((closest.distance_squared - (closest.point_b - closest.point_a).magnitude_squared()).abs() < 1e-12);
+            assert!(aabb.contains_point(&closest.point_b));
+            // No point along the segment is closer
+            for i in 0..=1000 {
+                let p = a + (b - a) * (i as f64 / 1000.0);
+                let distance = closest_point_aabb(&p, &aabb).unwrap().distance_squared;
+                assert!(closest.distance_squared <= distance + 1e-12);
+            }
+        }
+        // Crossing the box touches it
+        assert_eq!(closest_segment_aabb(&segments[2].0, &segments[2].1, &aabb).unwrap().distance_squared, 0.0);
+        let parallel = closest_segment_aabb(&segments[4].0, &segments[4].1, &aabb).unwrap();
+        assert!((parallel.distance_squared - 1.25).abs() < 1e-12);
+        assert!(closest_segment_aabb(&segments[0].0, &segments[0].1, &Aabb::empty()).is_none());
+
+        let unit = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+        let corner = closest_segment_aabb(&Vector3D::new(-1.0, 4.0, 0.5), &Vector3D::new(4.0, -1.0, 0.5), &unit).unwrap();
+        assert_eq!(corner.point_b, Vector3D::new(1.0, 1.0, 0.5));
+        assert!((corner.distance_squared - 0.5).abs() < 1e-12);
+    }
+}
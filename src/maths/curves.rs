@@ -0,0 +1,677 @@
+use super::{Vector3D, EPSILON};
+
+/// A parametric curve through space
+///
+/// Derivatives are with respect to the curve's own parameter, which is
+/// generally not arc length; see `ArcLengthTable` for constant speed.
+pub trait Curve {
+    /// Returns the point at parameter `t`
+    fn position(&self, t: f64) -> Vector3D;
+
+    /// Returns the first derivative, the velocity, at parameter `t`
+    fn derivative(&self, t: f64) -> Vector3D;
+
+    /// Returns the second derivative, the acceleration, at parameter `t`
+    fn second_derivative(&self, t: f64) -> Vector3D;
+
+    /// Returns the range of `t` the curve is defined over
+    fn domain(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+
+    /// Returns the parameters inside the domain where pieces of the curve
+    /// join, in increasing order
+    ///
+    /// Derivatives may jump there, so numerical methods such as arc length
+    /// integration split at these points instead of stepping across them.
+    fn breakpoints(&self) -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+impl<C: Curve + ?Sized> Curve for &C {
+    fn position(&self, t: f64) -> Vector3D {
+        (**self).position(t)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        (**self).derivative(t)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vector3D {
+        (**self).second_derivative(t)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (**self).domain()
+    }
+
+    fn breakpoints(&self) -> Vec<f64> {
+        (**self).breakpoints()
+    }
+}
+
+/// Quadratic Bézier curve over three control points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadraticBezier {
+    pub points: [Vector3D; 3],
+}
+
+impl QuadraticBezier {
+    /// Creates a new QuadraticBezier from its control points
+    pub fn new(p0: Vector3D, p1: Vector3D, p2: Vector3D) -> Self {
+        Self { points: [p0, p1, p2] }
+    }
+
+    /// Splits the curve at `t` into two curves covering `0..t` and `t..1`
+    pub fn subdivide(&self, t: f64) -> (Self, Self) {
+        let [p0, p1, p2] = self.points;
+        let a = p0 + (p1 - p0) * t;
+        let b = p1 + (p2 - p1) * t;
+        let mid = a + (b - a) * t;
+        (Self::new(p0, a, mid), Self::new(mid, b, p2))
+    }
+
+    /// Returns the same curve as a cubic by degree elevation
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Curve, QuadraticBezier, Vector3D};
+    ///
+    /// let quadratic = QuadraticBezier::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(3.0, 3.0, 0.0), Vector3D::new(6.0, 0.0, 0.0));
+    /// let cubic = quadratic.elevate();
+    /// assert_eq!(cubic.points[1], Vector3D::new(2.0, 2.0, 0.0));
+    /// assert_eq!(cubic.position(0.5), quadratic.position(0.5));
+    /// ```
+    pub fn elevate(&self) -> CubicBezier {
+        let [p0, p1, p2] = self.points;
+        CubicBezier::new(p0, p0 / 3.0 + p1 * (2.0 / 3.0), p1 * (2.0 / 3.0) + p2 / 3.0, p2)
+    }
+}
+
+impl Curve for QuadraticBezier {
+    fn position(&self, t: f64) -> Vector3D {
+        let [p0, p1, p2] = self.points;
+        let s = 1.0 - t;
+        p0 * (s * s) + p1 * (2.0 * s * t) + p2 * (t * t)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        let [p0, p1, p2] = self.points;
+        (p1 - p0) * (2.0 * (1.0 - t)) + (p2 - p1) * (2.0 * t)
+    }
+
+    fn second_derivative(&self, _t: f64) -> Vector3D {
+        let [p0, p1, p2] = self.points;
+        (p2 - p1 * 2.0 + p0) * 2.0
+    }
+}
+
+/// Cubic Bézier curve over four control points
+///
+/// The curve starts at the first point heading to the second and ends at
+/// the fourth coming from the third. It is the common form the other
+/// cubic curves convert to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    pub points: [Vector3D; 4],
+}
+
+impl CubicBezier {
+    /// Creates a new CubicBezier from its control points
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{CubicBezier, Curve, Vector3D};
+    ///
+    /// let curve = CubicBezier::new(
+    ///     Vector3D::new(0.0, 0.0, 0.0),
+    ///     Vector3D::new(0.0, 4.0, 0.0),
+    ///     Vector3D::new(8.0, 4.0, 0.0),
+    ///     Vector3D::new(8.0, 0.0, 0.0),
+    /// );
+    /// assert_eq!(curve.position(0.5), Vector3D::new(4.0, 3.0, 0.0));
+    /// assert_eq!(curve.derivative(0.0), Vector3D::new(0.0, 12.0, 0.0));
+    /// ```
+    pub fn new(p0: Vector3D, p1: Vector3D, p2: Vector3D, p3: Vector3D) -> Self {
+        Self { points: [p0, p1, p2, p3] }
+    }
+
+    /// Splits the curve at `t` into two curves covering `0..t` and `t..1`
+    /// with de Casteljau's algorithm
+    pub fn subdivide(&self, t: f64) -> (Self, Self) {
+        let [p0, p1, p2, p3] = self.points;
+        let a = p0 + (p1 - p0) * t;
+        let b = p1 + (p2 - p1) * t;
+        let c = p2 + (p3 - p2) * t;
+        let ab = a + (b - a) * t;
+        let bc = b + (c - b) * t;
+        let mid = ab + (bc - ab) * t;
+        (Self::new(p0, a, ab, mid), Self::new(mid, bc, c, p3))
+    }
+
+    /// Returns the same curve in Hermite form
+    pub fn to_hermite(&self) -> Hermite {
+        let [p0, p1, p2, p3] = self.points;
+        Hermite::new(p0, (p1 - p0) * 3.0, p3, (p3 - p2) * 3.0)
+    }
+
+    /// Returns the four B-spline control points whose single uniform
+    /// segment is this curve
+    pub fn to_bspline(&self) -> BSpline {
+        let [b0, b1, b2, b3] = self.points;
+        let p1 = b1 * 2.0 - b2;
+        let p2 = b2 * 2.0 - b1;
+        let p0 = b0 * 6.0 - p1 * 4.0 - p2;
+        let p3 = b3 * 6.0 - p1 - p2 * 4.0;
+        BSpline { points: vec![p0, p1, p2, p3] }
+    }
+}
+
+impl Curve for CubicBezier {
+    fn position(&self, t: f64) -> Vector3D {
+        let [p0, p1, p2, p3] = self.points;
+        let s = 1.0 - t;
+        p0 * (s * s * s) + p1 * (3.0 * s * s * t) + p2 * (3.0 * s * t * t) + p3 * (t * t * t)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        let [p0, p1, p2, p3] = self.points;
+        let s = 1.0 - t;
+        ((p1 - p0) * (s * s) + (p2 - p1) * (2.0 * s * t) + (p3 - p2) * (t * t)) * 3.0
+    }
+
+    fn second_derivative(&self, t: f64) -> Vector3D {
+        let [p0, p1, p2, p3] = self.points;
+        ((p2 - p1 * 2.0 + p0) * (1.0 - t) + (p3 - p2 * 2.0 + p1) * t) * 6.0
+    }
+}
+
+/// Cubic Hermite curve between two points with given tangents
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hermite {
+    pub start: Vector3D,
+    pub start_tangent: Vector3D,
+    pub end: Vector3D,
+    pub end_tangent: Vector3D,
+}
+
+impl Hermite {
+    /// Creates a new Hermite curve
+    ///
+    /// # Arguments
+    /// - `start` - The point at `t == 0`
+    /// - `start_tangent` - The derivative at `t == 0`
+    /// - `end` - The point at `t == 1`
+    /// - `end_tangent` - The derivative at `t == 1`
+    pub fn new(start: Vector3D, start_tangent: Vector3D, end: Vector3D, end_tangent: Vector3D) -> Self {
+        Self { start, start_tangent, end, end_tangent }
+    }
+
+    /// Returns the same curve in Bézier form
+    pub fn to_bezier(&self) -> CubicBezier {
+        CubicBezier::new(self.start, self.start + self.start_tangent / 3.0, self.end - self.end_tangent / 3.0, self.end)
+    }
+
+    /// Splits the curve at `t` into two curves covering `0..t` and `t..1`,
+    /// each reparameterised over `0..1`
+    pub fn subdivide(&self, t: f64) -> (Self, Self) {
+        let (a, b) = self.to_bezier().subdivide(t);
+        (a.to_hermite(), b.to_hermite())
+    }
+}
+
+impl Curve for Hermite {
+    fn position(&self, t: f64) -> Vector3D {
+        let (t2, t3) = (t * t, t * t * t);
+        self.start * (2.0 * t3 - 3.0 * t2 + 1.0)
+            + self.start_tangent * (t3 - 2.0 * t2 + t)
+            + self.end * (3.0 * t2 - 2.0 * t3)
+            + self.end_tangent * (t3 - t2)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        let t2 = t * t;
+        (self.start - self.end) * (6.0 * t2 - 6.0 * t)
+            + self.start_tangent * (3.0 * t2 - 4.0 * t + 1.0)
+            + self.end_tangent * (3.0 * t2 - 2.0 * t)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vector3D {
+        (self.start - self.end) * (12.0 * t - 6.0) + self.start_tangent * (6.0 * t - 4.0) + self.end_tangent * (6.0 * t - 2.0)
+    }
+}
+
+/// How a Catmull-Rom spline spaces its knots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CatmullRomKind {
+    /// Evenly spaced knots, the classic form, which can loop and overshoot
+    /// where points are unevenly spaced
+    Uniform,
+    /// Knots spaced by the square root of the chord length, which never
+    /// forms cusps or self-intersections within a segment
+    #[default]
+    Centripetal,
+    /// Knots spaced by chord length, tightest around sharp corners
+    Chordal,
+}
+
+impl CatmullRomKind {
+    fn alpha(&self) -> f64 {
+        match self {
+            CatmullRomKind::Uniform => 0.0,
+            CatmullRomKind::Centripetal => 0.5,
+            CatmullRomKind::Chordal => 1.0,
+        }
+    }
+}
+
+/// Catmull-Rom spline through a sequence of points
+///
+/// The curve passes through every point except the first and last, which
+/// only shape the ends. Segment `i` runs from `points[i + 1]` to
+/// `points[i + 2]` over `t` in `i..i + 1`.
+///
+/// Every segment gets a unit range of `t`, so with non-uniform knots the
+/// tangent keeps its direction across a point but its length can jump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom {
+    pub points: Vec<Vector3D>,
+    pub kind: CatmullRomKind,
+}
+
+impl CatmullRom {
+    /// Creates a new CatmullRom spline, or None with fewer than four points
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{CatmullRom, CatmullRomKind, Curve, Vector3D};
+    ///
+    /// let points = vec![
+    ///     Vector3D::new(-1.0, 0.0, 0.0),
+    ///     Vector3D::new(0.0, 0.0, 0.0),
+    ///     Vector3D::new(1.0, 1.0, 0.0),
+    ///     Vector3D::new(2.0, 1.0, 0.0),
+    ///     Vector3D::new(3.0, 0.0, 0.0),
+    /// ];
+    /// let spline = CatmullRom::new(points, CatmullRomKind::Uniform).unwrap();
+    /// assert_eq!(spline.domain(), (0.0, 2.0));
+    /// assert_eq!(spline.position(1.0), Vector3D::new(1.0, 1.0, 0.0));
+    /// assert_eq!(spline.derivative(1.0), Vector3D::new(1.0, 0.5, 0.0));
+    /// ```
+    pub fn new(points: Vec<Vector3D>, kind: CatmullRomKind) -> Option<Self> {
+        (points.len() >= 4).then_some(Self { points, kind })
+    }
+
+    /// Returns the number of segments
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(3)
+    }
+
+    /// Returns segment `index` as a Hermite curve over `0..1`
+    ///
+    /// Non-uniform kinds use the Barry-Goldman tangents for their knots,
+    /// scaled to the segment's unit parameter range.
+    pub fn segment(&self, index: usize) -> Hermite {
+        let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| self.points[index + i]);
+        let alpha = self.kind.alpha();
+        let interval = |a: Vector3D, b: Vector3D| {
+            let dt = (b - a).magnitude().powf(alpha);
+            // Repeated points would give a zero interval
+            if dt < EPSILON {
+                1.0
+            } else {
+                dt
+            }
+        };
+        let (d0, d1, d2) = (interval(p0, p1), interval(p1, p2), interval(p2, p3));
+        let m1 = (p1 - p0) / d0 - (p2 - p0) / (d0 + d1) + (p2 - p1) / d1;
+        let m2 = (p2 - p1) / d1 - (p3 - p1) / (d1 + d2) + (p3 - p2) / d2;
+        Hermite::new(p1, m1 * d1, p2, m2 * d1)
+    }
+
+    /// Returns every segment in Bézier form
+    pub fn to_beziers(&self) -> Vec<CubicBezier> {
+        (0..self.segment_count()).map(|i| self.segment(i).to_bezier()).collect()
+    }
+
+    fn locate(&self, t: f64) -> (Hermite, f64) {
+        let (index, u) = locate_segment(t, self.segment_count());
+        (self.segment(index), u)
+    }
+}
+
+impl Curve for CatmullRom {
+    fn position(&self, t: f64) -> Vector3D {
+        let (segment, u) = self.locate(t);
+        segment.position(u)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        let (segment, u) = self.locate(t);
+        segment.derivative(u)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vector3D {
+        let (segment, u) = self.locate(t);
+        segment.second_derivative(u)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (0.0, self.segment_count() as f64)
+    }
+
+    fn breakpoints(&self) -> Vec<f64> {
+        (1..self.segment_count()).map(|i| i as f64).collect()
+    }
+}
+
+/// Uniform cubic B-spline over a control polygon
+///
+/// The curve is C2 continuous but only approximates its control points.
+/// Segment `i` is shaped by `points[i..i + 4]` and covers `t` in `i..i + 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline {
+    pub points: Vec<Vector3D>,
+}
+
+impl BSpline {
+    /// Creates a new BSpline, or None with fewer than four control points
+    pub fn new(points: Vec<Vector3D>) -> Option<Self> {
+        (points.len() >= 4).then_some(Self { points })
+    }
+
+    /// Returns the number of segments
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(3)
+    }
+
+    /// Returns segment `index` in Bézier form
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{BSpline, Curve, Vector3D};
+    ///
+    /// let spline = BSpline::new(vec![
+    ///     Vector3D::new(0.0, 0.0, 0.0),
+    ///     Vector3D::new(6.0, 0.0, 0.0),
+    ///     Vector3D::new(6.0, 6.0, 0.0),
+    ///     Vector3D::new(0.0, 6.0, 0.0),
+    /// ]).unwrap();
+    /// let bezier = spline.segment(0);
+    /// assert_eq!(bezier.points[0], Vector3D::new(5.0, 1.0, 0.0));
+    /// assert_eq!(bezier.points[1], Vector3D::new(6.0, 2.0, 0.0));
+    /// assert_eq!(spline.position(0.5), bezier.position(0.5));
+    /// ```
+    pub fn segment(&self, index: usize) -> CubicBezier {
+        let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| self.points[index + i]);
+        CubicBezier::new(
+            (p0 + p1 * 4.0 + p2) / 6.0,
+            (p1 * 2.0 + p2) / 3.0,
+            (p1 + p2 * 2.0) / 3.0,
+            (p1 + p2 * 4.0 + p3) / 6.0,
+        )
+    }
+
+    /// Returns every segment in Bézier form
+    pub fn to_beziers(&self) -> Vec<CubicBezier> {
+        (0..self.segment_count()).map(|i| self.segment(i)).collect()
+    }
+
+    /// Returns the same curve with a control polygon twice as fine
+    ///
+    /// One round of Lane-Riesenfeld knot doubling: the new polygon hugs the
+    /// curve more closely, and the refined spline at `2 * t` is the
+    /// original at `t`. A polygon too short to refine is returned as is.
+    pub fn subdivide(&self) -> Self {
+        let p = &self.points;
+        if p.len() < 2 {
+            return self.clone();
+        }
+        let mut points = Vec::with_capacity(p.len() * 2);
+        points.push((p[0] + p[1]) * 0.5);
+        for i in 1..p.len() - 1 {
+            points.push((p[i - 1] + p[i] * 6.0 + p[i + 1]) / 8.0);
+            points.push((p[i] + p[i + 1]) * 0.5);
+        }
+        Self { points }
+    }
+
+    fn weights(t: f64) -> [[f64; 4]; 3] {
+        let s = 1.0 - t;
+        let (t2, t3) = (t * t, t * t * t);
+        [
+            [s * s * s / 6.0, (3.0 * t3 - 6.0 * t2 + 4.0) / 6.0, (-3.0 * t3 + 3.0 * t2 + 3.0 * t + 1.0) / 6.0, t3 / 6.0],
+            [-s * s * 0.5, (3.0 * t2 - 4.0 * t) * 0.5, (-3.0 * t2 + 2.0 * t + 1.0) * 0.5, t2 * 0.5],
+            [s, 3.0 * t - 2.0, 1.0 - 3.0 * t, t],
+        ]
+    }
+
+    fn evaluate(&self, t: f64, order: usize) -> Vector3D {
+        let (index, u) = locate_segment(t, self.segment_count());
+        let weights = Self::weights(u)[order];
+        (0..4).fold(Vector3D::default(), |sum, i| sum + self.points[index + i] * weights[i])
+    }
+}
+
+impl Curve for BSpline {
+    fn position(&self, t: f64) -> Vector3D {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: f64) -> Vector3D {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vector3D {
+        self.evaluate(t, 2)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (0.0, self.segment_count() as f64)
+    }
+
+    fn breakpoints(&self) -> Vec<f64> {
+        (1..self.segment_count()).map(|i| i as f64).collect()
+    }
+}
+
+/// Splits a spline parameter into a segment index and the local parameter,
+/// clamping to the first and last segments
+fn locate_segment(t: f64, segments: usize) -> (usize, f64) {
+    let last = segments.saturating_sub(1);
+    let index = if t <= 0.0 { 0 } else { (t.floor() as usize).min(last) };
+    (index, t - index as f64)
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// A helix of radius 2 rising 1 per radian, over four turns
+    pub struct Helix;
+
+    impl Curve for Helix {
+        fn position(&self, t: f64) -> Vector3D {
+            Vector3D::new(2.0 * t.cos(), 2.0 * t.sin(), t)
+        }
+
+        fn derivative(&self, t: f64) -> Vector3D {
+            Vector3D::new(-2.0 * t.sin(), 2.0 * t.cos(), 1.0)
+        }
+
+        fn second_derivative(&self, t: f64) -> Vector3D {
+            Vector3D::new(-2.0 * t.cos(), -2.0 * t.sin(), 0.0)
+        }
+
+        fn domain(&self) -> (f64, f64) {
+            (0.0, 8.0 * PI)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vector3D, b: Vector3D, tolerance: f64) -> bool {
+        (a - b).magnitude() <= tolerance * (1.0 + a.magnitude().max(b.magnitude()))
+    }
+
+    /// Compares the analytic derivatives with central differences
+    fn assert_derivatives<C: Curve>(curve: &C) {
+        let (start, end) = curve.domain();
+        let h = 1e-5;
+        for i in 1..20 {
+            let t = start + (end - start) * i as f64 / 20.0 + 1e-3;
+            let d = (curve.position(t + h) - curve.position(t - h)) / (2.0 * h);
+            assert!(close(curve.derivative(t), d, 1e-6), "derivative at {}", t);
+            let dd = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+            assert!(close(curve.second_derivative(t), dd, 1e-6), "second derivative at {}", t);
+        }
+    }
+
+    fn control_points() -> Vec<Vector3D> {
+        vec![
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(1.0, 3.0, -1.0),
+            Vector3D::new(4.0, 3.5, 0.5),
+            Vector3D::new(5.0, -1.0, 2.0),
+            Vector3D::new(9.0, 0.0, 2.0),
+            Vector3D::new(9.5, 4.0, -3.0),
+            Vector3D::new(9.6, 4.0, -3.0),
+        ]
+    }
+
+    #[test]
+    fn test_curves_bezier() {
+        let p = control_points();
+        let cubic = CubicBezier::new(p[0], p[1], p[2], p[3]);
+        assert_eq!(cubic.position(0.0), p[0]);
+        assert_eq!(cubic.position(1.0), p[3]);
+        assert!(close(cubic.position(0.5), (p[0] + p[1] * 3.0 + p[2] * 3.0 + p[3]) / 8.0, 1e-15));
+        assert_eq!(cubic.derivative(1.0), (p[3] - p[2]) * 3.0);
+        assert_derivatives(&cubic);
+
+        let quadratic = QuadraticBezier::new(p[0], p[1], p[2]);
+        assert!(close(quadratic.position(0.5), (p[0] + p[1] * 2.0 + p[2]) / 4.0, 1e-15));
+        assert_derivatives(&quadratic);
+        let elevated = quadratic.elevate();
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!(close(elevated.position(t), quadratic.position(t), 1e-14));
+            assert!(close(elevated.derivative(t), quadratic.derivative(t), 1e-14));
+        }
+
+        // Each half of a subdivision retraces its part of the original
+        let (a, b) = cubic.subdivide(0.3);
+        for i in 0..=10 {
+            let u = i as f64 / 10.0;
+            assert!(close(a.position(u), cubic.position(0.3 * u), 1e-14));
+            assert!(close(b.position(u), cubic.position(0.3 + 0.7 * u), 1e-14));
+        }
+        let (a, b) = quadratic.subdivide(0.6);
+        assert!(close(a.position(0.5), quadratic.position(0.3), 1e-14));
+        assert!(close(b.position(0.5), quadratic.position(0.8), 1e-14));
+    }
+
+    #[test]
+    fn test_curves_basis_conversions() {
+        let p = control_points();
+        let bezier = CubicBezier::new(p[0], p[1], p[2], p[3]);
+        let hermite = bezier.to_hermite();
+        let bspline = bezier.to_bspline();
+        assert_derivatives(&hermite);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            for other in [hermite.position(t), bspline.position(t), hermite.to_bezier().position(t)] {
+                assert!(close(other, bezier.position(t), 1e-12));
+            }
+            assert!(close(hermite.derivative(t), bezier.derivative(t), 1e-12));
+            assert!(close(bspline.derivative(t), bezier.derivative(t), 1e-12));
+            assert!(close(bspline.second_derivative(t), bezier.second_derivative(t), 1e-12));
+        }
+        let back = bspline.segment(0);
+        for (a, b) in back.points.iter().zip(&bezier.points) {
+            assert!(close(*a, *b, 1e-12));
+        }
+
+        let (a, b) = hermite.subdivide(0.25);
+        assert!(close(a.end, hermite.position(0.25), 1e-14));
+        assert!(close(a.end_tangent, hermite.derivative(0.25) * 0.25, 1e-14));
+        assert!(close(b.position(0.5), hermite.position(0.625), 1e-14));
+    }
+
+    #[test]
+    fn test_curves_catmull_rom() {
+        let points = control_points();
+        for kind in [CatmullRomKind::Uniform, CatmullRomKind::Centripetal, CatmullRomKind::Chordal] {
+            let spline = CatmullRom::new(points.clone(), kind).unwrap();
+            assert_eq!(spline.domain(), (0.0, 4.0));
+            // Interpolates every inner point and is C1 at them
+            for i in 0..=4 {
+                assert!(close(spline.position(i as f64), points[i + 1], 1e-14));
+            }
+            for i in 1..4 {
+                let before = spline.segment(i - 1).derivative(1.0);
+                let after = spline.segment(i).derivative(0.0);
+                assert!(close(before.normalise(), after.normalise(), 1e-12), "{:?} tangent at {}", kind, i);
+                if kind == CatmullRomKind::Uniform {
+                    assert!(close(before, after, 1e-12));
+                }
+            }
+            assert_derivatives(&spline);
+            for (i, bezier) in spline.to_beziers().iter().enumerate() {
+                assert!(close(bezier.position(0.4), spline.position(i as f64 + 0.4), 1e-12));
+            }
+        }
+
+        // The uniform tangent is half the chord of the neighbours
+        let uniform = CatmullRom::new(points.clone(), CatmullRomKind::Uniform).unwrap();
+        assert!(close(uniform.derivative(1.0), (points[3] - points[1]) * 0.5, 1e-14));
+
+        // Centripetal stays finite over repeated points
+        let mut repeated = points.clone();
+        repeated.insert(3, points[2]);
+        let spline = CatmullRom::new(repeated, CatmullRomKind::Centripetal).unwrap();
+        for i in 0..=50 {
+            let p = spline.position(i as f64 * 0.1);
+            assert!(p.x.is_finite() && p.y.is_finite() && p.z.is_finite());
+        }
+        assert!(CatmullRom::new(points[..3].to_vec(), CatmullRomKind::Uniform).is_none());
+    }
+
+    #[test]
+    fn test_curves_bspline() {
+        let spline = BSpline::new(control_points()).unwrap();
+        assert_eq!(spline.domain(), (0.0, 4.0));
+        assert_eq!(spline.breakpoints(), vec![1.0, 2.0, 3.0]);
+        assert_derivatives(&spline);
+        // Joins are C2
+        for i in 1..4 {
+            let (a, b) = (spline.segment(i - 1), spline.segment(i));
+            assert!(close(a.position(1.0), b.position(0.0), 1e-14));
+            assert!(close(a.derivative(1.0), b.derivative(0.0), 1e-12));
+            assert!(close(a.second_derivative(1.0), b.second_derivative(0.0), 1e-12));
+        }
+
+        let refined = spline.subdivide();
+        assert_eq!(refined.segment_count(), 2 * spline.segment_count());
+        for i in 0..=40 {
+            let t = i as f64 * 0.1;
+            assert!(close(refined.position(2.0 * t), spline.position(t), 1e-12));
+        }
+        assert!(BSpline::new(control_points()[..3].to_vec()).is_none());
+        assert!(BSpline::new(Vec::new()).is_none());
+
+        // Fields are public, so short polygons must not underflow
+        let short = BSpline { points: vec![Vector3D::default()] };
+        assert_eq!(short.segment_count(), 0);
+        assert_eq!(short.subdivide(), short);
+        assert_eq!(BSpline { points: Vec::new() }.subdivide().points.len(), 0);
+        let short = CatmullRom { points: control_points()[..2].to_vec(), kind: CatmullRomKind::Uniform };
+        assert_eq!(short.segment_count(), 0);
+        assert!(short.to_beziers().is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Overshoot of the back curves, about 10% past the end
+const BACK_OVERSHOOT: f64 = 1.70158;
+
+/// Overshoot of the in-out back curve, larger so each half still reaches
+/// about 10% once squeezed into half the time
+const BACK_IN_OUT_OVERSHOOT: f64 = BACK_OVERSHOOT * 1.525;
+
+/// Iterations of Newton's method when solving a timing curve for its parameter
+const BEZIER_NEWTON_ITERATIONS: usize = 8;
+
+/// Tolerance on `x` when solving a timing curve for its parameter
+const BEZIER_TOLERANCE: f64 = 1.0e-12;
+
+/// Shape of one of Robert Penner's easing curves
+///
+/// Each is defined by its ease-in form, accelerating from rest. The other
+/// variants of `Easing` mirror it to decelerate or do both, except for the
+/// in-out back and elastic curves, which have their own formulas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EaseCurve {
+    Sine,
+    Quad,
+    Cubic,
+    Quart,
+    Quint,
+    Expo,
+    Circ,
+    /// Pulls back below 0 before starting
+    Back,
+    /// Oscillates around 0 with growing amplitude before springing to 1
+    Elastic,
+    /// Bounces with growing height, the reverse of a dropped ball
+    Bounce,
+}
+
+impl EaseCurve {
+    /// Returns the ease-in value at `t` in 0..1
+    pub fn ease_in(&self, t: f64) -> f64 {
+        match self {
+            Self::Sine => 1.0 - (t * FRAC_PI_2).cos(),
+            Self::Quad => t * t,
+            Self::Cubic => t * t * t,
+            Self::Quart => t * t * t * t,
+            Self::Quint => t * t * t * t * t,
+            Self::Expo => {
+                if t <= 0.0 {
+                    0.0
+                } else {
+                    2.0f64.powf(10.0 * t - 10.0)
+                }
+            }
+            Self::Circ => 1.0 - (1.0 - t * t).max(0.0).sqrt(),
+            Self::Back => t * t * ((BACK_OVERSHOOT + 1.0) * t - BACK_OVERSHOOT),
+            Self::Elastic => {
+                if t <= 0.0 {
+                    0.0
+                } else if t >= 1.0 {
+                    1.0
+                } else {
+                    -(2.0f64.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * (2.0 * PI / 3.0)).sin()
+                }
+            }
+            Self::Bounce => 1.0 - bounce_out(1.0 - t),
+        }
+    }
+
+    /// Returns the ease-out value at `t` in 0..1, the ease-in curve rotated
+    /// half a turn
+    pub fn ease_out(&self, t: f64) -> f64 {
+        1.0 - self.ease_in(1.0 - t)
+    }
+
+    /// Returns the ease-in-out value at `t` in 0..1, ease-in for the first
+    /// half and ease-out for the second
+    ///
+    /// Back and elastic follow Penner's dedicated in-out formulas, with a
+    /// stronger overshoot and a shorter period than their ease-in halves.
+    pub fn ease_in_out(&self, t: f64) -> f64 {
+        match self {
+            Self::Back => {
+                let overshoot = |t: f64| t * t * ((BACK_IN_OUT_OVERSHOOT + 1.0) * t - BACK_IN_OUT_OVERSHOOT);
+                if t < 0.5 {
+                    overshoot(2.0 * t) * 0.5
+                } else {
+                    1.0 - overshoot(2.0 - 2.0 * t) * 0.5
+                }
+            }
+            Self::Elastic => {
+                if t <= 0.0 {
+                    0.0
+                } else if t >= 1.0 {
+                    1.0
+                } else {
+                    let wave = ((20.0 * t - 11.125) * (2.0 * PI / 4.5)).sin() * 0.5;
+                    if t < 0.5 {
+                        -(2.0f64.powf(20.0 * t - 10.0)) * wave
+                    } else {
+                        2.0f64.powf(10.0 - 20.0 * t) * wave + 1.0
+                    }
+                }
+            }
+            _ => {
+                if t < 0.5 {
+                    self.ease_in(2.0 * t) * 0.5
+                } else {
+                    1.0 - self.ease_in(2.0 - 2.0 * t) * 0.5
+                }
+            }
+        }
+    }
+}
+
+/// Ball dropped onto the floor from height 1, landing at `t == 1`
+fn bounce_out(t: f64) -> f64 {
+    const SCALE: f64 = 7.5625;
+    const STEP: f64 = 2.75;
+    if t < 1.0 / STEP {
+        SCALE * t * t
+    } else if t < 2.0 / STEP {
+        let t = t - 1.5 / STEP;
+        SCALE * t * t + 0.75
+    } else if t < 2.5 / STEP {
+        let t = t - 2.25 / STEP;
+        SCALE * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / STEP;
+        SCALE * t * t + 0.984375
+    }
+}
+
+/// CSS-style timing curve, a cubic Bezier from (0, 0) to (1, 1) mapping
+/// progress `x` to eased value `y`
+///
+/// The inner control points must have `x` in 0..1 so the curve is a
+/// function of `x`. Their `y` is free, outside 0..1 the curve overshoots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezierTiming {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl CubicBezierTiming {
+    /// CSS `ease`
+    pub const EASE: Self = Self { x1: 0.25, y1: 0.1, x2: 0.25, y2: 1.0 };
+    /// CSS `ease-in`
+    pub const EASE_IN: Self = Self { x1: 0.42, y1: 0.0, x2: 1.0, y2: 1.0 };
+    /// CSS `ease-out`
+    pub const EASE_OUT: Self = Self { x1: 0.0, y1: 0.0, x2: 0.58, y2: 1.0 };
+    /// CSS `ease-in-out`
+    pub const EASE_IN_OUT: Self = Self { x1: 0.42, y1: 0.0, x2: 0.58, y2: 1.0 };
+
+    /// Creates a CubicBezierTiming from its two inner control points, or
+    /// None if either `x` is outside 0..1
+    ///
+    /// # Arguments
+    /// - `x1`, `y1` - The control point after (0, 0)
+    /// - `x2`, `y2` - The control point before (1, 1)
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::CubicBezierTiming;
+    ///
+    /// let linear = CubicBezierTiming::new(0.25, 0.25, 0.75, 0.75).unwrap();
+    /// assert!((linear.apply(0.3) - 0.3).abs() < 1e-12);
+    /// assert!(CubicBezierTiming::new(1.5, 0.0, 0.5, 1.0).is_none());
+    /// ```
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Option<Self> {
+        let valid = |x: f64| (0.0..=1.0).contains(&x);
+        (valid(x1) && valid(x2) && y1.is_finite() && y2.is_finite()).then_some(Self { x1, y1, x2, y2 })
+    }
+
+    /// Returns the eased value at progress `x`, clamped to 0..1
+    ///
+    /// Solves for the curve parameter with Newton's method, falling back
+    /// on bisection where the slope is too flat.
+    pub fn apply(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        let s = self.solve(x);
+        bezier_component(self.y1, self.y2, s)
+    }
+
+    fn solve(&self, x: f64) -> f64 {
+        let mut s = x;
+        for _ in 0..BEZIER_NEWTON_ITERATIONS {
+            let error = bezier_component(self.x1, self.x2, s) - x;
+            if error.abs() < BEZIER_TOLERANCE {
+                return s;
+            }
+            let slope = bezier_derivative(self.x1, self.x2, s);
+            if slope.abs() < 1.0e-6 {
+                break;
+            }
+            s -= error / slope;
+        }
+        // x(s) is non-decreasing for valid control points
+        let (mut low, mut high) = (0.0, 1.0);
+        s = x;
+        while high - low > BEZIER_TOLERANCE {
+            let value = bezier_component(self.x1, self.x2, s);
+            if (value - x).abs() < BEZIER_TOLERANCE {
+                break;
+            }
+            if value < x {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) * 0.5;
+        }
+        s
+    }
+}
+
+/// One coordinate of a cubic Bezier from 0 to 1 with inner controls `a` and `b`
+fn bezier_component(a: f64, b: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+}
+
+fn bezier_derivative(a: f64, b: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+}
+
+/// Mapping from linear progress to eased progress, used by `Tween`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    In(EaseCurve),
+    Out(EaseCurve),
+    InOut(EaseCurve),
+    CubicBezier(CubicBezierTiming),
+}
+
+impl Easing {
+    /// Returns the eased progress at `t`, clamped to 0..1
+    ///
+    /// Every easing maps 0 to 0 and 1 to 1. Back, elastic and some timing
+    /// curves leave 0..1 in between.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{EaseCurve, Easing};
+    ///
+    /// assert_eq!(Easing::In(EaseCurve::Quad).apply(0.5), 0.25);
+    /// assert_eq!(Easing::Out(EaseCurve::Quad).apply(0.5), 0.75);
+    /// assert_eq!(Easing::InOut(EaseCurve::Cubic).apply(0.25), 0.0625);
+    /// assert_eq!(Easing::Linear.apply(2.0), 1.0);
+    /// ```
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::In(curve) => curve.ease_in(t),
+            Self::Out(curve) => curve.ease_out(t),
+            Self::InOut(curve) => curve.ease_in_out(t),
+            Self::CubicBezier(timing) => timing.apply(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [EaseCurve; 10] = [
+        EaseCurve::Sine,
+        EaseCurve::Quad,
+        EaseCurve::Cubic,
+        EaseCurve::Quart,
+        EaseCurve::Quint,
+        EaseCurve::Expo,
+        EaseCurve::Circ,
+        EaseCurve::Back,
+        EaseCurve::Elastic,
+        EaseCurve::Bounce,
+    ];
+
+    fn all_easings() -> Vec<Easing> {
+        let mut easings = vec![
+            Easing::Linear,
+            Easing::CubicBezier(CubicBezierTiming::EASE),
+            Easing::CubicBezier(CubicBezierTiming::EASE_IN),
+            Easing::CubicBezier(CubicBezierTiming::EASE_OUT),
+            Easing::CubicBezier(CubicBezierTiming::EASE_IN_OUT),
+            Easing::CubicBezier(CubicBezierTiming::new(0.3, -0.5, 0.7, 1.5).unwrap()),
+        ];
+        for curve in CURVES {
+            easings.extend([Easing::In(curve), Easing::Out(curve), Easing::InOut(curve)]);
+        }
+        easings
+    }
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in all_easings() {
+            assert!(easing.apply(0.0).abs() < 1e-12, "{:?} at 0", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-12, "{:?} at 1", easing);
+            assert!(easing.apply(-1.0).abs() < 1e-12);
+            assert!((easing.apply(2.0) - 1.0).abs() < 1e-12);
+        }
+        for curve in CURVES {
+            // In-out is symmetric and passes through the middle
+            assert!((curve.ease_in_out(0.5) - 0.5).abs() < 1e-12);
+            for i in 0..=20 {
+                let t = i as f64 / 20.0;
+                assert!((curve.ease_in_out(t) + curve.ease_in_out(1.0 - t) - 1.0).abs() < 1e-12);
+            }
+        }
+        // Overshooting curves do leave 0..1
+        assert!(Easing::In(EaseCurve::Back).apply(0.3) < 0.0);
+        assert!(Easing::Out(EaseCurve::Elastic).apply(0.2) > 1.0);
+    }
+
+    #[test]
+    fn test_easing_monotonic() {
+        let mut monotonic: Vec<Easing> = vec![
+            Easing::Linear,
+            Easing::CubicBezier(CubicBezierTiming::EASE),
+            Easing::CubicBezier(CubicBezierTiming::EASE_IN),
+            Easing::CubicBezier(CubicBezierTiming::EASE_OUT),
+            Easing::CubicBezier(CubicBezierTiming::EASE_IN_OUT),
+            Easing::CubicBezier(CubicBezierTiming::new(0.0, 1.0, 1.0, 0.0).unwrap()),
+        ];
+        for curve in &CURVES[..7] {
+            monotonic.extend([Easing::In(*curve), Easing::Out(*curve), Easing::InOut(*curve)]);
+        }
+        for easing in monotonic {
+            let mut previous = 0.0;
+            for i in 1..=1000 {
+                let value = easing.apply(i as f64 / 1000.0);
+                assert!(value >= previous - 1e-12, "{:?} decreases at {}", easing, i);
+                assert!(value <= 1.0 + 1e-12);
+                previous = value;
+            }
+        }
+        // Each bounce of the ball returns to the floor
+        let bounce = Easing::Out(EaseCurve::Bounce);
+        for t in [1.0 / 2.75, 2.0 / 2.75, 2.5 / 2.75] {
+            assert!((bounce.apply(t) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_easing_penner_reference() {
+        // Values from Penner's easeInOutBack and easeInOutElastic
+        let back = [(0.1, -0.037518552), (0.2, -0.092555656), (0.3, -0.078833484), (0.45, 0.25940616525), (0.6, 0.910074208), (0.75, 1.09968184375), (0.9, 1.037518552)];
+        for (t, expected) in back {
+            assert!((EaseCurve::Back.ease_in_out(t) - expected).abs() < 1e-12, "back at {}", t);
+        }
+        let elastic = [
+            (0.1, 0.000339156597005722),
+            (0.2, -0.00390625),
+            (0.3, 0.023938888847468056),
+            (0.45, 0.04341204441673267),
+            (0.6, 1.1174615775982386),
+            (0.75, 0.988030555576266),
+            (0.9, 0.9996608434029943),
+        ];
+        for (t, expected) in elastic {
+            assert!((EaseCurve::Elastic.ease_in_out(t) - expected).abs() < 1e-12, "elastic at {}", t);
+        }
+        // The in-out back dips about 10% below the start
+        let dip = (0..=1000).map(|i| EaseCurve::Back.ease_in_out(i as f64 / 1000.0)).fold(f64::INFINITY, f64::min);
+        assert!((dip + 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_cubic_bezier_timing() {
+        // Compare with points sampled along the curve parameter
+        for timing in [CubicBezierTiming::EASE, CubicBezierTiming::EASE_IN_OUT, CubicBezierTiming::new(0.9, 0.1, 1.0, 0.0).unwrap()] {
+            for i in 0..=100 {
+                let s = i as f64 / 100.0;
+                let x = bezier_component(timing.x1, timing.x2, s);
+                let y = bezier_component(timing.y1, timing.y2, s);
+                assert!((timing.apply(x) - y).abs() < 1e-9, "{:?} at s = {}", timing, s);
+            }
+        }
+        // Known value of CSS ease-in-out at the midpoint
+        assert!((CubicBezierTiming::EASE_IN_OUT.apply(0.5) - 0.5).abs() < 1e-12);
+        assert!(CubicBezierTiming::new(0.5, 0.0, -0.1, 1.0).is_none());
+        assert!(CubicBezierTiming::new(0.5, f64::NAN, 0.5, 1.0).is_none());
+    }
+}
@@ -0,0 +1,360 @@
+use super::{Aabb, Matrix3D, Obb, ParallelBatch, Sphere, Vector3D, EPSILON};
+
+/// Returns the smallest sphere containing every point, using Welzl's algorithm
+///
+/// The points are visited in a fixed pseudo-random order, so the same input
+/// always gives the same sphere while keeping the expected linear running
+/// time. Returns None for an empty slice.
+///
+/// # Arguments
+/// - `points` - The points to enclose
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{minimal_enclosing_sphere, Vector3D};
+///
+/// let points = [Vector3D::new(-1.0, 0.0, 0.0),
+///               Vector3D::new(1.0, 0.0, 0.0),
+///               Vector3D::new(0.0, 0.5, 0.0)];
+/// let sphere = minimal_enclosing_sphere(&points).unwrap();
+/// assert_eq!(sphere.center, Vector3D::default());
+/// assert_eq!(sphere.radius, 1.0);
+/// ```
+pub fn minimal_enclosing_sphere(points: &[Vector3D]) -> Option<Sphere> {
+    if points.is_empty() {
+        return None;
+    }
+    let mut shuffled = points.to_vec();
+    shuffle(&mut shuffled);
+    let mut boundary = Vec::with_capacity(4);
+    let end = shuffled.len();
+    Some(move_to_front(&mut shuffled, end, &mut boundary))
+}
+
+/// Returns an Obb aligned to the principal axes of the points
+///
+/// The axes are the eigenvectors of the covariance matrix, largest variance
+/// first, and the extents are fitted tightly around the points along them.
+/// Returns None for an empty slice.
+///
+/// # Arguments
+/// - `points` - The points to enclose
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{pca_obb, Vector3D};
+///
+/// let points = [Vector3D::new(-4.0, -4.0, 0.0), Vector3D::new(4.0, 4.0, 0.0),
+///               Vector3D::new(-3.0, -5.0, 0.0), Vector3D::new(5.0, 3.0, 0.0)];
+/// let obb = pca_obb(&points).unwrap();
+/// assert!(points.iter().all(|p| (obb.to_local(p).x.abs() - obb.half_extents.x) < 1e-9));
+/// assert!(obb.volume() < 1e-9);
+/// ```
+pub fn pca_obb(points: &[Vector3D]) -> Option<Obb> {
+    let covariance = ParallelBatch::new().covariance(points)?;
+    let (_, axes) = covariance.symmetric_eigen();
+    Some(fit_obb(points, &axes))
+}
+
+/// Returns an Obb with approximately the smallest volume around the points
+///
+/// Starts from the better of the PCA and world-aligned boxes, then refines
+/// the frame by repeatedly rotating it about each of its own axes and
+/// keeping any rotation that shrinks the box. The result is never larger
+/// than the `pca_obb` or `tight_aabb` boxes. Returns None for an empty slice.
+///
+/// # Arguments
+/// - `points` - The points to enclose
+pub fn approximate_minimal_obb(points: &[Vector3D]) -> Option<Obb> {
+    let pca = pca_obb(points)?;
+    let aligned = fit_obb(points, &Matrix3D::identity());
+    let mut best = if aligned.volume() < pca.volume() { aligned } else { pca };
+
+    const PASSES: usize = 4;
+    const SAMPLES: usize = 24;
+    let quarter = std::f64::consts::FRAC_PI_2;
+    for _ in 0..PASSES {
+        let before = best.volume();
+        for axis in 0..3 {
+            let frame = best.orientation;
+            let evaluate = |angle: f64| fit_obb(points, &rotate_frame(&frame, axis, angle));
+
+            // Coarse sweep over a quarter turn, the box repeats after that
+            let step = quarter / SAMPLES as f64;
+            let mut best_angle = 0.0;
+            let mut best_volume = best.volume();
+            for i in 1..SAMPLES {
+                let angle = step * i as f64;
+                let volume = evaluate(angle).volume();
+                if volume < best_volume {
+                    best_volume = volume;
+                    best_angle = angle;
+                }
+            }
+
+            // Golden-section refinement around the best sample
+            let (mut lo, mut hi) = (best_angle - step, best_angle + step);
+            let ratio = 0.5 * (5.0_f64.sqrt() - 1.0);
+            let mut c = hi - ratio * (hi - lo);
+            let mut d = lo + ratio * (hi - lo);
+            let (mut fc, mut fd) = (evaluate(c).volume(), evaluate(d).volume());
+            for _ in 0..32 {
+                if fc < fd {
+                    hi = d;
+                    d = c;
+                    fd = fc;
+                    c = hi - ratio * (hi - lo);
+                    fc = evaluate(c).volume();
+                } else {
+                    lo = c;
+                    c = d;
+                    fc = fd;
+                    d = lo + ratio * (hi - lo);
+                    fd = evaluate(d).volume();
+                }
+            }
+
+            for candidate in [evaluate(best_angle), evaluate(0.5 * (lo + hi))] {
+                if candidate.volume() < best.volume() {
+                    best = candidate;
+                }
+            }
+        }
+        if best.volume() >= before * (1.0 - EPSILON) {
+            break;
+        }
+    }
+    Some(best)
+}
+
+/// Returns the smallest world-aligned Aabb containing every point
+///
+/// Returns an empty Aabb for an empty slice.
+///
+/// # Arguments
+/// - `points` - The points to enclose
+pub fn tight_aabb(points: &[Vector3D]) -> Aabb {
+    Aabb::from_points(points.iter().copied())
+}
+
+/// Fits the extents of an Obb with the given axes tightly around the points
+fn fit_obb(points: &[Vector3D], axes: &Matrix3D) -> Obb {
+    let to_local = axes.transpose();
+    let mut min = Vector3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    let mut max = -min;
+    for p in points {
+        let local = to_local * *p;
+        min = min.min(&local);
+        max = max.max(&local);
+    }
+    Obb::new(*axes * ((min + max) * 0.5), *axes, (max - min) * 0.5)
+}
+
+/// Rotates the two columns of `frame` that are not `axis` by `angle`
+fn rotate_frame(frame: &Matrix3D, axis: usize, angle: f64) -> Matrix3D {
+    let (i, j) = ((axis + 1) % 3, (axis + 2) % 3);
+    let (s, c) = angle.sin_cos();
+    let (u, v) = (frame.column(i), frame.column(j));
+    let mut columns = [frame.column(0), frame.column(1), frame.column(2)];
+    columns[i] = u * c + v * s;
+    columns[j] = v * c - u * s;
+    Matrix3D::new_vectors(columns[0], columns[1], columns[2])
+}
+
+/// Deterministic Fisher-Yates shuffle driven by a fixed-seed xorshift
+fn shuffle(points: &mut [Vector3D]) {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+/// Move-to-front Welzl over `points[..end]` with the given boundary points
+///
+/// Recursion only happens when a point is added to the boundary, so the
+/// depth is at most four.
+fn move_to_front(points: &mut [Vector3D], end: usize, boundary: &mut Vec<Vector3D>) -> Sphere {
+    let mut sphere = sphere_from_boundary(boundary);
+    if boundary.len() == 4 {
+        return sphere;
+    }
+    for i in 0..end {
+        if !encloses(&sphere, &points[i]) {
+            boundary.push(points[i]);
+            sphere = move_to_front(points, i, boundary);
+            boundary.pop();
+            points[..=i].rotate_right(1);
+        }
+    }
+    sphere
+}
+
+/// Containment test with a relative tolerance to absorb rounding
+fn encloses(sphere: &Sphere, point: &Vector3D) -> bool {
+    sphere.radius >= 0.0
+        && (*point - sphere.center).magnitude() <= sphere.radius * (1.0 + 1e-12) + EPSILON
+}
+
+/// Returns the smallest sphere with every boundary point on its surface
+///
+/// An empty boundary gives a sphere with negative radius that contains
+/// nothing. Degenerate boundaries (collinear or coplanar) fall back to the
+/// smallest sphere through a subset that still encloses them all.
+fn sphere_from_boundary(boundary: &[Vector3D]) -> Sphere {
+    match *boundary {
+        [] => Sphere::new(Vector3D::default(), -1.0),
+        [a] => Sphere::new(a, 0.0),
+        [a, b] => Sphere::new((a + b) * 0.5, (b - a).magnitude() * 0.5),
+        [a, b, c] => circumsphere_3(a, b, c).unwrap_or_else(|| {
+            smallest_enclosing(&[[a, b], [a, c], [b, c]].map(|[p, q]| sphere_from_boundary(&[p, q])), boundary)
+        }),
+        [a, b, c, d] => circumsphere_4(a, b, c, d).unwrap_or_else(|| {
+            let faces = [[a, b, c], [a, b, d], [a, c, d], [b, c, d]];
+            smallest_enclosing(&faces.map(|f| sphere_from_boundary(&f)), boundary)
+        }),
+        _ => unreachable!("boundary never holds more than four points"),
+    }
+}
+
+/// Picks the smallest candidate that encloses all of `points`
+fn smallest_enclosing(candidates: &[Sphere], points: &[Vector3D]) -> Sphere {
+    candidates
+        .iter()
+        .filter(|s| points.iter().all(|p| encloses(s, p)))
+        .min_by(|a, b| a.radius.total_cmp(&b.radius))
+        .copied()
+        .unwrap_or(candidates[0])
+}
+
+/// Sphere through three points with its centre in their plane
+fn circumsphere_3(a: Vector3D, b: Vector3D, c: Vector3D) -> Option<Sphere> {
+    let ab = b - a;
+    let ac = c - a;
+    let n = ab.cross(&ac);
+    let n2 = n.magnitude_squared();
+    if n2 <= EPSILON * EPSILON * ab.magnitude_squared() * ac.magnitude_squared() {
+        return None;
+    }
+    let offset = (n.cross(&ab) * ac.magnitude_squared() + ac.cross(&n) * ab.magnitude_squared()) * (0.5 / n2);
+    Some(Sphere::new(a + offset, offset.magnitude()))
+}
+
+/// Sphere through four points
+fn circumsphere_4(a: Vector3D, b: Vector3D, c: Vector3D, d: Vector3D) -> Option<Sphere> {
+    let (ab, ac, ad) = (b - a, c - a, d - a);
+    let rows = Matrix3D::new(ab.x, ab.y, ab.z,
+                             ac.x, ac.y, ac.z,
+                             ad.x, ad.y, ad.z);
+    let scale = ab.magnitude() * ac.magnitude() * ad.magnitude();
+    if rows.determinant().abs() <= EPSILON * scale {
+        return None;
+    }
+    let rhs = Vector3D::new(ab.magnitude_squared(), ac.magnitude_squared(), ad.magnitude_squared()) * 0.5;
+    let offset = rows.solve(&rhs)?;
+    Some(Sphere::new(a + offset, offset.magnitude()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::TestRng;
+
+    /// Deterministic scattered points inside a skewed, rotated slab
+    fn cloud(count: usize) -> Vec<Vector3D> {
+        let mut rng = TestRng::new(12345);
+        let mut next = || rng.signed();
+        let frame = Matrix3D::new(0.36, 0.48, -0.8,
+                                  -0.8, 0.6, 0.0,
+                                  0.48, 0.64, 0.6);
+        (0..count)
+            .map(|_| frame * Vector3D::new(next() * 5.0, next() * 2.0, next() * 0.5) + Vector3D::new(3.0, -1.0, 2.0))
+            .collect()
+    }
+
+    fn obb_contains_all(obb: &Obb, points: &[Vector3D]) -> bool {
+        points.iter().all(|p| {
+            let local = obb.to_local(p);
+            (0..3).all(|i| local[i].abs() <= obb.half_extents[i] + 1e-9)
+        })
+    }
+
+    #[test]
+    fn test_minimal_enclosing_sphere() {
+        assert!(minimal_enclosing_sphere(&[]).is_none());
+
+        let points = cloud(500);
+        let sphere = minimal_enclosing_sphere(&points).unwrap();
+        assert!(points.iter().all(|p| (*p - sphere.center).magnitude() <= sphere.radius + 1e-9));
+        // Minimal: at least two points lie on the surface
+        let on_surface = points.iter().filter(|p| ((**p - sphere.center).magnitude() - sphere.radius).abs() < 1e-9).count();
+        assert!(on_surface >= 2);
+        assert_eq!(minimal_enclosing_sphere(&points), Some(sphere));
+
+        // Regular tetrahedron corners
+        let tetra = [Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(1.0, -1.0, -1.0),
+                     Vector3D::new(-1.0, 1.0, -1.0), Vector3D::new(-1.0, -1.0, 1.0)];
+        let sphere = minimal_enclosing_sphere(&tetra).unwrap();
+        assert!(sphere.center.magnitude() < 1e-12);
+        assert!((sphere.radius - 3.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_minimal_enclosing_sphere_degenerate() {
+        let single = [Vector3D::new(1.0, 2.0, 3.0); 5];
+        assert_eq!(minimal_enclosing_sphere(&single), Some(Sphere::new(Vector3D::new(1.0, 2.0, 3.0), 0.0)));
+
+        let collinear: Vec<Vector3D> = (0..10).map(|i| Vector3D::new(i as f64, 2.0 * i as f64, 0.0)).collect();
+        let sphere = minimal_enclosing_sphere(&collinear).unwrap();
+        assert!(collinear.iter().all(|p| (*p - sphere.center).magnitude() <= sphere.radius + 1e-9));
+        assert!((sphere.radius - 0.5 * 405.0_f64.sqrt()).abs() < 1e-9);
+
+        let coplanar: Vec<Vector3D> = (0..16).map(|i| {
+            let angle = i as f64 * std::f64::consts::TAU / 16.0;
+            Vector3D::new(angle.cos(), angle.sin(), 0.0)
+        }).collect();
+        let sphere = minimal_enclosing_sphere(&coplanar).unwrap();
+        assert!((sphere.radius - 1.0).abs() < 1e-9);
+        assert!(sphere.center.magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn test_fitted_boxes_contain_points() {
+        assert!(pca_obb(&[]).is_none());
+        assert!(approximate_minimal_obb(&[]).is_none());
+        assert!(tight_aabb(&[]).is_empty());
+
+        let points = cloud(400);
+        let aabb = tight_aabb(&points);
+        assert!(points.iter().all(|p| aabb.contains_point(p)));
+
+        let pca = pca_obb(&points).unwrap();
+        assert!(obb_contains_all(&pca, &points));
+        assert!((pca.orientation.determinant() - 1.0).abs() < 1e-12);
+
+        let best = approximate_minimal_obb(&points).unwrap();
+        assert!(obb_contains_all(&best, &points));
+        assert!(best.volume() <= pca.volume() + 1e-9);
+        assert!(best.volume() <= aabb.volume() + 1e-9);
+        // The cloud was generated inside a 10 x 4 x 1 box
+        assert!(best.volume() <= 40.0 + 1e-9);
+        assert!(best.volume() < 0.5 * aabb.volume());
+    }
+
+    #[test]
+    fn test_minimal_obb_of_rotated_box() {
+        // Corners of a 4 x 2 x 1 box rotated 30 degrees about z
+        let (s, c) = 30.0_f64.to_radians().sin_cos();
+        let frame = Matrix3D::new(c, -s, 0.0,
+                                  s, c, 0.0,
+                                  0.0, 0.0, 1.0);
+        let local = Obb::new(Vector3D::default(), Matrix3D::identity(), Vector3D::new(2.0, 1.0, 0.5));
+        let corners: Vec<Vector3D> = local.corners().iter().map(|p| frame * *p).collect();
+
+        let obb = approximate_minimal_obb(&corners).unwrap();
+        assert!(obb_contains_all(&obb, &corners));
+        assert!((obb.volume() - 8.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,332 @@
+use super::{Curve, Matrix3D, Vector3D, EPSILON};
+
+/// Returns the Frenet-Serret frame of a curve at `t`
+///
+/// The frame's columns are the unit tangent, the principal normal towards
+/// the centre of curvature and the binormal, so it is a rotation. Returns
+/// None where the frame is undefined: where the curve stops or is locally
+/// straight.
+///
+/// # Arguments
+/// - `curve` - The curve
+/// - `t` - The parameter to evaluate at
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{frenet_frame, QuadraticBezier, Vector3D};
+///
+/// let arc = QuadraticBezier::new(Vector3D::new(-1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(1.0, 0.0, 0.0));
+/// let frame = frenet_frame(&arc, 0.5).unwrap();
+/// assert_eq!(frame.column(0), Vector3D::new(1.0, 0.0, 0.0));
+/// assert_eq!(frame.column(1), Vector3D::new(0.0, -1.0, 0.0));
+/// assert_eq!(frame.column(2), Vector3D::new(0.0, 0.0, -1.0));
+///
+/// let line = QuadraticBezier::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(2.0, 0.0, 0.0));
+/// assert!(frenet_frame(&line, 0.5).is_none());
+/// ```
+pub fn frenet_frame<C: Curve + ?Sized>(curve: &C, t: f64) -> Option<Matrix3D> {
+    let velocity = curve.derivative(t);
+    let speed = velocity.magnitude();
+    if speed <= EPSILON {
+        return None;
+    }
+    let binormal = velocity.cross(&curve.second_derivative(t));
+    let length = binormal.magnitude();
+    // |v x a| / |v|^3 is the curvature, so this is a relative straightness test
+    if length <= EPSILON * speed * speed * speed.max(1.0) {
+        return None;
+    }
+    let tangent = velocity / speed;
+    let binormal = binormal / length;
+    Some(Matrix3D::new_vectors(tangent, binormal.cross(&tangent), binormal))
+}
+
+/// Returns Frenet-Serret frames at each parameter, bridging the places
+/// where the frame is undefined
+///
+/// Where the curve is straight the last defined normal is carried along,
+/// or the first one found further on for a straight start, so a tube
+/// built from the frames does not collapse or spin. A curve straight
+/// everywhere gets an arbitrary but consistent normal. Frenet frames flip
+/// when the curvature changes side, use `rotation_minimising_frames` to
+/// avoid that.
+///
+/// # Arguments
+/// - `curve` - The curve
+/// - `parameters` - The parameters to sample at, in order
+pub fn frenet_frames<C: Curve + ?Sized>(curve: &C, parameters: &[f64]) -> Vec<Matrix3D> {
+    let tangents = sample_tangents(curve, parameters);
+    let frenet: Vec<Option<Matrix3D>> = parameters.iter().map(|&t| frenet_frame(curve, t)).collect();
+    let Some(first) = frenet.iter().position(Option::is_some) else {
+        return transport_from(&tangents, tangents.first().copied().unwrap_or_default().any_perpendicular());
+    };
+
+    let mut normal = frenet[first].unwrap().column(1);
+    let mut frames = Vec::with_capacity(parameters.len());
+    for (i, tangent) in tangents.iter().enumerate() {
+        match frenet[i] {
+            Some(frame) if i >= first => {
+                normal = frame.column(1);
+                frames.push(frame);
+            }
+            _ => frames.push(frame_from(tangent, &normal)),
+        }
+    }
+    frames
+}
+
+/// Returns rotation-minimising frames at each parameter
+///
+/// Uses the double reflection method of Wang et al., which carries the
+/// normal from sample to sample with the least rotation about the
+/// tangent. The frames' columns are the unit tangent, the normal and
+/// their cross product. The first normal is the Frenet normal where that
+/// is defined, otherwise any perpendicular.
+///
+/// For a closed loop sampled over its whole domain, the carried normal
+/// generally comes back rotated about the tangent. With `closed` set,
+/// that twist is spread over the samples by distance so the last frame
+/// matches the first.
+///
+/// # Arguments
+/// - `curve` - The curve
+/// - `parameters` - The parameters to sample at, in order
+/// - `closed` - Whether to correct the twist of a closed loop
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{rotation_minimising_frames, CubicBezier, Vector3D};
+///
+/// let curve = CubicBezier::new(
+///     Vector3D::new(0.0, 0.0, 0.0),
+///     Vector3D::new(1.0, 2.0, 0.0),
+///     Vector3D::new(3.0, -1.0, 0.0),
+///     Vector3D::new(4.0, 1.0, 0.0),
+/// );
+/// let parameters: Vec<f64> = (0..=32).map(|i| i as f64 / 32.0).collect();
+/// // A flat curve keeps its normal in the plane and the third axis fixed
+/// for frame in rotation_minimising_frames(&curve, &parameters, false) {
+///     assert!((frame.column(2).z.abs() - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn rotation_minimising_frames<C: Curve + ?Sized>(curve: &C, parameters: &[f64], closed: bool) -> Vec<Matrix3D> {
+    if parameters.is_empty() {
+        return Vec::new();
+    }
+    let tangents = sample_tangents(curve, parameters);
+    let points: Vec<Vector3D> = parameters.iter().map(|&t| curve.position(t)).collect();
+    let start = frenet_frame(curve, parameters[0]).map_or_else(|| tangents[0].any_perpendicular(), |f| f.column(1));
+
+    let mut normals = vec![start - tangents[0] * tangents[0].dot(&start)];
+    for i in 0..points.len() - 1 {
+        let r = normals[i];
+        let (t, next) = (tangents[i], tangents[i + 1]);
+        // Reflect across the plane bisecting the two points, then across
+        // the one that takes the reflected tangent to the next tangent
+        let v1 = points[i + 1] - points[i];
+        let c1 = v1.dot(&v1);
+        let (r_l, t_l) = if c1 > 0.0 { (r - v1 * (2.0 * v1.dot(&r) / c1), t - v1 * (2.0 * v1.dot(&t) / c1)) } else { (r, t) };
+        let v2 = next - t_l;
+        let c2 = v2.dot(&v2);
+        let r = if c2 > 0.0 { r_l - v2 * (2.0 * v2.dot(&r_l) / c2) } else { r_l };
+        normals.push(r);
+    }
+
+    if closed && points.len() > 1 {
+        let (first, last) = (normals[0], normals[normals.len() - 1]);
+        let axis = tangents[tangents.len() - 1];
+        let twist = last.cross(&first).dot(&axis).atan2(last.dot(&first));
+        let mut distances = vec![0.0];
+        for pair in points.windows(2) {
+            distances.push(distances[distances.len() - 1] + (pair[1] - pair[0]).magnitude());
+        }
+        let total = distances[distances.len() - 1];
+        for (i, normal) in normals.iter_mut().enumerate() {
+            let fraction = if total > 0.0 { distances[i] / total } else { i as f64 / (points.len() - 1) as f64 };
+            let (sin, cos) = (twist * fraction).sin_cos();
+            *normal = *normal * cos + tangents[i].cross(normal) * sin;
+        }
+    }
+
+    tangents.iter().zip(&normals).map(|(t, r)| frame_from(t, r)).collect()
+}
+
+/// Unit tangents at each parameter, falling back on the direction between
+/// neighbouring samples, then the previous tangent, where the curve stops
+fn sample_tangents<C: Curve + ?Sized>(curve: &C, parameters: &[f64]) -> Vec<Vector3D> {
+    let mut tangents: Vec<Vector3D> = Vec::with_capacity(parameters.len());
+    for (i, &t) in parameters.iter().enumerate() {
+        let mut tangent = curve.derivative(t);
+        if tangent.magnitude() <= EPSILON {
+            let before = parameters[i.saturating_sub(1)];
+            let after = parameters[(i + 1).min(parameters.len() - 1)];
+            tangent = curve.position(after) - curve.position(before);
+        }
+        if tangent.magnitude() <= EPSILON {
+            tangent = tangents.last().copied().unwrap_or(Vector3D::new(1.0, 0.0, 0.0));
+        }
+        tangents.push(tangent.normalise());
+    }
+    tangents
+}
+
+/// A frame with the given tangent and a normal as close to `normal` as
+/// possible, falling back on any perpendicular
+fn frame_from(tangent: &Vector3D, normal: &Vector3D) -> Matrix3D {
+    let mut normal = *normal - *tangent * tangent.dot(normal);
+    if normal.magnitude() <= EPSILON {
+        normal = tangent.any_perpendicular();
+    }
+    let normal = normal.normalise();
+    Matrix3D::new_vectors(*tangent, normal, tangent.cross(&normal))
+}
+
+/// Frames along the tangents, each normal projected from the previous one
+fn transport_from(tangents: &[Vector3D], start: Vector3D) -> Vec<Matrix3D> {
+    let mut normal = start;
+    tangents
+        .iter()
+        .map(|t| {
+            let frame = frame_from(t, &normal);
+            normal = frame.column(1);
+            frame
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::curves::testing::Helix;
+    use crate::maths::{CatmullRom, CatmullRomKind};
+    use std::f64::consts::PI;
+
+    /// A (2, 3) torus knot, closed over 0..2 pi
+    struct TrefoilKnot;
+
+    impl Curve for TrefoilKnot {
+        fn position(&self, t: f64) -> Vector3D {
+            let r = 2.0 + (3.0 * t).cos();
+            Vector3D::new(r * (2.0 * t).cos(), r * (2.0 * t).sin(), (3.0 * t).sin())
+        }
+
+        fn derivative(&self, t: f64) -> Vector3D {
+            let r = 2.0 + (3.0 * t).cos();
+            let dr = -3.0 * (3.0 * t).sin();
+            Vector3D::new(
+                dr * (2.0 * t).cos() - 2.0 * r * (2.0 * t).sin(),
+                dr * (2.0 * t).sin() + 2.0 * r * (2.0 * t).cos(),
+                3.0 * (3.0 * t).cos(),
+            )
+        }
+
+        fn second_derivative(&self, t: f64) -> Vector3D {
+            let h = 1e-5;
+            (self.derivative(t + h) - self.derivative(t - h)) / (2.0 * h)
+        }
+
+        fn domain(&self) -> (f64, f64) {
+            (0.0, 2.0 * PI)
+        }
+    }
+
+    fn assert_rotation(m: &Matrix3D) {
+        let product = *m * m.transpose();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((product[(i, j)] - expected).abs() < 1e-9);
+            }
+        }
+        assert!((m.determinant() - 1.0).abs() < 1e-9);
+    }
+
+    fn samples(start: f64, end: f64, count: usize) -> Vec<f64> {
+        (0..=count).map(|i| start + (end - start) * i as f64 / count as f64).collect()
+    }
+
+    #[test]
+    fn test_frenet_helix() {
+        for t in samples(0.0, 10.0, 20) {
+            let frame = frenet_frame(&Helix, t).unwrap();
+            assert_rotation(&frame);
+            // The principal normal of a helix points straight at its axis
+            let normal = frame.column(1);
+            assert!((normal - Vector3D::new(-t.cos(), -t.sin(), 0.0)).magnitude() < 1e-12);
+            assert!((frame.column(0) - Helix.derivative(t).normalise()).magnitude() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_frames_straight_segments() {
+        // Curved, then straight in the middle, then curved again
+        let points = vec![
+            Vector3D::new(-1.0, 1.0, 0.0),
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(3.0, 0.0, 0.0),
+            Vector3D::new(4.0, 0.0, 1.0),
+        ];
+        let spline = CatmullRom::new(points, CatmullRomKind::Uniform).unwrap();
+        assert!(frenet_frame(&spline, 1.5).is_none());
+        let parameters = samples(0.0, 3.0, 60);
+        let frenet = frenet_frames(&spline, &parameters);
+        let rmf = rotation_minimising_frames(&spline, &parameters, false);
+        for (i, (f, r)) in frenet.iter().zip(&rmf).enumerate() {
+            assert_rotation(f);
+            assert_rotation(r);
+            assert!((f.column(0) - r.column(0)).magnitude() < 1e-12);
+            // The straight part carries the normal from before it
+            if parameters[i] > 1.0 && parameters[i] < 2.0 {
+                assert!((f.column(1) - frenet[20].column(1)).magnitude() < 1e-9);
+            }
+        }
+
+        // Straight everywhere still gives valid, constant frames
+        let line = CatmullRom::new((0..5).map(|i| Vector3D::new(i as f64, i as f64, 0.0)).collect(), CatmullRomKind::Uniform).unwrap();
+        let frames = frenet_frames(&line, &samples(0.0, 2.0, 10));
+        for frame in &frames {
+            assert_rotation(frame);
+            for column in 0..3 {
+                assert!((frame.column(column) - frames[0].column(column)).magnitude() < 1e-12);
+            }
+        }
+        assert!(rotation_minimising_frames(&line, &[], false).is_empty());
+    }
+
+    #[test]
+    fn test_rotation_minimising_frames() {
+        let parameters = samples(0.0, 10.0, 400);
+        let frames = rotation_minimising_frames(&Helix, &parameters, false);
+        for (i, frame) in frames.iter().enumerate() {
+            assert_rotation(frame);
+            assert!((frame.column(0) - Helix.derivative(parameters[i]).normalise()).magnitude() < 1e-12);
+        }
+        // The Frenet normal turns away from it at the torsion, 1/5 for this
+        // helix, times the speed of sqrt(5)
+        for (i, frame) in frames.iter().enumerate() {
+            let frenet = frenet_frame(&Helix, parameters[i]).unwrap();
+            let angle = frame.column(1).cross(&frenet.column(1)).dot(&frame.column(0)).atan2(frame.column(1).dot(&frenet.column(1)));
+            let expected = parameters[i] / 5.0_f64.sqrt();
+            let wrapped = (angle - expected + PI).rem_euclid(2.0 * PI) - PI;
+            assert!(wrapped.abs() < 1e-4, "twist at {}", parameters[i]);
+        }
+    }
+
+    #[test]
+    fn test_rotation_minimising_closed_loop() {
+        let parameters = samples(0.0, 2.0 * PI, 600);
+        let open = rotation_minimising_frames(&TrefoilKnot, &parameters, false);
+        let closed = rotation_minimising_frames(&TrefoilKnot, &parameters, true);
+        let (first, last) = (open[0].column(1), open[open.len() - 1].column(1));
+        assert!((first - last).magnitude() > 1e-3, "the knot has a twist to correct");
+
+        assert!((closed[0].column(1) - closed[closed.len() - 1].column(1)).magnitude() < 1e-9);
+        for pair in closed.windows(2) {
+            assert_rotation(&pair[1]);
+            // Still smooth: neighbouring normals barely move
+            assert!((pair[1].column(1) - pair[0].column(1)).magnitude() < 0.1);
+        }
+    }
+}
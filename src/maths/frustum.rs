@@ -0,0 +1,387 @@
+use super::{Aabb, DepthRange, Matrix3D, Matrix4D, Obb, Plane, ProjectionOptions, Sphere, Vector3D, EPSILON};
+
+/// Result of testing a shape against a Frustum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    /// Entirely inside every plane
+    Inside,
+    /// Entirely outside at least one plane
+    Outside,
+    /// Straddling one or more planes, and not provably outside
+    Intersecting,
+}
+
+/// View frustum bounded by six planes with normals facing inwards
+///
+/// A point is inside when its signed distance to every plane is positive
+/// or zero. The planes are stored in the order given by the `LEFT`..`FAR`
+/// constants. An infinite far plane is stored with a zero normal and a
+/// positive distance, so every point is in front of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    pub const LEFT: usize = 0;
+    pub const RIGHT: usize = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize = 3;
+    pub const NEAR: usize = 4;
+    pub const FAR: usize = 5;
+
+    /// Plane mask with every plane still to be tested, the starting value
+    /// for hierarchical culling
+    pub const ALL_PLANES: u8 = 0b11_1111;
+
+    /// Creates a new Frustum from six inward-facing planes
+    pub fn new(planes: [Plane; 6]) -> Self {
+        Self { planes }
+    }
+
+    /// Extracts the frustum from a combined `projection * view` matrix
+    ///
+    /// Uses the Gribb-Hartmann method: each clip-space bound such as
+    /// `-w <= x` is a plane in world space made from two rows of the matrix.
+    /// With a projection alone the planes are in view space. For reverse-Z
+    /// matrices the near end of the depth range holds the far plane, so the
+    /// two are swapped back into `NEAR` and `FAR` order.
+    ///
+    /// # Arguments
+    /// - `m` - The projection-view matrix, mapping world points to clip space
+    /// - `options` - The clip-space conventions the projection targets,
+    ///   handedness is already baked into `m` and is ignored
+    pub fn from_matrix(m: &Matrix4D, options: &ProjectionOptions) -> Self {
+        let [r0, r1, r2, r3] = [m.row(0), m.row(1), m.row(2), m.row(3)];
+        let add = |a: [f64; 4], b: [f64; 4]| std::array::from_fn(|i| a[i] + b[i]);
+        let sub = |a: [f64; 4], b: [f64; 4]| std::array::from_fn(|i| a[i] - b[i]);
+        let depth_low = match options.depth {
+            DepthRange::ZeroToOne => r2,
+            DepthRange::NegativeOneToOne => add(r3, r2),
+        };
+        let mut planes = [add(r3, r0), sub(r3, r0), add(r3, r1), sub(r3, r1), depth_low, sub(r3, r2)].map(plane_from_row);
+        if options.reverse_z {
+            planes.swap(Self::NEAR, Self::FAR);
+        }
+        Self { planes }
+    }
+
+    /// Builds a perspective frustum directly from camera parameters
+    ///
+    /// The camera looks down its local -z axis with +y up, so the columns
+    /// of `orientation` are the world directions of the camera's right, up
+    /// and backward axes.
+    ///
+    /// # Arguments
+    /// - `position` - The eye position
+    /// - `orientation` - The camera-to-world rotation
+    /// - `fov_y` - The full vertical field of view in radians
+    /// - `aspect` - Width divided by height
+    /// - `near` - Distance to the near plane
+    /// - `far` - Distance to the far plane, may be `f64::INFINITY`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Containment, Frustum, Matrix3D, Vector3D};
+    ///
+    /// let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+    ///                                         std::f64::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+    /// assert_eq!(frustum.classify_point(&Vector3D::new(0.0, 0.0, -10.0)), Containment::Inside);
+    /// assert_eq!(frustum.classify_point(&Vector3D::new(0.0, 0.0, 10.0)), Containment::Outside);
+    /// ```
+    pub fn from_perspective(
+        position: &Vector3D,
+        orientation: &Matrix3D,
+        fov_y: f64,
+        aspect: f64,
+        near: f64,
+        far: f64,
+    ) -> Self {
+        let right = orientation.column(0);
+        let up = orientation.column(1);
+        let forward = -orientation.column(2);
+        let tan_y = (fov_y * 0.5).tan();
+        let tan_x = tan_y * aspect;
+
+        let side = |normal: Vector3D| Plane::from_point_normal(*position, normal);
+        let far_plane = if far.is_finite() {
+            Plane::from_point_normal(*position + forward * far, -forward)
+        } else {
+            Plane::new(Vector3D::default(), 1.0)
+        };
+        Self {
+            planes: [
+                side(right + forward * tan_x),
+                side(-right + forward * tan_x),
+                side(up + forward * tan_y),
+                side(-up + forward * tan_y),
+                Plane::from_point_normal(*position + forward * near, forward),
+                far_plane,
+            ],
+        }
+    }
+
+    /// Returns the eight corners, near face first, or `None` if the far
+    /// plane is infinite or the planes do not meet
+    ///
+    /// Each face is ordered bottom-left, bottom-right, top-right, top-left.
+    pub fn corners(&self) -> Option<[Vector3D; 8]> {
+        let p = &self.planes;
+        let corner = |depth: usize, x: usize, y: usize| Plane::intersect_planes(&p[depth], &p[x], &p[y]);
+        let mut corners = [Vector3D::default(); 8];
+        for (face, depth) in [Self::NEAR, Self::FAR].into_iter().enumerate() {
+            let quad = [(Self::LEFT, Self::BOTTOM), (Self::RIGHT, Self::BOTTOM), (Self::RIGHT, Self::TOP), (Self::LEFT, Self::TOP)];
+            for (i, (x, y)) in quad.into_iter().enumerate() {
+                corners[face * 4 + i] = corner(depth, x, y)?;
+            }
+        }
+        Some(corners)
+    }
+
+    /// Classifies a point, points on a plane count as inside
+    pub fn classify_point(&self, point: &Vector3D) -> Containment {
+        if self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0) {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+
+    /// Classifies a sphere
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Containment, Frustum, Matrix3D, Sphere, Vector3D};
+    ///
+    /// let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+    ///                                         std::f64::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+    /// let straddling = Sphere::new(Vector3D::new(0.0, 0.0, -1.0), 0.5);
+    /// assert_eq!(frustum.classify_sphere(&straddling), Containment::Intersecting);
+    /// ```
+    pub fn classify_sphere(&self, sphere: &Sphere) -> Containment {
+        self.classify_sphere_coherent(sphere, Self::ALL_PLANES, &mut 0).0
+    }
+
+    /// Classifies an Aabb, an empty box is outside
+    pub fn classify_aabb(&self, aabb: &Aabb) -> Containment {
+        self.classify_aabb_coherent(aabb, Self::ALL_PLANES, &mut 0).0
+    }
+
+    /// Classifies an Obb
+    pub fn classify_obb(&self, obb: &Obb) -> Containment {
+        let axes = [obb.axis(0) * obb.half_extents.x, obb.axis(1) * obb.half_extents.y, obb.axis(2) * obb.half_extents.z];
+        self.classify(&obb.center, |normal| axes.iter().map(|a| normal.dot(a).abs()).sum(), Self::ALL_PLANES, &mut 0).0
+    }
+
+    /// Classifies a sphere for hierarchical culling, see `classify_aabb_coherent`
+    pub fn classify_sphere_coherent(&self, sphere: &Sphere, mask: u8, last_rejecting: &mut usize) -> (Containment, u8) {
+        self.classify(&sphere.center, |_| sphere.radius, mask, last_rejecting)
+    }
+
+    /// Classifies an Aabb for hierarchical culling
+    ///
+    /// Only the planes whose bits are set in `mask` are tested. The returned
+    /// mask has a bit set for each plane the box straddles: pass it down
+    /// when testing the box's children, since a child can only straddle
+    /// planes its parent straddles. Start at the root with `ALL_PLANES`.
+    ///
+    /// `last_rejecting` is the plane coherency hint. It holds the index of
+    /// the plane that last rejected this node and is tested first. Keep one
+    /// per node between frames, since a node that was culled is usually
+    /// culled by the same plane again.
+    ///
+    /// # Arguments
+    /// - `aabb` - The box to test
+    /// - `mask` - The planes still to test, from the parent
+    /// - `last_rejecting` - The plane that rejected this box last time
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Aabb, Containment, Frustum, Matrix3D, Vector3D};
+    ///
+    /// let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+    ///                                         std::f64::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+    /// let parent = Aabb::new(Vector3D::new(-1.0, -1.0, -200.0), Vector3D::new(1.0, 1.0, -10.0));
+    /// let mut hint = 0;
+    /// let (result, mask) = frustum.classify_aabb_coherent(&parent, Frustum::ALL_PLANES, &mut hint);
+    /// assert_eq!(result, Containment::Intersecting);
+    /// // Only the far plane is crossed, so children need only test that one
+    /// assert_eq!(mask, 1 << Frustum::FAR);
+    ///
+    /// let child = Aabb::new(Vector3D::new(-1.0, -1.0, -20.0), Vector3D::new(1.0, 1.0, -10.0));
+    /// assert_eq!(frustum.classify_aabb_coherent(&child, mask, &mut hint), (Containment::Inside, 0));
+    /// ```
+    pub fn classify_aabb_coherent(&self, aabb: &Aabb, mask: u8, last_rejecting: &mut usize) -> (Containment, u8) {
+        if aabb.is_empty() {
+            return (Containment::Outside, 0);
+        }
+        let half = aabb.half_extents();
+        self.classify(
+            &aabb.center(),
+            |normal| normal.x.abs() * half.x + normal.y.abs() * half.y + normal.z.abs() * half.z,
+            mask,
+            last_rejecting,
+        )
+    }
+
+    /// Shared plane loop: a shape with `center` extends `radius(normal)`
+    /// along each plane normal
+    fn classify<F>(&self, center: &Vector3D, radius: F, mask: u8, last_rejecting: &mut usize) -> (Containment, u8)
+    where
+        F: Fn(&Vector3D) -> f64,
+    {
+        let start = *last_rejecting % 6;
+        let mut straddled = 0;
+        for i in (0..6).map(|offset| (start + offset) % 6) {
+            if mask & (1 << i) == 0 {
+                continue;
+            }
+            let plane = &self.planes[i];
+            let distance = plane.signed_distance(center);
+            let r = radius(&plane.normal);
+            if distance < -r {
+                *last_rejecting = i;
+                return (Containment::Outside, 0);
+            }
+            if distance < r {
+                straddled |= 1 << i;
+            }
+        }
+        if straddled == 0 {
+            (Containment::Inside, 0)
+        } else {
+            (Containment::Intersecting, straddled)
+        }
+    }
+}
+
+/// Turns a clip-space bound `row . (x, y, z, 1) >= 0` into a unit plane
+fn plane_from_row([a, b, c, d]: [f64; 4]) -> Plane {
+    let plane = Plane::new(Vector3D::new(a, b, c), d);
+    let magnitude = plane.normal.magnitude();
+    if magnitude <= EPSILON * d.abs() || magnitude == 0.0 {
+        // An infinite far plane has no normal and bounds nothing
+        return Plane::new(Vector3D::default(), 1.0);
+    }
+    plane.normalised()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::{perspective, ProjectionOptions};
+
+    fn camera() -> (Vector3D, Matrix3D) {
+        // Rotated 90 degrees about y so the camera looks down world -x
+        let orientation = Matrix3D::new(0.0, 0.0, 1.0,
+                                        0.0, 1.0, 0.0,
+                                        -1.0, 0.0, 0.0);
+        (Vector3D::new(5.0, 2.0, -3.0), orientation)
+    }
+
+    fn assert_planes_close(a: &Frustum, b: &Frustum) {
+        for (p, q) in a.planes.iter().zip(&b.planes) {
+            assert!((p.normal - q.normal).magnitude() < 1e-9, "{:?} != {:?}", p, q);
+            assert!((p.distance - q.distance).abs() < 1e-9, "{:?} != {:?}", p, q);
+        }
+    }
+
+    #[test]
+    fn test_frustum_extraction_matches_camera() {
+        let (position, orientation) = camera();
+        let (fov, aspect, near, far) = (1.1, 16.0 / 9.0, 0.5, 250.0);
+        let view = Matrix4D::from_affine(&orientation.transpose(), &-(orientation.transpose() * position));
+        let direct = Frustum::from_perspective(&position, &orientation, fov, aspect, near, far);
+
+        for depth in [DepthRange::ZeroToOne, DepthRange::NegativeOneToOne] {
+            for reverse_z in [false, true] {
+                let options = ProjectionOptions { depth, reverse_z, ..Default::default() };
+                let extracted = Frustum::from_matrix(&(perspective(fov, aspect, near, far, &options) * view), &options);
+                assert_planes_close(&extracted, &direct);
+            }
+        }
+
+        // Infinite far plane accepts everything in front of the camera
+        let infinite = Frustum::from_perspective(&position, &orientation, fov, aspect, near, f64::INFINITY);
+        assert_eq!(infinite.classify_point(&(position + Vector3D::new(-1.0e9, 0.0, 0.0))), Containment::Inside);
+        assert!(infinite.corners().is_none());
+    }
+
+    #[test]
+    fn test_frustum_extraction_reverse_z_infinite_far() {
+        let near = 0.5;
+        for depth in [DepthRange::ZeroToOne, DepthRange::NegativeOneToOne] {
+            let options = ProjectionOptions { depth, reverse_z: true, ..Default::default() };
+            let frustum = Frustum::from_matrix(&perspective(1.1, 16.0 / 9.0, near, f64::INFINITY, &options), &options);
+            let near_plane = frustum.planes[Frustum::NEAR];
+            assert!((near_plane.normal - Vector3D::new(0.0, 0.0, -1.0)).magnitude() < 1e-12, "{:?}", depth);
+            assert!((near_plane.distance + near).abs() < 1e-12, "{:?}", depth);
+            let far_plane = frustum.planes[Frustum::FAR];
+            assert_eq!(far_plane.normal, Vector3D::default());
+            assert!(far_plane.distance > 0.0);
+            assert_eq!(frustum.classify_point(&Vector3D::new(0.0, 0.0, -1.0e12)), Containment::Inside);
+            assert_eq!(frustum.classify_point(&Vector3D::new(0.0, 0.0, -0.25)), Containment::Outside);
+        }
+    }
+
+    #[test]
+    fn test_frustum_corners() {
+        let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+                                                std::f64::consts::FRAC_PI_2, 2.0, 1.0, 10.0);
+        let corners = frustum.corners().unwrap();
+        assert!((corners[0] - Vector3D::new(-2.0, -1.0, -1.0)).magnitude() < 1e-12);
+        assert!((corners[6] - Vector3D::new(20.0, 10.0, -10.0)).magnitude() < 1e-12);
+        for corner in corners {
+            let nudged = corner * 0.999 + Vector3D::new(0.0, 0.0, -0.005);
+            assert_eq!(frustum.classify_point(&nudged), Containment::Inside);
+        }
+    }
+
+    #[test]
+    fn test_frustum_classification() {
+        let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+                                                std::f64::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+        let inside = Sphere::new(Vector3D::new(0.0, 0.0, -50.0), 1.0);
+        let outside = Sphere::new(Vector3D::new(0.0, 60.0, -50.0), 1.0);
+        let straddling = Sphere::new(Vector3D::new(0.0, 0.0, -100.0), 1.0);
+        assert_eq!(frustum.classify_sphere(&inside), Containment::Inside);
+        assert_eq!(frustum.classify_sphere(&outside), Containment::Outside);
+        assert_eq!(frustum.classify_sphere(&straddling), Containment::Intersecting);
+
+        for sphere in [inside, outside, straddling] {
+            let obb = Obb::from_aabb(&sphere.aabb());
+            assert_eq!(frustum.classify_aabb(&sphere.aabb()), frustum.classify_obb(&obb));
+        }
+        assert_eq!(frustum.classify_aabb(&Aabb::empty()), Containment::Outside);
+
+        // Turned 45 degrees a box reaches the near plane it misses when aligned
+        let (s, c) = (0.5_f64.sqrt(), 0.5_f64.sqrt());
+        let rotation = Matrix3D::new(c, 0.0, s,
+                                     0.0, 1.0, 0.0,
+                                     -s, 0.0, c);
+        let center = Vector3D::new(0.0, 0.0, -0.4);
+        let half = Vector3D::new(0.5, 0.5, 0.5);
+        assert_eq!(frustum.classify_obb(&Obb::new(center, Matrix3D::identity(), half)), Containment::Outside);
+        assert_eq!(frustum.classify_obb(&Obb::new(center, rotation, half)), Containment::Intersecting);
+    }
+
+    #[test]
+    fn test_frustum_coherency() {
+        let frustum = Frustum::from_perspective(&Vector3D::default(), &Matrix3D::identity(),
+                                                std::f64::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+        let above = Aabb::new(Vector3D::new(-1.0, 40.0, -20.0), Vector3D::new(1.0, 42.0, -10.0));
+        let mut hint = 0;
+        assert_eq!(frustum.classify_aabb_coherent(&above, Frustum::ALL_PLANES, &mut hint).0, Containment::Outside);
+        assert_eq!(hint, Frustum::TOP);
+        // The hint does not change the answer for other boxes
+        let inside = Aabb::new(Vector3D::new(-1.0, -1.0, -20.0), Vector3D::new(1.0, 1.0, -10.0));
+        assert_eq!(frustum.classify_aabb_coherent(&inside, Frustum::ALL_PLANES, &mut hint), (Containment::Inside, 0));
+
+        // A cleared mask skips planes, so a box the parent was inside is inside
+        let mut hint = 0;
+        assert_eq!(frustum.classify_aabb_coherent(&above, 0, &mut hint), (Containment::Inside, 0));
+        let mask = 1 << Frustum::LEFT | 1 << Frustum::NEAR;
+        let near_left = Aabb::new(Vector3D::new(-30.0, -1.0, -2.0), Vector3D::new(0.0, 1.0, 0.0));
+        assert_eq!(frustum.classify_aabb_coherent(&near_left, mask, &mut hint), (Containment::Intersecting, mask));
+    }
+}
@@ -0,0 +1,637 @@
+use super::{SupportMap, Vector3D, EPSILON};
+
+/// Upper bound on GJK iterations, reached only by curved shapes that
+/// converge slowly or by inputs that are numerically degenerate
+const GJK_MAX_ITERATIONS: usize = 128;
+
+/// Upper bound on the number of points EPA adds to its polytope
+const EPA_MAX_ITERATIONS: usize = 256;
+
+/// GJK stops when the next support point improves the distance estimate
+/// by less than this fraction of it
+const GJK_RELATIVE_TOLERANCE: f64 = 1.0e-12;
+
+/// EPA stops when the polytope's nearest face is within this fraction of
+/// the true boundary
+const EPA_RELATIVE_TOLERANCE: f64 = 1.0e-9;
+
+/// Closest points between two separated shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Separation {
+    /// Distance between the shapes, always positive
+    pub distance: f64,
+    /// Unit vector pointing from the first shape towards the second
+    pub normal: Vector3D,
+    /// Closest point on the first shape
+    pub point_a: Vector3D,
+    /// Closest point on the second shape
+    pub point_b: Vector3D,
+}
+
+/// How deeply two overlapping shapes interpenetrate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Penetration {
+    /// Smallest distance the second shape must move to stop overlapping
+    pub depth: f64,
+    /// Unit vector pointing from the first shape towards the second, moving
+    /// the second shape by `normal * depth` separates them
+    pub normal: Vector3D,
+    /// Deepest point of the first shape inside the second
+    pub point_a: Vector3D,
+    /// Deepest point of the second shape inside the first
+    pub point_b: Vector3D,
+}
+
+/// Returns true if the two convex shapes overlap, touching counts
+///
+/// # Arguments
+/// - `a` - The first shape
+/// - `b` - The second shape
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{gjk_intersects, Aabb, Sphere, Vector3D};
+///
+/// let aabb = Aabb::new(Vector3D::new(-1.0, -1.0, -1.0), Vector3D::new(1.0, 1.0, 1.0));
+/// assert!(gjk_intersects(&aabb, &Sphere::new(Vector3D::new(2.0, 0.0, 0.0), 1.0)));
+/// assert!(!gjk_intersects(&aabb, &Sphere::new(Vector3D::new(2.0, 2.0, 0.0), 1.0)));
+/// ```
+pub fn gjk_intersects<A, B>(a: &A, b: &B) -> bool
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    matches!(gjk(a, b), Gjk::Overlapping(_))
+}
+
+/// Returns the closest points between two convex shapes
+///
+/// Returns None if the shapes overlap or touch.
+///
+/// # Arguments
+/// - `a` - The first shape
+/// - `b` - The second shape
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{gjk_distance, Sphere, Vector3D};
+///
+/// let a = Sphere::new(Vector3D::new(0.0, 0.0, 0.0), 1.0);
+/// let b = Sphere::new(Vector3D::new(5.0, 0.0, 0.0), 2.0);
+/// let separation = gjk_distance(&a, &b).unwrap();
+/// assert!((separation.distance - 2.0).abs() < 1e-9);
+/// assert!((separation.normal - Vector3D::new(1.0, 0.0, 0.0)).magnitude() < 1e-9);
+/// ```
+pub fn gjk_distance<A, B>(a: &A, b: &B) -> Option<Separation>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let Gjk::Separated(simplex) = gjk(a, b) else {
+        return None;
+    };
+    let (point_a, point_b) = simplex.closest_points();
+    let offset = point_b - point_a;
+    let distance = offset.magnitude();
+    Some(Separation {
+        distance,
+        normal: offset * (1.0 / distance),
+        point_a,
+        point_b,
+    })
+}
+
+/// Returns the penetration depth and contact normal of two convex shapes
+///
+/// Runs GJK and, if the shapes overlap, expands its final simplex with EPA.
+/// Returns None if the shapes are separated. Shapes that merely touch give
+/// a depth of zero.
+///
+/// # Arguments
+/// - `a` - The first shape
+/// - `b` - The second shape
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{epa_penetration, Aabb, Vector3D};
+///
+/// let a = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 2.0, 2.0));
+/// let b = Aabb::new(Vector3D::new(1.5, 0.5, 0.5), Vector3D::new(3.0, 1.5, 1.5));
+/// let penetration = epa_penetration(&a, &b).unwrap();
+/// assert!((penetration.depth - 0.5).abs() < 1e-9);
+/// assert!((penetration.normal - Vector3D::new(1.0, 0.0, 0.0)).magnitude() < 1e-9);
+/// ```
+pub fn epa_penetration<A, B>(a: &A, b: &B) -> Option<Penetration>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let Gjk::Overlapping(simplex) = gjk(a, b) else {
+        return None;
+    };
+    Some(epa(a, b, simplex))
+}
+
+/// A point of the Minkowski difference `A - B` and the shape points that made it
+#[derive(Debug, Clone, Copy)]
+struct SupportPoint {
+    w: Vector3D,
+    a: Vector3D,
+    b: Vector3D,
+}
+
+impl SupportPoint {
+    fn new<A, B>(a: &A, b: &B, direction: &Vector3D) -> Self
+    where
+        A: SupportMap + ?Sized,
+        B: SupportMap + ?Sized,
+    {
+        let pa = a.support(direction);
+        let pb = b.support(&-*direction);
+        Self { w: pa - pb, a: pa, b: pb }
+    }
+}
+
+/// A simplex with the barycentric weights of its point closest to the origin
+#[derive(Debug, Clone)]
+struct Simplex {
+    points: Vec<SupportPoint>,
+    weights: Vec<f64>,
+}
+
+impl Simplex {
+    fn closest(&self) -> Vector3D {
+        self.points.iter().zip(&self.weights).fold(Vector3D::default(), |v, (p, w)| v + p.w * *w)
+    }
+
+    fn closest_points(&self) -> (Vector3D, Vector3D) {
+        self.points.iter().zip(&self.weights).fold(
+            (Vector3D::default(), Vector3D::default()),
+            |(a, b), (p, w)| (a + p.a * *w, b + p.b * *w),
+        )
+    }
+}
+
+enum Gjk {
+    Separated(Simplex),
+    Overlapping(Vec<SupportPoint>),
+}
+
+/// Runs GJK on the Minkowski difference `A - B`
+fn gjk<A, B>(a: &A, b: &B) -> Gjk
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let first = SupportPoint::new(a, b, &Vector3D::new(1.0, 0.0, 0.0));
+    let mut simplex = Simplex { points: vec![first], weights: vec![1.0] };
+    let mut v = first.w;
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let v2 = v.magnitude_squared();
+        if v2 <= EPSILON * EPSILON {
+            return Gjk::Overlapping(simplex.points);
+        }
+
+        let w = SupportPoint::new(a, b, &-v);
+        // No support point gets meaningfully closer, v is the answer
+        if v2 - v.dot(&w.w) <= GJK_RELATIVE_TOLERANCE * v2 {
+            break;
+        }
+        // A repeated point means the simplex cannot grow any further
+        if simplex.points.iter().any(|p| p.w == w.w) {
+            break;
+        }
+
+        let mut points = simplex.points.clone();
+        points.push(w);
+        let Some(next) = closest_simplex(&points) else {
+            return Gjk::Overlapping(points);
+        };
+        let next_v = next.closest();
+        // Rounding can stall progress on nearly degenerate simplices
+        if next_v.magnitude_squared() >= v2 {
+            break;
+        }
+        simplex = next;
+        v = next_v;
+    }
+    Gjk::Separated(simplex)
+}
+
+/// Reduces `points` to the sub-simplex nearest the origin
+///
+/// Returns None if the origin is inside a tetrahedron.
+fn closest_simplex(points: &[SupportPoint]) -> Option<Simplex> {
+    let ws: Vec<Vector3D> = points.iter().map(|p| p.w).collect();
+    let weighted = match ws.len() {
+        1 => vec![(0, 1.0)],
+        2 => closest_on_segment(&ws, [0, 1]),
+        3 => closest_on_triangle(&ws, [0, 1, 2]),
+        _ => closest_on_tetrahedron(&ws)?,
+    };
+    Some(Simplex {
+        points: weighted.iter().map(|&(i, _)| points[i]).collect(),
+        weights: weighted.iter().map(|&(_, w)| w).collect(),
+    })
+}
+
+/// Closest point on segment `ws[i]`, `ws[j]` to the origin as (index, weight) pairs
+fn closest_on_segment(ws: &[Vector3D], [i, j]: [usize; 2]) -> Vec<(usize, f64)> {
+    let ab = ws[j] - ws[i];
+    let length2 = ab.magnitude_squared();
+    if length2 == 0.0 {
+        return vec![(i, 1.0)];
+    }
+    let t = -ws[i].dot(&ab) / length2;
+    if t <= 0.0 {
+        vec![(i, 1.0)]
+    } else if t >= 1.0 {
+        vec![(j, 1.0)]
+    } else {
+        vec![(i, 1.0 - t), (j, t)]
+    }
+}
+
+/// Closest point on a triangle to the origin, following Ericson's region tests
+fn closest_on_triangle(ws: &[Vector3D], [i, j, k]: [usize; 3]) -> Vec<(usize, f64)> {
+    let (a, b, c) = (ws[i], ws[j], ws[k]);
+    let ab = b - a;
+    let ac = c - a;
+
+    let d1 = -ab.dot(&a);
+    let d2 = -ac.dot(&a);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return vec![(i, 1.0)];
+    }
+    let d3 = -ab.dot(&b);
+    let d4 = -ac.dot(&b);
+    if d3 >= 0.0 && d4 <= d3 {
+        return vec![(j, 1.0)];
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let t = d1 / (d1 - d3);
+        return vec![(i, 1.0 - t), (j, t)];
+    }
+    let d5 = -ab.dot(&c);
+    let d6 = -ac.dot(&c);
+    if d6 >= 0.0 && d5 <= d6 {
+        return vec![(k, 1.0)];
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let t = d2 / (d2 - d6);
+        return vec![(i, 1.0 - t), (k, t)];
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return vec![(j, 1.0 - t), (k, t)];
+    }
+
+    let total = va + vb + vc;
+    if total <= 0.0 {
+        // Collinear triangle, the nearest edge is the answer
+        return [[i, j], [i, k], [j, k]]
+            .into_iter()
+            .map(|edge| closest_on_segment(ws, edge))
+            .min_by(|p, q| weighted_length(ws, p).total_cmp(&weighted_length(ws, q)))
+            .unwrap();
+    }
+    let v = vb / total;
+    let w = vc / total;
+    vec![(i, 1.0 - v - w), (j, v), (k, w)]
+}
+
+/// Closest point on a tetrahedron to the origin, None if the origin is inside
+fn closest_on_tetrahedron(ws: &[Vector3D]) -> Option<Vec<(usize, f64)>> {
+    let faces = [([0, 1, 2], 3), ([0, 1, 3], 2), ([0, 2, 3], 1), ([1, 2, 3], 0)];
+    let mut best: Option<Vec<(usize, f64)>> = None;
+    let mut best_distance = f64::INFINITY;
+    let mut outside_any = false;
+
+    for (face, opposite) in faces {
+        let [i, j, k] = face;
+        let normal = (ws[j] - ws[i]).cross(&(ws[k] - ws[i]));
+        let origin_side = -normal.dot(&ws[i]);
+        let opposite_side = normal.dot(&(ws[opposite] - ws[i]));
+        // A flat tetrahedron has no inside, so every face is a candidate
+        let flat = opposite_side.abs() <= EPSILON * normal.magnitude() * (ws[opposite] - ws[i]).magnitude();
+        if !flat && origin_side * opposite_side >= 0.0 {
+            continue;
+        }
+        outside_any = true;
+        let candidate = closest_on_triangle(ws, face);
+        let distance = weighted_length(ws, &candidate);
+        if distance < best_distance {
+            best_distance = distance;
+            best = Some(candidate);
+        }
+    }
+    if outside_any { best } else { None }
+}
+
+fn weighted_length(ws: &[Vector3D], weighted: &[(usize, f64)]) -> f64 {
+    weighted.iter().fold(Vector3D::default(), |v, &(i, w)| v + ws[i] * w).magnitude_squared()
+}
+
+/// A triangle of the EPA polytope with its outward unit normal
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    indices: [usize; 3],
+    normal: Vector3D,
+    distance: f64,
+}
+
+impl Face {
+    /// Returns None for a face with no area
+    fn new(points: &[SupportPoint], indices: [usize; 3]) -> Option<Self> {
+        let [a, b, c] = indices.map(|i| points[i].w);
+        let normal = (b - a).cross(&(c - a));
+        let length = normal.magnitude();
+        if length <= EPSILON * (b - a).magnitude() * (c - a).magnitude() || length == 0.0 {
+            return None;
+        }
+        let normal = normal * (1.0 / length);
+        Some(Self { indices, normal, distance: normal.dot(&a) })
+    }
+}
+
+/// Expands the GJK simplex into a polytope until its nearest face lies on
+/// the boundary of `A - B`
+fn epa<A, B>(a: &A, b: &B, simplex: Vec<SupportPoint>) -> Penetration
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let points = match grow_to_tetrahedron(a, b, simplex) {
+        Ok(points) => points,
+        Err(penetration) => return penetration,
+    };
+    let mut points = points;
+
+    // Orient the initial faces away from the tetrahedron's centroid
+    let centroid = points.iter().fold(Vector3D::default(), |c, p| c + p.w) * 0.25;
+    let mut faces: Vec<Face> = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+        .into_iter()
+        .filter_map(|indices| {
+            let face = Face::new(&points, indices)?;
+            if face.normal.dot(&(points[indices[0]].w - centroid)) < 0.0 {
+                Face::new(&points, [indices[0], indices[2], indices[1]])
+            } else {
+                Some(face)
+            }
+        })
+        .collect();
+
+    let mut nearest = nearest_face(&faces);
+    for _ in 0..EPA_MAX_ITERATIONS {
+        let face = faces[nearest];
+        let w = SupportPoint::new(a, b, &face.normal);
+        let reach = w.w.dot(&face.normal);
+        if reach - face.distance <= EPA_RELATIVE_TOLERANCE * face.distance.abs().max(1.0) {
+            break;
+        }
+
+        // Remove every face the new point can see and stitch its horizon to it
+        let index = points.len();
+        points.push(w);
+        let mut horizon: Vec<[usize; 2]> = Vec::new();
+        faces.retain(|f| {
+            let visible = f.normal.dot(&(w.w - points[f.indices[0]].w)) > 0.0;
+            if visible {
+                for e in 0..3 {
+                    let edge = [f.indices[e], f.indices[(e + 1) % 3]];
+                    if let Some(shared) = horizon.iter().position(|h| *h == [edge[1], edge[0]]) {
+                        horizon.swap_remove(shared);
+                    } else {
+                        horizon.push(edge);
+                    }
+                }
+            }
+            !visible
+        });
+        if horizon.is_empty() {
+            break;
+        }
+        faces.extend(horizon.iter().filter_map(|&[i, j]| Face::new(&points, [i, j, index])));
+        if faces.is_empty() {
+            break;
+        }
+        nearest = nearest_face(&faces);
+    }
+
+    if faces.is_empty() {
+        return touching(&points[0], Vector3D::new(1.0, 0.0, 0.0));
+    }
+    let face = faces[nearest_face(&faces)];
+    let ws: Vec<Vector3D> = points.iter().map(|p| p.w).collect();
+    // Weights of the origin's projection onto the face
+    let shifted: Vec<Vector3D> = ws.iter().map(|w| *w - face.normal * face.distance).collect();
+    let weighted = closest_on_triangle(&shifted, face.indices);
+    let (point_a, point_b) = weighted.iter().fold(
+        (Vector3D::default(), Vector3D::default()),
+        |(pa, pb), &(i, w)| (pa + points[i].a * w, pb + points[i].b * w),
+    );
+    Penetration {
+        depth: face.distance.max(0.0),
+        normal: face.normal,
+        point_a,
+        point_b,
+    }
+}
+
+fn nearest_face(faces: &[Face]) -> usize {
+    (0..faces.len()).min_by(|&i, &j| faces[i].distance.total_cmp(&faces[j].distance)).unwrap_or(0)
+}
+
+/// Zero-depth contact at a single support point
+fn touching(point: &SupportPoint, normal: Vector3D) -> Penetration {
+    Penetration { depth: 0.0, normal, point_a: point.a, point_b: point.b }
+}
+
+/// Adds support points until the simplex is a tetrahedron with volume
+///
+/// GJK can stop early with the origin on a point, edge or face. If `A - B`
+/// is flat in some direction there is no volume to find, so the shapes only
+/// touch and a zero-depth contact is returned instead.
+fn grow_to_tetrahedron<A, B>(a: &A, b: &B, mut points: Vec<SupportPoint>) -> Result<Vec<SupportPoint>, Penetration>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let axes = [Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(0.0, 0.0, 1.0)];
+    let scale = |points: &[SupportPoint]| points.iter().fold(1.0_f64, |s, p| s.max(p.w.magnitude()));
+
+    if points.len() == 1 {
+        let origin = points[0];
+        let tolerance = EPSILON * scale(&points);
+        let found = axes.iter().flat_map(|axis| [*axis, -*axis]).find_map(|d| {
+            let w = SupportPoint::new(a, b, &d);
+            ((w.w - origin.w).magnitude() > tolerance).then_some(w)
+        });
+        match found {
+            Some(w) => points.push(w),
+            None => return Err(touching(&origin, axes[0])),
+        }
+    }
+
+    if points.len() == 2 {
+        let along = points[1].w - points[0].w;
+        let least = axes.iter().copied().min_by(|p, q| p.dot(&along).abs().total_cmp(&q.dot(&along).abs())).unwrap();
+        let side = along.cross(&least);
+        let tolerance = EPSILON * scale(&points);
+        // Try the perpendicular direction rotated through six positions
+        let other = side.cross(&along.normalise());
+        let found = (0..6).find_map(|step| {
+            let (s, c) = (step as f64 * std::f64::consts::FRAC_PI_3).sin_cos();
+            let d = side * c + other * s;
+            let w = SupportPoint::new(a, b, &d);
+            let off_line = (w.w - points[0].w).cross(&along).magnitude() / along.magnitude();
+            (off_line > tolerance).then_some(w)
+        });
+        match found {
+            Some(w) => points.push(w),
+            None => return Err(touching(&points[0], side.normalise())),
+        }
+    }
+
+    if points.len() == 3 {
+        let normal = (points[1].w - points[0].w).cross(&(points[2].w - points[0].w)).normalise();
+        let tolerance = EPSILON * scale(&points);
+        let found = [normal, -normal].into_iter().find_map(|d| {
+            let w = SupportPoint::new(a, b, &d);
+            ((w.w - points[0].w).dot(&normal).abs() > tolerance).then_some(w)
+        });
+        match found {
+            Some(w) => points.push(w),
+            None => return Err(touching(&points[0], normal)),
+        }
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::{Aabb, Capsule, Matrix3D, MinkowskiSum, Obb, Sphere, Transformed};
+
+    fn rotation_z(degrees: f64) -> Matrix3D {
+        let (s, c) = degrees.to_radians().sin_cos();
+        Matrix3D::new(c, -s, 0.0,
+                      s, c, 0.0,
+                      0.0, 0.0, 1.0)
+    }
+
+    #[test]
+    fn test_gjk_sphere_distance() {
+        let a = Sphere::new(Vector3D::new(1.0, 2.0, 3.0), 1.0);
+        let b = Sphere::new(Vector3D::new(4.0, 6.0, 3.0), 1.5);
+        let separation = gjk_distance(&a, &b).unwrap();
+        assert!((separation.distance - 2.5).abs() < 1e-6);
+        assert!((separation.point_a - Vector3D::new(1.6, 2.8, 3.0)).magnitude() < 1e-5);
+        assert!((separation.point_b - Vector3D::new(3.1, 4.8, 3.0)).magnitude() < 1e-5);
+        assert!(gjk_intersects(&a, &Sphere::new(b.center, 4.0)));
+        assert!(epa_penetration(&a, &b).is_none());
+    }
+
+    #[test]
+    fn test_gjk_box_distance() {
+        let a = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 1.0));
+        // Edge of a box rotated 45 degrees faces the first box
+        let b = Obb::new(Vector3D::new(3.0, 0.5, 0.5), rotation_z(45.0), Vector3D::new(0.5, 0.5, 0.5));
+        let separation = gjk_distance(&a, &b).unwrap();
+        assert!((separation.distance - (2.0 - 0.5 * 2.0_f64.sqrt())).abs() < 1e-9);
+        assert!((separation.normal - Vector3D::new(1.0, 0.0, 0.0)).magnitude() < 1e-9);
+        assert!((separation.point_a.x - 1.0).abs() < 1e-9);
+
+        let c = Obb::new(Vector3D::new(1.6, 0.5, 0.5), rotation_z(45.0), Vector3D::new(0.5, 0.5, 0.5));
+        assert!(gjk_intersects(&a, &c));
+        assert!(gjk_distance(&a, &c).is_none());
+    }
+
+    #[test]
+    fn test_gjk_matches_capsule_and_hull() {
+        let capsule = Capsule::new(Vector3D::new(-2.0, 0.0, 0.0), Vector3D::new(2.0, 0.0, 0.0), 0.5);
+        let hull = [Vector3D::new(0.0, 2.0, -1.0), Vector3D::new(1.0, 2.0, 1.0),
+                    Vector3D::new(-1.0, 2.0, 1.0), Vector3D::new(0.0, 3.0, 0.0)];
+        let separation = gjk_distance(&capsule, &hull[..]).unwrap();
+        assert!((separation.distance - 1.5).abs() < 1e-9);
+        assert!((separation.point_b.y - 2.0).abs() < 1e-9);
+
+        // The same pair rotated and moved together keeps its distance
+        let rotation = Matrix3D::new(0.36, 0.48, -0.8,
+                                     -0.8, 0.6, 0.0,
+                                     0.48, 0.64, 0.6);
+        let offset = Vector3D::new(5.0, -3.0, 2.0);
+        let moved_capsule = Transformed::new(capsule, rotation, offset);
+        let moved_hull = Transformed::new(&hull[..], rotation, offset);
+        let moved = gjk_distance(&moved_capsule, &moved_hull).unwrap();
+        assert!((moved.distance - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gjk_degenerate_shapes() {
+        // Points and segments are shapes with no volume
+        let point = [Vector3D::new(1.0, 1.0, 1.0)];
+        assert!(gjk_intersects(&point[..], &point[..]));
+        let other = [Vector3D::new(1.0, 1.0, 4.0)];
+        assert!((gjk_distance(&point[..], &other[..]).unwrap().distance - 3.0).abs() < 1e-12);
+
+        // Parallel segments
+        let s1 = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 0.0)];
+        let s2 = [Vector3D::new(1.0, 2.0, 0.0), Vector3D::new(3.0, 2.0, 0.0)];
+        assert!((gjk_distance(&s1[..], &s2[..]).unwrap().distance - 2.0).abs() < 1e-12);
+
+        // Crossing segments touch at a single point
+        let s3 = [Vector3D::new(2.0, -1.0, 0.0), Vector3D::new(2.0, 1.0, 0.0)];
+        assert!(gjk_intersects(&s1[..], &s3[..]));
+        let contact = epa_penetration(&s1[..], &s3[..]).unwrap();
+        assert!(contact.depth.abs() < 1e-12);
+
+        // Coplanar squares overlapping in their plane
+        let square = |x: f64| [Vector3D::new(x, 0.0, 0.0), Vector3D::new(x + 2.0, 0.0, 0.0),
+                               Vector3D::new(x + 2.0, 2.0, 0.0), Vector3D::new(x, 2.0, 0.0)];
+        let (q1, q2) = (square(0.0), square(1.0));
+        assert!(gjk_intersects(&q1[..], &q2[..]));
+        assert!(epa_penetration(&q1[..], &q2[..]).unwrap().depth.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_epa_boxes() {
+        let a = Obb::new(Vector3D::default(), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+        let b = Obb::new(Vector3D::new(0.3, 1.4, 0.1), rotation_z(10.0), Vector3D::new(0.5, 0.5, 0.5));
+        let penetration = epa_penetration(&a, &b).unwrap();
+        assert!((penetration.normal - Vector3D::new(0.0, 1.0, 0.0)).magnitude() < 1e-9);
+        // Lowest corner of the rotated box sits below y = 1
+        let lowest = b.corners().iter().map(|c| c.y).fold(f64::INFINITY, f64::min);
+        assert!((penetration.depth - (1.0 - lowest)).abs() < 1e-9);
+
+        // Moving b out along the normal leaves the boxes just touching
+        let moved = Obb::new(b.center + penetration.normal * (penetration.depth + 1e-6), b.orientation, b.half_extents);
+        assert!(!gjk_intersects(&a, &moved));
+
+        // Identical boxes, origin at the centre of A - B
+        let same = epa_penetration(&a, &a).unwrap();
+        assert!((same.depth - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_epa_spheres() {
+        let a = Sphere::new(Vector3D::new(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(Vector3D::new(1.2, 0.9, 0.0), 1.0);
+        let penetration = epa_penetration(&a, &b).unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1e-4);
+        assert!((penetration.normal - Vector3D::new(0.8, 0.6, 0.0)).magnitude() < 1e-2);
+        assert!((penetration.point_a - Vector3D::new(0.8, 0.6, 0.0)).magnitude() < 1e-2);
+
+        // A sphere swept box against a box matches the rounded-box depth
+        let rounded = MinkowskiSum::new(Aabb::new(Vector3D::new(-1.0, -1.0, -1.0), Vector3D::new(1.0, 1.0, 1.0)),
+                                        Sphere::new(Vector3D::default(), 0.25));
+        let slab = Aabb::new(Vector3D::new(-5.0, -5.0, 1.0), Vector3D::new(5.0, 5.0, 2.0));
+        let penetration = epa_penetration(&rounded, &slab).unwrap();
+        assert!((penetration.depth - 0.25).abs() < 1e-6);
+        assert!((penetration.normal - Vector3D::new(0.0, 0.0, 1.0)).magnitude() < 1e-6);
+    }
+}
@@ -0,0 +1,461 @@
+use std::{collections::HashMap, fmt};
+
+use super::{Plane, SupportMap, Vector3D, EPSILON};
+
+/// Why a convex hull could not be built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HullError {
+    /// Fewer than four points were given
+    TooFewPoints,
+    /// Every point is the same, within tolerance
+    Coincident,
+    /// Every point lies on one line, within tolerance
+    Collinear,
+    /// Every point lies in one plane, within tolerance
+    Coplanar,
+}
+
+impl fmt::Display for HullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            HullError::TooFewPoints => "fewer than four points",
+            HullError::Coincident => "all points coincide",
+            HullError::Collinear => "all points are collinear",
+            HullError::Coplanar => "all points are coplanar",
+        };
+        write!(f, "cannot build a convex hull: {}", reason)
+    }
+}
+
+impl std::error::Error for HullError {}
+
+/// A triangle of a ConvexHull
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HullFace {
+    /// Indices into the hull's vertices, counter-clockwise seen from outside
+    pub indices: [usize; 3],
+    /// The face plane with its normal facing outwards
+    pub plane: Plane,
+}
+
+/// Convex hull of a point cloud as a closed triangle mesh
+///
+/// Only the points on the hull are kept as vertices. Coplanar faces are
+/// left as separate triangles sharing a plane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHull {
+    pub vertices: Vec<Vector3D>,
+    pub faces: Vec<HullFace>,
+}
+
+/// A face while the hull is being built
+#[derive(Debug, Clone)]
+struct BuildFace {
+    indices: [usize; 3],
+    normal: Vector3D,
+    offset: f64,
+    outside: Vec<usize>,
+    /// The outside point furthest from the face, with its distance
+    furthest: Option<(usize, f64)>,
+    alive: bool,
+}
+
+impl BuildFace {
+    fn new(points: &[Vector3D], indices: [usize; 3]) -> Self {
+        let [a, b, c] = indices.map(|i| points[i]);
+        let normal = (b - a).cross(&(c - a));
+        let length = normal.magnitude();
+        let normal = if length > 0.0 { normal / length } else { normal };
+        Self { indices, normal, offset: normal.dot(&a), outside: Vec::new(), furthest: None, alive: true }
+    }
+
+    fn distance(&self, point: &Vector3D) -> f64 {
+        self.normal.dot(point) - self.offset
+    }
+}
+
+impl ConvexHull {
+    /// Builds the convex hull of `points` with Quickhull
+    ///
+    /// Points within a small tolerance of the hull, relative to the size of
+    /// the point cloud, are treated as on it, so duplicate and coplanar
+    /// points do not create slivers.
+    ///
+    /// # Arguments
+    /// - `points` - The point cloud
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{ConvexHull, Vector3D};
+    ///
+    /// let mut points = Vec::new();
+    /// for x in [0.0, 1.0] {
+    ///     for y in [0.0, 1.0] {
+    ///         for z in [0.0, 1.0] {
+    ///             points.push(Vector3D::new(x, y, z));
+    ///         }
+    ///     }
+    /// }
+    /// points.push(Vector3D::new(0.5, 0.5, 0.5));
+    /// let hull = ConvexHull::new(&points).unwrap();
+    /// assert_eq!(hull.vertices.len(), 8);
+    /// assert_eq!(hull.faces.len(), 12);
+    /// assert!((hull.volume() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn new(points: &[Vector3D]) -> Result<Self, HullError> {
+        Self::with_vertex_limit(points, usize::MAX)
+    }
+
+    /// Builds a simplified hull with at most `max_vertices` vertices
+    ///
+    /// Quickhull always adds the point furthest outside the current hull,
+    /// so stopping early keeps the points that matter most. The result is
+    /// convex but may leave some input points outside. At least the four
+    /// points of the starting tetrahedron are always kept.
+    ///
+    /// # Arguments
+    /// - `points` - The point cloud
+    /// - `max_vertices` - The vertex budget
+    pub fn with_vertex_limit(points: &[Vector3D], max_vertices: usize) -> Result<Self, HullError> {
+        if points.len() < 4 {
+            return Err(HullError::TooFewPoints);
+        }
+        let tolerance = hull_tolerance(points);
+        let simplex = initial_simplex(points, tolerance)?;
+
+        // Orient the tetrahedron's faces away from its centroid
+        let centroid = simplex.iter().fold(Vector3D::default(), |c, &i| c + points[i]) * 0.25;
+        let mut faces: Vec<BuildFace> = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+            .into_iter()
+            .map(|[a, b, c]| {
+                let face = BuildFace::new(points, [simplex[a], simplex[b], simplex[c]]);
+                if face.distance(&centroid) > 0.0 {
+                    BuildFace::new(points, [simplex[a], simplex[c], simplex[b]])
+                } else {
+                    face
+                }
+            })
+            .collect();
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            register_edges(&mut edges, face, f);
+        }
+
+        let candidates: Vec<usize> = (0..points.len()).filter(|i| !simplex.contains(i)).collect();
+        assign_outside(points, &mut faces, &[0, 1, 2, 3], candidates, tolerance);
+        // Live faces that still have points outside them, dead faces are
+        // dropped as they are met so the scan stays proportional to the hull
+        let mut pending: Vec<usize> = (0..4).filter(|&f| faces[f].furthest.is_some()).collect();
+
+        let mut vertex_count = 4;
+        while vertex_count < max_vertices {
+            pending.retain(|&f| faces[f].alive);
+            // The furthest outside point over the whole hull, for the best
+            // simplification when a budget applies
+            let Some((start, eye)) = pending
+                .iter()
+                .filter_map(|&f| faces[f].furthest.map(|(eye, distance)| (f, eye, distance)))
+                .max_by(|a, b| a.2.total_cmp(&b.2))
+                .map(|(f, eye, _)| (f, eye))
+            else {
+                break;
+            };
+            let eye_point = points[eye];
+
+            // Flood out from the start face to find every face the eye sees
+            let mut visible = vec![start];
+            let mut horizon = Vec::new();
+            faces[start].alive = false;
+            let mut next = 0;
+            while next < visible.len() {
+                let face = visible[next];
+                next += 1;
+                let [a, b, c] = faces[face].indices;
+                for (u, v) in [(a, b), (b, c), (c, a)] {
+                    let neighbour = edges[&(v, u)];
+                    if !faces[neighbour].alive {
+                        if !visible.contains(&neighbour) {
+                            horizon.push((u, v));
+                        }
+                        continue;
+                    }
+                    if faces[neighbour].distance(&eye_point) > tolerance {
+                        faces[neighbour].alive = false;
+                        visible.push(neighbour);
+                    } else {
+                        horizon.push((u, v));
+                    }
+                }
+            }
+
+            let mut orphans = Vec::new();
+            for &face in &visible {
+                let [a, b, c] = faces[face].indices;
+                for edge in [(a, b), (b, c), (c, a)] {
+                    edges.remove(&edge);
+                }
+                orphans.append(&mut faces[face].outside);
+            }
+
+            // Stitch the horizon to the eye
+            let first_new = faces.len();
+            for (u, v) in horizon {
+                let face = BuildFace::new(points, [u, v, eye]);
+                register_edges(&mut edges, &face, faces.len());
+                faces.push(face);
+            }
+            let new_faces: Vec<usize> = (first_new..faces.len()).collect();
+            orphans.retain(|&p| p != eye);
+            assign_outside(points, &mut faces, &new_faces, orphans, tolerance);
+            pending.extend(new_faces.into_iter().filter(|&f| faces[f].furthest.is_some()));
+            vertex_count += 1;
+        }
+
+        Ok(Self::compact(points, &faces))
+    }
+
+    /// Keeps the live faces and only the points they use
+    fn compact(points: &[Vector3D], faces: &[BuildFace]) -> Self {
+        let mut remap: HashMap<usize, usize> = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut hull_faces = Vec::new();
+        for face in faces.iter().filter(|f| f.alive) {
+            let indices = face.indices.map(|i| {
+                *remap.entry(i).or_insert_with(|| {
+                    vertices.push(points[i]);
+                    vertices.len() - 1
+                })
+            });
+            hull_faces.push(HullFace { indices, plane: Plane::new(face.normal, -face.offset) });
+        }
+        Self { vertices, faces: hull_faces }
+    }
+
+    /// Returns true if `point` is inside the hull or on its surface
+    pub fn contains_point(&self, point: &Vector3D) -> bool {
+        let tolerance = hull_tolerance(&self.vertices);
+        self.faces.iter().all(|f| f.plane.signed_distance(point) <= tolerance)
+    }
+
+    /// Returns the volume enclosed by the hull, zero if it has no vertices
+    pub fn volume(&self) -> f64 {
+        let Some(&origin) = self.vertices.first() else {
+            return 0.0;
+        };
+        self.faces
+            .iter()
+            .map(|f| {
+                let [a, b, c] = f.indices.map(|i| self.vertices[i] - origin);
+                a.dot(&b.cross(&c))
+            })
+            .sum::<f64>()
+            / 6.0
+    }
+}
+
+impl SupportMap for ConvexHull {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        self.vertices[..].support(direction)
+    }
+}
+
+/// Distances below this count as zero, scaled to the size of the input
+fn hull_tolerance(points: &[Vector3D]) -> f64 {
+    let extent = points.iter().fold(0.0_f64, |m, p| m.max(p.x.abs()).max(p.y.abs()).max(p.z.abs()));
+    (EPSILON * extent).max(f64::MIN_POSITIVE)
+}
+
+/// Finds four points spanning a tetrahedron, or the reason none exist
+fn initial_simplex(points: &[Vector3D], tolerance: f64) -> Result<[usize; 4], HullError> {
+    // Most distant pair among the extreme points on each axis
+    let mut extremes = Vec::with_capacity(6);
+    for axis in 0..3 {
+        let by_axis = |a: &&Vector3D, b: &&Vector3D| a[axis].total_cmp(&b[axis]);
+        let (min, _) = points.iter().enumerate().min_by(|a, b| by_axis(&a.1, &b.1)).unwrap();
+        let (max, _) = points.iter().enumerate().max_by(|a, b| by_axis(&a.1, &b.1)).unwrap();
+        extremes.push(min);
+        extremes.push(max);
+    }
+    let mut pair = (extremes[0], extremes[1]);
+    let mut best = -1.0;
+    for &i in &extremes {
+        for &j in &extremes {
+            let d = (points[i] - points[j]).magnitude_squared();
+            if d > best {
+                best = d;
+                pair = (i, j);
+            }
+        }
+    }
+    let (a, b) = pair;
+    if best.sqrt() <= tolerance {
+        return Err(HullError::Coincident);
+    }
+
+    let line = (points[b] - points[a]).normalise();
+    let off_line = |p: &Vector3D| (*p - points[a]).cross(&line).magnitude();
+    let c = (0..points.len()).max_by(|&i, &j| off_line(&points[i]).total_cmp(&off_line(&points[j]))).unwrap();
+    if off_line(&points[c]) <= tolerance {
+        return Err(HullError::Collinear);
+    }
+
+    let normal = (points[b] - points[a]).cross(&(points[c] - points[a])).normalise();
+    let off_plane = |p: &Vector3D| (*p - points[a]).dot(&normal).abs();
+    let d = (0..points.len()).max_by(|&i, &j| off_plane(&points[i]).total_cmp(&off_plane(&points[j]))).unwrap();
+    if off_plane(&points[d]) <= tolerance {
+        return Err(HullError::Coplanar);
+    }
+    Ok([a, b, c, d])
+}
+
+fn register_edges(edges: &mut HashMap<(usize, usize), usize>, face: &BuildFace, index: usize) {
+    let [a, b, c] = face.indices;
+    for edge in [(a, b), (b, c), (c, a)] {
+        edges.insert(edge, index);
+    }
+}
+
+/// Gives each point to the face it is furthest outside, dropping points
+/// that are inside every candidate face
+fn assign_outside(points: &[Vector3D], faces: &mut [BuildFace], candidates: &[usize], unassigned: Vec<usize>, tolerance: f64) {
+    for p in unassigned {
+        let best = candidates
+            .iter()
+            .map(|&f| (f, faces[f].distance(&points[p])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((f, distance)) = best {
+            if distance > tolerance {
+                let face = &mut faces[f];
+                face.outside.push(p);
+                if face.furthest.is_none_or(|(_, furthest)| distance > furthest) {
+                    face.furthest = Some((p, distance));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::gjk_distance;
+    use crate::maths::spatial::testing::random_points;
+
+    /// Checks the hull is closed, convex and outward facing
+    fn assert_valid(hull: &ConvexHull) {
+        let mut edges = HashMap::new();
+        for face in &hull.faces {
+            let [a, b, c] = face.indices;
+            for edge in [(a, b), (b, c), (c, a)] {
+                assert!(edges.insert(edge, ()).is_none(), "edge used twice");
+            }
+            for v in &hull.vertices {
+                assert!(face.plane.signed_distance(v) < 1e-9);
+            }
+        }
+        for &(a, b) in edges.keys() {
+            assert!(edges.contains_key(&(b, a)), "hull is not closed");
+        }
+        // Euler's formula for a closed triangulated sphere
+        assert_eq!(hull.faces.len(), 2 * hull.vertices.len() - 4);
+        assert!(hull.volume() > 0.0);
+    }
+
+    #[test]
+    fn test_hull_cube_with_redundant_points() {
+        let mut points = Vec::new();
+        for corner in 0..8 {
+            let bit = |b: usize| if corner & (1 << b) != 0 { 1.0 } else { -1.0 };
+            points.push(Vector3D::new(bit(0), bit(1), bit(2)));
+        }
+        // Interior points, duplicates, and points on faces and edges
+        points.extend(random_points(50, 1.0, 7).into_iter().map(|p| p * 0.9));
+        points.extend(points.clone().into_iter().take(8));
+        points.push(Vector3D::new(1.0, 0.2, -0.3));
+        points.push(Vector3D::new(1.0, 1.0, 0.5));
+        points.push(Vector3D::new(1.0 + 1e-13, 0.0, 0.0));
+
+        let hull = ConvexHull::new(&points).unwrap();
+        assert_valid(&hull);
+        assert_eq!(hull.vertices.len(), 8);
+        assert!((hull.volume() - 8.0).abs() < 1e-9);
+        for p in &points {
+            assert!(hull.contains_point(p));
+        }
+        assert!(!hull.contains_point(&Vector3D::new(0.0, 0.0, 1.01)));
+        for face in &hull.faces {
+            let n = face.plane.normal;
+            assert!((n.x.abs() + n.y.abs() + n.z.abs() - 1.0).abs() < 1e-12, "face normals follow the cube axes");
+        }
+    }
+
+    #[test]
+    fn test_hull_random_sphere() {
+        let points: Vec<Vector3D> = random_points(400, 1.0, 3)
+            .into_iter()
+            .filter(|p| p.magnitude() > 0.1)
+            .map(|p| p.normalise() * 2.0)
+            .collect();
+        let hull = ConvexHull::new(&points).unwrap();
+        assert_valid(&hull);
+        // Every point on a sphere is extreme
+        assert_eq!(hull.vertices.len(), points.len());
+        for p in &points {
+            assert!(hull.contains_point(p));
+        }
+
+        let cube = random_points(300, 1.0, 11);
+        let hull = ConvexHull::new(&cube).unwrap();
+        assert_valid(&hull);
+        assert!(cube.iter().all(|p| hull.contains_point(p)));
+    }
+
+    #[test]
+    fn test_hull_vertex_budget() {
+        let points: Vec<Vector3D> = random_points(500, 1.0, 5).into_iter().map(|p| p.normalise()).collect();
+        let full = ConvexHull::new(&points).unwrap();
+        let simplified = ConvexHull::with_vertex_limit(&points, 24).unwrap();
+        assert_valid(&simplified);
+        assert_eq!(simplified.vertices.len(), 24);
+        assert!(simplified.volume() < full.volume());
+        // Greedy furthest-point refinement already covers most of the sphere
+        assert!(simplified.volume() > 0.6 * full.volume());
+        for v in &simplified.vertices {
+            assert!(points.contains(v));
+        }
+        assert_eq!(ConvexHull::with_vertex_limit(&points, 0).unwrap().vertices.len(), 4);
+    }
+
+    #[test]
+    fn test_hull_degenerate_inputs() {
+        let p = Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(ConvexHull::new(&[p, p, p]), Err(HullError::TooFewPoints));
+        assert_eq!(ConvexHull::new(&[p; 10]), Err(HullError::Coincident));
+
+        let line: Vec<Vector3D> = (0..10).map(|i| p + Vector3D::new(1.0, -1.0, 0.5) * i as f64).collect();
+        assert_eq!(ConvexHull::new(&line), Err(HullError::Collinear));
+
+        let flat: Vec<Vector3D> = random_points(30, 1.0, 1).into_iter().map(|q| Vector3D::new(q.x, q.y, 4.0)).collect();
+        assert_eq!(ConvexHull::new(&flat), Err(HullError::Coplanar));
+        assert_eq!(HullError::Coplanar.to_string(), "cannot build a convex hull: all points are coplanar");
+
+        // Nearly flat within tolerance is still flat
+        let mut nearly = flat.clone();
+        nearly.push(Vector3D::new(0.0, 0.0, 4.0 + 1e-14));
+        assert_eq!(ConvexHull::new(&nearly), Err(HullError::Coplanar));
+
+        // The fields are public, so an emptied hull must not panic
+        let empty = ConvexHull { vertices: Vec::new(), faces: Vec::new() };
+        assert_eq!(empty.volume(), 0.0);
+    }
+
+    #[test]
+    fn test_hull_support_map() {
+        let a = ConvexHull::new(&random_points(100, 1.0, 9)).unwrap();
+        let b: Vec<Vector3D> = random_points(100, 1.0, 10).into_iter().map(|p| p + Vector3D::new(5.0, 0.0, 0.0)).collect();
+        let hull_b = ConvexHull::new(&b).unwrap();
+        let via_hulls = gjk_distance(&a, &hull_b).unwrap().distance;
+        let via_points = gjk_distance(&a.vertices[..], &b[..]).unwrap().distance;
+        assert!((via_hulls - via_points).abs() < 1e-9);
+    }
+}
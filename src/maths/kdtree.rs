@@ -0,0 +1,236 @@
+use super::{spatial::NearestSet, Vector3D};
+
+/// Static KD-tree over a point cloud
+///
+/// The tree is stored implicitly: the point at the middle of each range
+/// splits it, the left half holding the smaller coordinates on the split
+/// axis. Ids are indices into the slice the tree was built from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdTree {
+    points: Vec<Vector3D>,
+    /// Point ids in tree order
+    order: Vec<usize>,
+    /// Split axis of the node at each position of `order`
+    axes: Vec<u8>,
+}
+
+impl KdTree {
+    /// Builds a balanced KdTree, splitting each range at the median of its
+    /// widest axis
+    ///
+    /// # Arguments
+    /// - `points` - The point cloud, copied into the tree
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{KdTree, Vector3D};
+    ///
+    /// let points: Vec<Vector3D> = (0..100).map(|i| Vector3D::new(i as f64, 0.0, 0.0)).collect();
+    /// let tree = KdTree::new(&points);
+    /// assert_eq!(tree.nearest(&Vector3D::new(41.75, 0.0, 0.0)), Some((42, 0.25)));
+    /// assert_eq!(tree.query_radius(&Vector3D::new(0.0, 0.0, 0.0), 1.5), vec![0, 1]);
+    /// ```
+    pub fn new(points: &[Vector3D]) -> Self {
+        let mut tree = Self {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            axes: vec![0; points.len()],
+        };
+        tree.build(0, points.len());
+        tree
+    }
+
+    /// Returns the number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the tree has no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the point with the given id
+    pub fn point(&self, id: usize) -> Option<Vector3D> {
+        self.points.get(id).copied()
+    }
+
+    fn build(&mut self, start: usize, end: usize) {
+        if end - start <= 1 {
+            return;
+        }
+        let (min, max) = self.order[start..end]
+            .iter()
+            .fold((self.points[self.order[start]], self.points[self.order[start]]), |(min, max), &i| {
+                (min.min(&self.points[i]), max.max(&self.points[i]))
+            });
+        let extents = max - min;
+        let axis = (0..3).max_by(|&a, &b| extents[a].total_cmp(&extents[b])).unwrap();
+
+        let mid = (start + end) / 2;
+        let points = &self.points;
+        self.order[start..end].select_nth_unstable_by(mid - start, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+        self.axes[mid] = axis as u8;
+        self.build(start, mid);
+        self.build(mid + 1, end);
+    }
+
+    /// Returns the closest point to `point` as (id, distance)
+    pub fn nearest(&self, point: &Vector3D) -> Option<(usize, f64)> {
+        self.k_nearest(point, 1).first().copied()
+    }
+
+    /// Returns up to `k` closest points as (id, distance), nearest first
+    pub fn k_nearest(&self, point: &Vector3D, k: usize) -> Vec<(usize, f64)> {
+        self.approximate_k_nearest(point, k, 0.0)
+    }
+
+    /// Returns a point no further than `1 + epsilon` times the true nearest
+    /// distance, as (id, distance)
+    ///
+    /// Larger `epsilon` prunes more of the tree, which pays off on big or
+    /// clustered clouds where an exact answer is not needed.
+    ///
+    /// # Arguments
+    /// - `point` - The query point
+    /// - `epsilon` - The allowed relative error, zero for an exact search
+    pub fn approximate_nearest(&self, point: &Vector3D, epsilon: f64) -> Option<(usize, f64)> {
+        self.approximate_k_nearest(point, 1, epsilon).first().copied()
+    }
+
+    /// Returns up to `k` points as (id, distance), nearest first, where the
+    /// i-th is no further than `1 + epsilon` times the true i-th nearest
+    ///
+    /// # Arguments
+    /// - `point` - The query point
+    /// - `k` - The number of points to find
+    /// - `epsilon` - The allowed relative error, zero for an exact search
+    pub fn approximate_k_nearest(&self, point: &Vector3D, k: usize, epsilon: f64) -> Vec<(usize, f64)> {
+        let mut nearest = NearestSet::new(k);
+        if k > 0 {
+            let shrink = 1.0 / ((1.0 + epsilon.max(0.0)) * (1.0 + epsilon.max(0.0)));
+            self.search_nearest(0, self.len(), point, shrink, &mut nearest);
+        }
+        nearest.into_sorted()
+    }
+
+    fn search_nearest(&self, start: usize, end: usize, point: &Vector3D, shrink: f64, nearest: &mut NearestSet) {
+        if start >= end {
+            return;
+        }
+        let mid = (start + end) / 2;
+        let id = self.order[mid];
+        nearest.offer(id, (self.points[id] - *point).magnitude_squared());
+        if end - start == 1 {
+            return;
+        }
+
+        let axis = self.axes[mid] as usize;
+        let offset = point[axis] - self.points[id][axis];
+        let (near, far) = if offset < 0.0 { ((start, mid), (mid + 1, end)) } else { ((mid + 1, end), (start, mid)) };
+        self.search_nearest(near.0, near.1, point, shrink, nearest);
+        // The far side is at least `offset` away; with an epsilon it is only
+        // searched if it could improve on the bound by more than that factor
+        if offset * offset < nearest.bound() * shrink {
+            self.search_nearest(far.0, far.1, point, shrink, nearest);
+        }
+    }
+
+    /// Returns the ids of every point within `radius` of `center`, inclusive
+    pub fn query_radius(&self, center: &Vector3D, radius: f64) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = vec![(0, self.len())];
+        let radius_squared = radius * radius;
+        while let Some((start, end)) = stack.pop() {
+            if start >= end {
+                continue;
+            }
+            let mid = (start + end) / 2;
+            let id = self.order[mid];
+            if (self.points[id] - *center).magnitude_squared() <= radius_squared {
+                found.push(id);
+            }
+            let axis = self.axes[mid] as usize;
+            let offset = center[axis] - self.points[id][axis];
+            if offset <= radius {
+                stack.push((start, mid));
+            }
+            if offset >= -radius {
+                stack.push((mid + 1, end));
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::random_points;
+
+    fn brute_force(points: &[Vector3D], query: &Vector3D) -> Vec<f64> {
+        let mut distances: Vec<f64> = points.iter().map(|p| (*p - *query).magnitude()).collect();
+        distances.sort_by(f64::total_cmp);
+        distances
+    }
+
+    #[test]
+    fn test_kdtree_exact_queries() {
+        let mut clouds = vec![random_points(2000, 10.0, 1), random_points(7, 10.0, 2)];
+        // Clustered and duplicated points, and a flat cloud
+        let mut clustered: Vec<Vector3D> = random_points(500, 0.01, 3).into_iter().map(|p| p + Vector3D::new(5.0, 5.0, 5.0)).collect();
+        clustered.extend(clustered.clone());
+        clustered.extend(random_points(100, 10.0, 4));
+        clouds.push(clustered);
+        clouds.push(random_points(300, 10.0, 5).into_iter().map(|p| Vector3D::new(p.x, p.y, 1.0)).collect());
+
+        for points in &clouds {
+            let tree = KdTree::new(points);
+            assert_eq!(tree.len(), points.len());
+            for query in random_points(50, 12.0, 6) {
+                let expected = brute_force(points, &query);
+                let (id, distance) = tree.nearest(&query).unwrap();
+                assert_eq!(distance, expected[0]);
+                assert_eq!((points[id] - query).magnitude(), distance);
+
+                let k = 10.min(points.len());
+                let nearest: Vec<f64> = tree.k_nearest(&query, 10).into_iter().map(|n| n.1).collect();
+                assert_eq!(nearest, expected[..k]);
+
+                for radius in [0.0, 0.5, 3.0, 30.0] {
+                    let expected: Vec<usize> = (0..points.len()).filter(|&i| (points[i] - query).magnitude() <= radius).collect();
+                    assert_eq!(tree.query_radius(&query, radius), expected);
+                }
+            }
+        }
+
+        let empty = KdTree::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&Vector3D::default()), None);
+        assert!(empty.query_radius(&Vector3D::default(), 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_kdtree_approximate_bound() {
+        let points = random_points(5000, 10.0, 7);
+        let tree = KdTree::new(&points);
+        for epsilon in [0.0, 0.1, 0.5, 2.0] {
+            for query in random_points(100, 12.0, 8) {
+                let expected = brute_force(&points, &query);
+                let (id, distance) = tree.approximate_nearest(&query, epsilon).unwrap();
+                assert_eq!((points[id] - query).magnitude(), distance);
+                assert!(distance <= expected[0] * (1.0 + epsilon));
+                if epsilon == 0.0 {
+                    assert_eq!(distance, expected[0]);
+                }
+
+                let found = tree.approximate_k_nearest(&query, 5, epsilon);
+                assert_eq!(found.len(), 5);
+                for (i, (_, distance)) in found.into_iter().enumerate() {
+                    assert!(distance <= expected[i] * (1.0 + epsilon));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use super::{Matrix3D, Vector3D};
+
+/// Linear interpolation between two values of the same type
+///
+/// Used wherever a value has to be blended along a parameter, such as
+/// per-vertex attributes during clipping and tweens. Tuples and arrays of
+/// interpolable values interpolate element by element, so vertex layouts
+/// can be built from them without a new impl.
+pub trait Lerp {
+    /// Returns the value `t` of the way from `self` to `other`
+    ///
+    /// `t` is not clamped, values outside 0..1 extrapolate.
+    ///
+    /// # Arguments
+    /// - `other` - The value at `t == 1`
+    /// - `t` - The interpolation parameter
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Lerp, Vector3D};
+    ///
+    /// assert_eq!(2.0.lerp(&4.0, 0.25), 2.5);
+    /// let v = Vector3D::new(0.0, 2.0, 4.0).lerp(&Vector3D::new(4.0, 2.0, 0.0), 0.5);
+    /// assert_eq!(v, Vector3D::new(2.0, 2.0, 2.0));
+    /// assert_eq!((1.0, [0.0, 10.0]).lerp(&(3.0, [10.0, 0.0]), 0.5), (2.0, [5.0, 5.0]));
+    /// ```
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        // Exact at both ends, unlike `self + (other - self) * t`
+        self * (1.0 - t) + other * t
+    }
+}
+
+impl Lerp for Vector3D {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Vector3D::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t), self.z.lerp(&other.z, t))
+    }
+}
+
+/// Component-wise, so the result is generally not a rotation even between
+/// two rotations, use `Quaternion::slerp` for those
+impl Lerp for Matrix3D {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Matrix3D::new_vectors(
+            self.column(0).lerp(&other.column(0), t),
+            self.column(1).lerp(&other.column(1), t),
+            self.column(2).lerp(&other.column(2), t),
+        )
+    }
+}
+
+impl<T: Lerp, const N: usize> Lerp for [T; N] {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| self[i].lerp(&other[i], t))
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        (self.0.lerp(&other.0, t), self.1.lerp(&other.1, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp, C: Lerp> Lerp for (A, B, C) {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        (self.0.lerp(&other.0, t), self.1.lerp(&other.1, t), self.2.lerp(&other.2, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp_endpoints() {
+        let (a, b) = (0.1, 0.7);
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+        assert_eq!(1.0.lerp(&3.0, 1.5), 4.0);
+
+        let a = Vector3D::new(0.1, -0.3, 1e9);
+        let b = Vector3D::new(-7.0, 0.2, 1e-9);
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+        let triple = (a, [1.0, 2.0], 5.0).lerp(&(b, [3.0, 6.0], 1.0), 0.5);
+        assert_eq!(triple.1, [2.0, 4.0]);
+        assert_eq!(triple.2, 3.0);
+
+        let a = Matrix3D::identity();
+        let b = Matrix3D::new(3.0, 2.0, 0.0, -4.0, 1.0, 0.0, 0.0, 0.0, 5.0);
+        assert!(a.lerp(&b, 0.0) == a);
+        assert!(a.lerp(&b, 1.0) == b);
+        assert!(a.lerp(&b, 0.5) == Matrix3D::new(2.0, 1.0, 0.0, -2.0, 1.0, 0.0, 0.0, 0.0, 3.0));
+    }
+}
@@ -0,0 +1,355 @@
+use super::{Matrix3D, Plane, Vector3D, EPSILON};
+
+/// Infinite line in Plücker coordinates
+///
+/// Stored as a direction `v` and a moment `m = p x v` for any point `p` on the
+/// line. The moment is the same whichever point is picked, so two lines can be
+/// compared without choosing points on them. Directions are not required to
+/// be unit length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub direction: Vector3D,
+    pub moment: Vector3D,
+}
+
+impl Line {
+    /// Creates a new Line from raw Plücker coordinates
+    ///
+    /// # Arguments
+    /// - `direction` - The direction of the line
+    /// - `moment` - The moment `p x direction` for a point `p` on the line
+    pub fn new(direction: Vector3D, moment: Vector3D) -> Self {
+        Self { direction, moment }
+    }
+
+    /// Creates a new Line through `a` and `b`, or `None` if the points coincide
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let line = Line::from_points(Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 1.0)).unwrap();
+    /// assert_eq!(line.direction, Vector3D::new(0.0, 0.0, 1.0));
+    /// assert_eq!(line.moment, Vector3D::new(0.0, -1.0, 0.0));
+    /// assert!(Line::from_points(Vector3D::default(), Vector3D::default()).is_none());
+    /// ```
+    pub fn from_points(a: Vector3D, b: Vector3D) -> Option<Self> {
+        Self::from_point_direction(a, b - a)
+    }
+
+    /// Creates a new Line through `point` along `direction`, or `None` if the
+    /// direction is zero
+    pub fn from_point_direction(point: Vector3D, direction: Vector3D) -> Option<Self> {
+        if direction.magnitude_squared() <= EPSILON * EPSILON {
+            return None;
+        }
+        Some(Self {
+            direction,
+            moment: point.cross(&direction),
+        })
+    }
+
+    /// Creates the Line where two planes meet, or `None` if they are parallel
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Plane, Vector3D};
+    ///
+    /// let a = Plane::new(Vector3D::new(1.0, 0.0, 0.0), -1.0);
+    /// let b = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -2.0);
+    /// let line = Line::from_planes(&a, &b).unwrap();
+    /// assert_eq!(line.point(), Vector3D::new(1.0, 2.0, 0.0));
+    /// ```
+    pub fn from_planes(a: &Plane, b: &Plane) -> Option<Self> {
+        let direction = a.normal.cross(&b.normal);
+        if direction.magnitude_squared() <= EPSILON * EPSILON {
+            return None;
+        }
+        Some(Self {
+            direction,
+            moment: b.normal * a.distance - a.normal * b.distance,
+        })
+    }
+
+    /// Returns the line rescaled so that its direction is unit length
+    pub fn normalised(&self) -> Self {
+        let magnitude = self.direction.magnitude();
+        Self {
+            direction: self.direction / magnitude,
+            moment: self.moment / magnitude,
+        }
+    }
+
+    /// Returns the point on the line closest to the origin
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let line = Line::from_point_direction(Vector3D::new(3.0, 1.0, 5.0), Vector3D::new(0.0, 0.0, 2.0)).unwrap();
+    /// assert_eq!(line.point(), Vector3D::new(3.0, 1.0, 0.0));
+    /// ```
+    pub fn point(&self) -> Vector3D {
+        self.direction.cross(&self.moment) / self.direction.magnitude_squared()
+    }
+
+    /// Returns the point on the line closest to `point`
+    pub fn closest_point(&self, point: &Vector3D) -> Vector3D {
+        let origin = self.point();
+        let t = (*point - origin).dot(&self.direction) / self.direction.magnitude_squared();
+        origin + self.direction * t
+    }
+
+    /// Returns the distance from the line to `point`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let line = Line::from_point_direction(Vector3D::default(), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+    /// assert_eq!(line.distance_to_point(&Vector3D::new(3.0, 4.0, 9.0)), 5.0);
+    /// ```
+    pub fn distance_to_point(&self, point: &Vector3D) -> f64 {
+        // The moment of a parallel line through `point`, minus ours, scales with the offset
+        (self.moment - point.cross(&self.direction)).magnitude() / self.direction.magnitude()
+    }
+
+    /// Returns the reciprocal (permuted inner) product of two lines
+    ///
+    /// This is zero exactly when the lines are coplanar, and its sign gives
+    /// the handedness of the twist between skew lines.
+    pub fn reciprocal_product(&self, other: &Line) -> f64 {
+        self.direction.dot(&other.moment) + other.direction.dot(&self.moment)
+    }
+
+    /// Returns true if the two lines lie in a common plane, meaning they
+    /// either intersect or are parallel
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let a = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+    /// let b = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0)).unwrap();
+    /// let c = Line::from_points(Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(0.0, 1.0, 1.0)).unwrap();
+    /// assert!(a.is_coplanar(&b));
+    /// assert!(!a.is_coplanar(&c));
+    /// ```
+    pub fn is_coplanar(&self, other: &Line) -> bool {
+        let a = self.normalised();
+        let b = other.normalised();
+        let scale = 1.0 + a.moment.magnitude().max(b.moment.magnitude());
+        a.reciprocal_product(&b).abs() <= EPSILON * scale
+    }
+
+    /// Returns true if the two lines have parallel (or anti-parallel) directions
+    pub fn is_parallel(&self, other: &Line) -> bool {
+        let cross = self.direction.cross(&other.direction);
+        cross.magnitude_squared()
+            <= EPSILON * EPSILON * self.direction.magnitude_squared() * other.direction.magnitude_squared()
+    }
+
+    /// Returns the shortest distance between two lines
+    ///
+    /// Parallel lines fall back to the distance between the two lines'
+    /// closest points to the origin, so the result is always finite.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let a = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+    /// let b = Line::from_points(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(0.0, 1.0, 2.0)).unwrap();
+    /// assert_eq!(a.distance(&b), 2.0);
+    /// ```
+    pub fn distance(&self, other: &Line) -> f64 {
+        if self.is_parallel(other) {
+            return self.distance_to_point(&other.point());
+        }
+        let cross = self.direction.cross(&other.direction);
+        self.reciprocal_product(other).abs() / cross.magnitude()
+    }
+
+    /// Returns the closest pair of points `(on self, on other)` between two lines
+    ///
+    /// For parallel lines every point pairs with one on the other line, so
+    /// this picks the point on `self` closest to the origin.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Vector3D};
+    ///
+    /// let a = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+    /// let b = Line::from_points(Vector3D::new(3.0, 0.0, 2.0), Vector3D::new(3.0, 1.0, 2.0)).unwrap();
+    /// let (pa, pb) = a.closest_points(&b);
+    /// assert_eq!(pa, Vector3D::new(3.0, 0.0, 0.0));
+    /// assert_eq!(pb, Vector3D::new(3.0, 0.0, 2.0));
+    /// ```
+    pub fn closest_points(&self, other: &Line) -> (Vector3D, Vector3D) {
+        let p1 = self.point();
+        if self.is_parallel(other) {
+            return (p1, other.closest_point(&p1));
+        }
+        let p2 = other.point();
+        let (d1, d2) = (self.direction, other.direction);
+        let w = p1 - p2;
+        let a = d1.dot(&d1);
+        let b = d1.dot(&d2);
+        let c = d2.dot(&d2);
+        let d = d1.dot(&w);
+        let e = d2.dot(&w);
+        let denominator = a * c - b * b;
+        let s = (b * e - c * d) / denominator;
+        let t = (a * e - b * d) / denominator;
+        (p1 + d1 * s, p2 + d2 * t)
+    }
+
+    /// Returns the point where the line crosses `plane`, or `None` if the line
+    /// is parallel to the plane (including lying in it)
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Plane, Vector3D};
+    ///
+    /// let line = Line::from_points(Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 1.0)).unwrap();
+    /// let plane = Plane::new(Vector3D::new(0.0, 0.0, 1.0), -5.0);
+    /// assert_eq!(line.intersect_plane(&plane), Some(Vector3D::new(1.0, 0.0, 5.0)));
+    /// ```
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Vector3D> {
+        let denominator = plane.normal.dot(&self.direction);
+        if denominator.abs() <= EPSILON * self.direction.magnitude() * plane.normal.magnitude() {
+            return None;
+        }
+        Some((self.moment.cross(&plane.normal) + self.direction * plane.distance) / -denominator)
+    }
+
+    /// Returns the line transformed by `p -> rotation * p + translation`
+    ///
+    /// The moment is carried through the cofactor matrix (the transposed
+    /// adjugate), which equals `rotation` for a pure rotation and stays
+    /// correct if the matrix also scales.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Line, Matrix3D, Vector3D};
+    ///
+    /// let line = Line::from_points(Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 1.0)).unwrap();
+    /// let moved = line.transform(&Matrix3D::identity(), &Vector3D::new(0.0, 2.0, 0.0));
+    /// assert_eq!(moved.point(), Vector3D::new(1.0, 2.0, 0.0));
+    /// ```
+    pub fn transform(&self, rotation: &Matrix3D, translation: &Vector3D) -> Self {
+        let direction = *rotation * self.direction;
+        let moment = rotation.adjugate().transpose() * self.moment + translation.cross(&direction);
+        Self { direction, moment }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!((a - b).magnitude() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_line_construction() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let line = Line::from_points(a, b).unwrap();
+        assert!(line.distance_to_point(&a) < 1e-12);
+        assert!(line.distance_to_point(&b) < 1e-12);
+        assert!(line.point().dot(&line.direction).abs() < 1e-12);
+
+        // Moment is independent of the point chosen
+        let other = Line::from_point_direction(b, line.direction).unwrap();
+        assert_close(other.moment, line.moment);
+
+        assert!(Line::from_point_direction(a, Vector3D::default()).is_none());
+    }
+
+    #[test]
+    fn test_line_distance_and_closest_points() {
+        // Skew lines
+        let a = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 0.0)).unwrap();
+        let b = Line::from_points(Vector3D::new(0.0, 0.0, 3.0), Vector3D::new(1.0, -1.0, 3.0)).unwrap();
+        assert!((a.distance(&b) - 3.0).abs() < 1e-12);
+        let (pa, pb) = a.closest_points(&b);
+        assert!(((pa - pb).magnitude() - 3.0).abs() < 1e-12);
+        assert!(a.distance_to_point(&pa) < 1e-12);
+        assert!(b.distance_to_point(&pb) < 1e-12);
+        // The connecting segment is perpendicular to both lines
+        assert!((pb - pa).dot(&a.direction).abs() < 1e-12);
+        assert!((pb - pa).dot(&b.direction).abs() < 1e-12);
+
+        // Intersecting lines
+        let c = Line::from_points(Vector3D::new(2.0, 2.0, 0.0), Vector3D::new(2.0, 2.0, 1.0)).unwrap();
+        assert!(a.distance(&c) < 1e-12);
+        let (pa, pc) = a.closest_points(&c);
+        assert_close(pa, Vector3D::new(2.0, 2.0, 0.0));
+        assert_close(pc, Vector3D::new(2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_line_parallel() {
+        let a = Line::from_points(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        let b = Line::from_points(Vector3D::new(3.0, 4.0, 7.0), Vector3D::new(3.0, 4.0, -2.0)).unwrap();
+        assert!(a.is_parallel(&b));
+        assert!(a.is_coplanar(&b));
+        assert!((a.distance(&b) - 5.0).abs() < 1e-12);
+        let (pa, pb) = a.closest_points(&b);
+        assert!(((pa - pb).magnitude() - 5.0).abs() < 1e-12);
+        assert!(pa.x.is_finite() && pb.x.is_finite());
+
+        // The same line is at distance zero from itself
+        assert_eq!(a.distance(&a), 0.0);
+    }
+
+    #[test]
+    fn test_line_plane_intersection() {
+        let plane = Plane::from_point_normal(Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(1.0, 2.0, 3.0));
+        let line = Line::from_points(Vector3D::new(-2.0, 5.0, 0.5), Vector3D::new(4.0, -1.0, 2.0)).unwrap();
+        let p = line.intersect_plane(&plane).unwrap();
+        assert!(plane.signed_distance(&p).abs() < 1e-12);
+        assert!(line.distance_to_point(&p) < 1e-12);
+
+        // Parallel to the plane, either off it or lying in it
+        let along = plane.normal.cross(&Vector3D::new(0.0, 0.0, 1.0));
+        let off = Line::from_point_direction(Vector3D::new(9.0, 9.0, 9.0), along).unwrap();
+        let on = Line::from_point_direction(plane.project_point(&Vector3D::default()), along).unwrap();
+        assert!(off.intersect_plane(&plane).is_none());
+        assert!(on.intersect_plane(&plane).is_none());
+
+        // Round trip through the two-plane constructor
+        let other = Plane::from_point_normal(Vector3D::new(0.0, 2.0, 0.0), Vector3D::new(0.0, 1.0, 0.0));
+        let meet = Line::from_planes(&plane, &other).unwrap();
+        let q = meet.point();
+        assert!(plane.signed_distance(&q).abs() < 1e-12);
+        assert!(other.signed_distance(&q).abs() < 1e-12);
+        assert!(Line::from_planes(&plane, &plane).is_none());
+    }
+
+    #[test]
+    fn test_line_transform() {
+        let (s, c) = (0.6, 0.8);
+        let rotation = Matrix3D::new(c, -s, 0.0,
+                                     s, c, 0.0,
+                                     0.0, 0.0, 1.0);
+        let translation = Vector3D::new(1.0, -2.0, 0.5);
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-1.0, 0.0, 2.0);
+        let line = Line::from_points(a, b).unwrap();
+        let moved = line.transform(&rotation, &translation);
+        let expected = Line::from_points(rotation * a + translation, rotation * b + translation).unwrap();
+        assert_close(moved.direction, expected.direction);
+        assert_close(moved.moment, expected.moment);
+
+        // Scaling is handled by the cofactor matrix
+        let scale = Matrix3D::new(2.0, 0.0, 0.0,
+                                  0.0, 3.0, 0.0,
+                                  0.0, 0.0, 0.5);
+        let scaled = line.transform(&scale, &translation);
+        assert!(scaled.distance_to_point(&(scale * a + translation)) < 1e-12);
+        assert!(scaled.distance_to_point(&(scale * b + translation)) < 1e-12);
+    }
+}
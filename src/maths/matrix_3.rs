@@ -1,4 +1,4 @@
-use std::{fmt::{Debug, Formatter, self}, ops::{IndexMut, Index, Mul}};
+use std::{fmt::{Debug, Formatter, self}, ops::{IndexMut, Index, Mul, Add, Sub}};
 
 use super::Vector3D;
 
@@ -30,9 +30,9 @@ impl Default for Matrix3D {
 impl Debug for Matrix3D {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         //TODO: Make this a dynamic utility function (AlexC / victoryforphil)
-        write!(f, " Row: \t \t Col (mem):\n\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}]  \n", self[(0,0)], self[(0,1)], self[(0,2)], self.n[0][0], self.n[0][1],  self.n[0][2])?;
-        write!(f, "\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}] \n", self[(1,0)], self[(1,1)], self[(1,2)], self.n[1][0], self.n[1][1],  self.n[1][2])?;
-        write!(f, "\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}] \n", self[(2,0)], self[(2,1)], self[(2,2)], self.n[2][0], self.n[0][1],  self.n[2][2])?;
+        writeln!(f, " Row: \t \t Col (mem):\n\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}]  ", self[(0,0)], self[(0,1)], self[(0,2)], self.n[0][0], self.n[0][1],  self.n[0][2])?;
+        writeln!(f, "\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}] ", self[(1,0)], self[(1,1)], self[(1,2)], self.n[1][0], self.n[1][1],  self.n[1][2])?;
+        writeln!(f, "\t [{:?} {:?} {:?}] \t [{:?} {:?} {:?}] ", self[(2,0)], self[(2,1)], self[(2,2)], self.n[2][0], self.n[0][1],  self.n[2][2])?;
 
         Ok(())
         
@@ -98,9 +98,63 @@ impl Mul<Vector3D> for Matrix3D {
     }
 }
 
+/// Scalar multiplication
+///
+/// # Example
+/// ```
+/// use game_maths::maths::Matrix3D;
+///
+/// let m = Matrix3D::identity() * 2.0;
+/// assert_eq!(m[(1,1)], 2.0);
+/// ```
+impl Mul<f64> for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, s: f64) -> Self::Output {
+        let mut m = self;
+        for col in m.n.iter_mut() {
+            for v in col.iter_mut() {
+                *v *= s;
+            }
+        }
+        m
+    }
+}
+
+/// Component-wise matrix addition
+impl Add for Matrix3D {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut m = self;
+        for j in 0..3 {
+            for i in 0..3 {
+                m.n[j][i] += rhs.n[j][i];
+            }
+        }
+        m
+    }
+}
+
+/// Component-wise matrix subtraction
+impl Sub for Matrix3D {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut m = self;
+        for j in 0..3 {
+            for i in 0..3 {
+                m.n[j][i] -= rhs.n[j][i];
+            }
+        }
+        m
+    }
+}
+
 
 
 impl Matrix3D {
+    #[allow(clippy::too_many_arguments)]
     pub fn new( n00:f64,n01:f64,n02:f64,
                 n10:f64,n11:f64,n12:f64,
                 n20:f64,n21:f64,n22:f64) -> Self {
@@ -160,9 +214,205 @@ impl Matrix3D {
     /// assert_eq!(m[(2,2)], 8.0);
     /// ```
     pub fn index_test() -> Self{
-        return Self::new(0.0,1.0,2.0,
-                         3.0,4.0,5.0,
-                         6.0,7.0,8.0);
+        Self::new(0.0,1.0,2.0,
+                  3.0,4.0,5.0,
+                  6.0,7.0,8.0)
+    }
+
+    /// Returns the 3x3 identity matrix
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Vector3D};
+    ///
+    /// let v = Vector3D::new(1.0, 2.0, 3.0);
+    /// assert_eq!(Matrix3D::identity() * v, v);
+    /// ```
+    pub fn identity() -> Self {
+        Self::new(1.0,0.0,0.0,
+                  0.0,1.0,0.0,
+                  0.0,0.0,1.0)
+    }
+
+    /// Returns column `j` as a Vector3D
+    pub fn column(&self, j: usize) -> Vector3D {
+        Vector3D::from_array(self.n[j])
+    }
+
+    /// Returns row `i` as a Vector3D
+    pub fn row(&self, i: usize) -> Vector3D {
+        Vector3D::new(self[(i,0)], self[(i,1)], self[(i,2)])
+    }
+
+    /// Returns the transpose of the matrix
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Matrix3D;
+    ///
+    /// let m = Matrix3D::index_test().transpose();
+    /// assert_eq!(m[(0,1)], 3.0);
+    /// assert_eq!(m[(1,0)], 1.0);
+    /// ```
+    pub fn transpose(&self) -> Self {
+        let mut m = Self::default();
+        for i in 0..3 {
+            for j in 0..3 {
+                m[(i,j)] = self[(j,i)];
+            }
+        }
+        m
+    }
+
+    /// Returns the determinant of the matrix
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Matrix3D;
+    ///
+    /// assert_eq!(Matrix3D::identity().determinant(), 1.0);
+    /// assert_eq!(Matrix3D::index_test().determinant(), 0.0);
+    /// ```
+    pub fn determinant(&self) -> f64 {
+        // Scalar triple product of the columns: a . (b x c)
+        self.column(0).dot(&self.column(1).cross(&self.column(2)))
+    }
+
+    /// Returns the adjugate (transpose of the cofactor matrix)
+    ///
+    /// `m * m.adjugate() == identity * m.determinant()`, and unlike the
+    /// inverse it is defined for singular matrices too.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Matrix3D;
+    ///
+    /// let m = Matrix3D::new(2.0,0.0,0.0,
+    ///                       0.0,3.0,0.0,
+    ///                       0.0,0.0,4.0);
+    /// assert_eq!(m.adjugate(), Matrix3D::new(12.0,0.0,0.0,
+    ///                                        0.0,8.0,0.0,
+    ///                                        0.0,0.0,6.0));
+    /// ```
+    pub fn adjugate(&self) -> Self {
+        // The rows of the adjugate are the cross products of column pairs
+        let (a, b, c) = (self.column(0), self.column(1), self.column(2));
+        let r0 = b.cross(&c);
+        let r1 = c.cross(&a);
+        let r2 = a.cross(&b);
+        Self::new(r0.x,r0.y,r0.z,
+                  r1.x,r1.y,r1.z,
+                  r2.x,r2.y,r2.z)
+    }
+
+    /// Returns the inverse of the matrix, or `None` if it is singular
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Matrix3D;
+    ///
+    /// let m = Matrix3D::new(2.0,0.0,0.0,
+    ///                       0.0,4.0,0.0,
+    ///                       0.0,0.0,8.0);
+    /// assert_eq!(m.inverse().unwrap()[(1,1)], 0.25);
+    /// assert!(Matrix3D::index_test().inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= f64::EPSILON * self.max_abs().powi(3) || !det.is_finite() {
+            return None;
+        }
+        Some(self.adjugate() * (1.0 / det))
+    }
+
+    /// Solves `m * x = b` for `x`, or returns `None` if the matrix is singular
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Vector3D};
+    ///
+    /// let m = Matrix3D::new(2.0,0.0,0.0,
+    ///                       0.0,4.0,0.0,
+    ///                       0.0,0.0,8.0);
+    /// let x = m.solve(&Vector3D::new(2.0, 2.0, 2.0)).unwrap();
+    /// assert_eq!(x, Vector3D::new(1.0, 0.5, 0.25));
+    /// ```
+    pub fn solve(&self, b: &Vector3D) -> Option<Vector3D> {
+        self.inverse().map(|inv| inv * *b)
+    }
+
+    /// Returns the eigenvalues and eigenvectors of a symmetric matrix
+    ///
+    /// Uses cyclic Jacobi rotations. The eigenvalues are sorted from largest
+    /// to smallest and column `i` of the returned matrix is the unit
+    /// eigenvector for eigenvalue `i`. The columns form a right-handed
+    /// rotation. Only the upper triangle of the matrix is read.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Vector3D};
+    ///
+    /// let m = Matrix3D::new(2.0,0.0,0.0,
+    ///                       0.0,5.0,0.0,
+    ///                       0.0,0.0,3.0);
+    /// let (values, vectors) = m.symmetric_eigen();
+    /// assert_eq!(values, Vector3D::new(5.0, 3.0, 2.0));
+    /// assert_eq!(vectors.column(0), Vector3D::new(0.0, 1.0, 0.0));
+    /// ```
+    pub fn symmetric_eigen(&self) -> (Vector3D, Self) {
+        let mut a = [[0.0; 3]; 3];
+        for (i, row) in a.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = if i <= j { self[(i,j)] } else { self[(j,i)] };
+            }
+        }
+        let mut v = Self::identity();
+
+        for _ in 0..32 {
+            let off = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+            if off <= f64::EPSILON * (a[0][0].abs() + a[1][1].abs() + a[2][2].abs()) || off == 0.0 {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                // Rotation angle that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                for k in 0..3 {
+                    let (vkp, vkq) = (v[(k,p)], v[(k,q)]);
+                    v[(k,p)] = c * vkp - s * vkq;
+                    v[(k,q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+        let values = Vector3D::new(a[order[0]][order[0]], a[order[1]][order[1]], a[order[2]][order[2]]);
+        let mut vectors = Self::new_vectors(v.column(order[0]), v.column(order[1]), v.column(order[2]));
+        if vectors.determinant() < 0.0 {
+            vectors.n[2] = (-vectors.column(2)).into();
+        }
+        (values, vectors)
+    }
+
+    /// Returns the largest absolute element, used to scale tolerances
+    fn max_abs(&self) -> f64 {
+        self.n.iter().flatten().fold(0.0, |m: f64, v| m.max(v.abs()))
     }
 }
 #[cfg(test)]
@@ -238,7 +488,7 @@ mod tests {
 
 
         let m = Matrix3D::index_test();
-        let m2 = m.clone();
+        let m2 = m;
 
         let m3 = m * m2;
 
@@ -265,4 +515,57 @@ mod tests {
         assert_eq!(m4.z, 44.0);
         
     }
+
+    #[test]
+    fn test_matrix_3_inverse(){
+        let m = Matrix3D::new(2.0,-1.0,0.0,
+                              1.0,3.0,-2.0,
+                              0.0,5.0,4.0);
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.determinant(), m.transpose().determinant());
+
+        // m * adj(m) = det(m) * I
+        let det = m.determinant();
+        assert_eq!(m * m.adjugate(), Matrix3D::identity() * det);
+
+        let inv = m.inverse().unwrap();
+        let product = m * inv;
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((product[(i,j)] - expected).abs() < 1e-12);
+            }
+        }
+
+        let b = Vector3D::new(1.0,-2.0,3.0);
+        let x = m.solve(&b).unwrap();
+        assert!(((m * x) - b).magnitude() < 1e-12);
+
+        assert!(Matrix3D::default().inverse().is_none());
+        assert!(Matrix3D::index_test().solve(&b).is_none());
+        assert_eq!(m.row(1), Vector3D::new(1.0,3.0,-2.0));
+        assert_eq!(m.column(1), Vector3D::new(-1.0,3.0,5.0));
+        assert_eq!(m - m, Matrix3D::default());
+        assert_eq!(m + m, m * 2.0);
+    }
+
+    #[test]
+    fn test_matrix_3_symmetric_eigen(){
+        let m = Matrix3D::new(4.0,1.0,-2.0,
+                              1.0,2.0,0.5,
+                              -2.0,0.5,3.0);
+        let (values, vectors) = m.symmetric_eigen();
+        assert!(values.x >= values.y && values.y >= values.z);
+        assert!((vectors.determinant() - 1.0).abs() < 1e-12);
+        for i in 0..3 {
+            let v = vectors.column(i);
+            assert!((v.magnitude() - 1.0).abs() < 1e-12);
+            assert!((m * v - v * values[i]).magnitude() < 1e-10);
+        }
+
+        // Repeated eigenvalues still give an orthonormal frame
+        let (values, vectors) = Matrix3D::identity().symmetric_eigen();
+        assert_eq!(values, Vector3D::new(1.0,1.0,1.0));
+        assert_eq!(vectors, Matrix3D::identity());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,268 @@
+use std::{fmt::{Debug, Formatter, self}, ops::{IndexMut, Index, Mul}};
+
+use super::{Matrix3D, Vector3D};
+
+
+pub type Matrix4DRow = [f64;4];
+/// Collumn major order, like Matrix3D
+/// [ 0 4  8 12 ]
+/// [ 1 5  9 13 ]
+/// [ 2 6 10 14 ]
+/// [ 3 7 11 15 ]
+///
+/// Points are column vectors, so a transform is `m * p` and the
+/// translation lives in the last column.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct Matrix4D{
+    pub n: [Matrix4DRow;4]
+}
+
+impl Debug for Matrix4D {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for i in 0..4 {
+            writeln!(f, "\t [{:?} {:?} {:?} {:?}]", self[(i,0)], self[(i,1)], self[(i,2)], self[(i,3)])?;
+        }
+        Ok(())
+    }
+}
+
+/// Indexing is (row, column)
+impl Index<(usize, usize)> for Matrix4D {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.n[col][row]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix4D {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.n[col][row]
+    }
+}
+
+/// Matrix multiplication, `a * b` applies `b` first
+impl Mul for Matrix4D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut m = Self::default();
+        for i in 0..4 { // Row
+            for j in 0..4 { // Col
+                m[(i,j)] = (0..4).map(|k| self[(i,k)] * rhs[(k,j)]).sum();
+            }
+        }
+        m
+    }
+}
+
+impl Matrix4D {
+    /// Create a matrix with the values in row major order
+    #[allow(clippy::too_many_arguments)]
+    pub fn new( n00:f64,n01:f64,n02:f64,n03:f64,
+                n10:f64,n11:f64,n12:f64,n13:f64,
+                n20:f64,n21:f64,n22:f64,n23:f64,
+                n30:f64,n31:f64,n32:f64,n33:f64) -> Self {
+        Self {
+            n: [
+                [n00, n10, n20, n30],
+                [n01, n11, n21, n31],
+                [n02, n12, n22, n32],
+                [n03, n13, n23, n33],
+            ]
+        }
+    }
+
+    /// Returns the 4x4 identity matrix
+    pub fn identity() -> Self {
+        Self::new(1.0,0.0,0.0,0.0,
+                  0.0,1.0,0.0,0.0,
+                  0.0,0.0,1.0,0.0,
+                  0.0,0.0,0.0,1.0)
+    }
+
+    /// Creates the affine transform `p -> m * p + translation`
+    ///
+    /// # Arguments
+    /// - `m` - The linear part, usually a rotation
+    /// - `translation` - The translation applied after `m`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Matrix4D, Vector3D};
+    ///
+    /// let m = Matrix4D::from_affine(&(Matrix3D::identity() * 2.0), &Vector3D::new(1.0, 0.0, 0.0));
+    /// assert_eq!(m.transform_point(&Vector3D::new(1.0, 1.0, 1.0)), Vector3D::new(3.0, 2.0, 2.0));
+    /// ```
+    pub fn from_affine(m: &Matrix3D, translation: &Vector3D) -> Self {
+        Self::new(m[(0,0)], m[(0,1)], m[(0,2)], translation.x,
+                  m[(1,0)], m[(1,1)], m[(1,2)], translation.y,
+                  m[(2,0)], m[(2,1)], m[(2,2)], translation.z,
+                  0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Returns the upper-left 3x3 block
+    pub fn linear(&self) -> Matrix3D {
+        Matrix3D::new(self[(0,0)], self[(0,1)], self[(0,2)],
+                      self[(1,0)], self[(1,1)], self[(1,2)],
+                      self[(2,0)], self[(2,1)], self[(2,2)])
+    }
+
+    /// Returns the first three entries of the last column
+    pub fn translation(&self) -> Vector3D {
+        Vector3D::new(self[(0,3)], self[(1,3)], self[(2,3)])
+    }
+
+    /// Returns row `i`
+    pub fn row(&self, i: usize) -> Matrix4DRow {
+        [self[(i,0)], self[(i,1)], self[(i,2)], self[(i,3)]]
+    }
+
+    /// Returns column `j`
+    pub fn column(&self, j: usize) -> Matrix4DRow {
+        self.n[j]
+    }
+
+    /// Returns the transpose of the matrix
+    pub fn transpose(&self) -> Self {
+        let mut m = Self::default();
+        for i in 0..4 {
+            for j in 0..4 {
+                m[(i,j)] = self[(j,i)];
+            }
+        }
+        m
+    }
+
+    /// Multiplies the homogeneous column vector `v` by the matrix
+    pub fn transform_homogeneous(&self, v: &[f64;4]) -> [f64;4] {
+        std::array::from_fn(|i| (0..4).map(|k| self[(i,k)] * v[k]).sum())
+    }
+
+    /// Transforms a point with `w = 1`, ignoring the bottom row
+    ///
+    /// Use `project_point` when the matrix is a projection.
+    pub fn transform_point(&self, p: &Vector3D) -> Vector3D {
+        self.linear() * *p + self.translation()
+    }
+
+    /// Transforms a direction with `w = 0`, so translation is ignored
+    pub fn transform_vector(&self, v: &Vector3D) -> Vector3D {
+        self.linear() * *v
+    }
+
+    /// Transforms a point with `w = 1` and divides by the resulting `w`
+    ///
+    /// Returns `None` if `w` is zero, i.e. the point is on the plane
+    /// through the eye parallel to the near plane.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix4D, Vector3D};
+    ///
+    /// let m = Matrix4D::new(1.0,0.0,0.0,0.0,
+    ///                       0.0,1.0,0.0,0.0,
+    ///                       0.0,0.0,1.0,0.0,
+    ///                       0.0,0.0,0.5,0.0);
+    /// assert_eq!(m.project_point(&Vector3D::new(1.0, 2.0, 4.0)), Some(Vector3D::new(0.5, 1.0, 2.0)));
+    /// ```
+    pub fn project_point(&self, p: &Vector3D) -> Option<Vector3D> {
+        let [x, y, z, w] = self.transform_homogeneous(&[p.x, p.y, p.z, 1.0]);
+        if w == 0.0 {
+            return None;
+        }
+        Some(Vector3D::new(x / w, y / w, z / w))
+    }
+
+    /// Returns the determinant of the matrix
+    pub fn determinant(&self) -> f64 {
+        let (s, t, u, v) = self.blocks();
+        s.dot(&v) + t.dot(&u)
+    }
+
+    /// Returns the inverse of the matrix, or `None` if it is singular
+    ///
+    /// Uses the cross-product form of the 4x4 inverse: the upper three rows
+    /// of each column are treated as Vector3Ds.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Matrix4D, Vector3D};
+    ///
+    /// let m = Matrix4D::from_affine(&(Matrix3D::identity() * 2.0), &Vector3D::new(1.0, 2.0, 3.0));
+    /// let p = Vector3D::new(4.0, 5.0, 6.0);
+    /// assert_eq!(m.inverse().unwrap().transform_point(&m.transform_point(&p)), p);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let (a, b, c, d) = (self.upper(0), self.upper(1), self.upper(2), self.upper(3));
+        let (x, y, z, w) = (self[(3,0)], self[(3,1)], self[(3,2)], self[(3,3)]);
+
+        let (s, t, u, v) = self.blocks();
+        let det = s.dot(&v) + t.dot(&u);
+        let scale = self.n.iter().flatten().fold(0.0_f64, |m, e| m.max(e.abs()));
+        if det.abs() <= f64::EPSILON * scale.powi(4) || !det.is_finite() {
+            return None;
+        }
+        let inv = 1.0 / det;
+        let (s, t, u, v) = (s * inv, t * inv, u * inv, v * inv);
+
+        let r0 = b.cross(&v) + t * y;
+        let r1 = v.cross(&a) - t * x;
+        let r2 = d.cross(&u) + s * w;
+        let r3 = u.cross(&c) - s * z;
+        Some(Self::new(r0.x, r0.y, r0.z, -b.dot(&t),
+                       r1.x, r1.y, r1.z, a.dot(&t),
+                       r2.x, r2.y, r2.z, -d.dot(&s),
+                       r3.x, r3.y, r3.z, c.dot(&s)))
+    }
+
+    /// Upper three entries of column `j`
+    fn upper(&self, j: usize) -> Vector3D {
+        Vector3D::new(self.n[j][0], self.n[j][1], self.n[j][2])
+    }
+
+    /// The four intermediate vectors shared by the determinant and inverse
+    fn blocks(&self) -> (Vector3D, Vector3D, Vector3D, Vector3D) {
+        let (a, b, c, d) = (self.upper(0), self.upper(1), self.upper(2), self.upper(3));
+        let (x, y, z, w) = (self[(3,0)], self[(3,1)], self[(3,2)], self[(3,3)]);
+        (a.cross(&b), c.cross(&d), a * y - b * x, c * w - d * z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_4_indexing() {
+        let m = Matrix4D::new(0.0,1.0,2.0,3.0,
+                              4.0,5.0,6.0,7.0,
+                              8.0,9.0,10.0,11.0,
+                              12.0,13.0,14.0,15.0);
+        assert_eq!(m[(1,2)], 6.0);
+        assert_eq!(m.n[2][1], 6.0);
+        assert_eq!(m.row(3), [12.0,13.0,14.0,15.0]);
+        assert_eq!(m.column(3), [3.0,7.0,11.0,15.0]);
+        assert_eq!(m.transpose()[(2,1)], 6.0);
+        assert_eq!(m * Matrix4D::identity(), m);
+        assert_eq!(m.determinant(), 0.0);
+        assert!(m.inverse().is_none());
+    }
+
+    #[test]
+    fn test_matrix_4_inverse() {
+        let m = Matrix4D::new(2.0,1.0,0.0,3.0,
+                              0.0,1.0,4.0,1.0,
+                              1.0,0.0,1.0,2.0,
+                              0.5,0.0,1.0,1.0);
+        let inv = m.inverse().unwrap();
+        let product = m * inv;
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((product[(i,j)] - expected).abs() < 1e-12);
+            }
+        }
+        assert!((m.determinant() * inv.determinant() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+
+use super::{Vector3D, EPSILON};
+
+/// How each triangle's contribution to its vertices' normals is weighted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalWeighting {
+    /// By triangle area: cheap, but long thin triangles dominate
+    Area,
+    /// By the triangle's angle at the vertex, independent of how the
+    /// surrounding surface is triangulated
+    #[default]
+    Angle,
+}
+
+/// How per-triangle tangents are combined at each vertex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TangentMode {
+    /// Lengyel's method: raw UV-derived tangents are summed, so larger
+    /// triangles weigh more
+    Standard,
+    /// Follows the MikkTSpace reference: each triangle's tangents are
+    /// projected into the vertex's tangent plane, normalised and weighted
+    /// by the corner angle, so baked normal maps line up wherever the mesh
+    /// is split at its mirror seams
+    #[default]
+    MikkTSpace,
+}
+
+/// A per-vertex tangent frame for normal mapping
+///
+/// The bitangent is not stored, rebuild it as
+/// `normal.cross(&tangent) * sign`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tangent {
+    /// Unit tangent along increasing u, perpendicular to the vertex normal
+    pub tangent: Vector3D,
+    /// +1.0 for a right-handed frame, -1.0 where the UVs are mirrored
+    pub sign: f64,
+}
+
+impl Tangent {
+    /// Returns the bitangent for the given vertex normal
+    pub fn bitangent(&self, normal: &Vector3D) -> Vector3D {
+        normal.cross(&self.tangent) * self.sign
+    }
+}
+
+/// Returns a unit normal for every vertex of an indexed triangle list
+///
+/// Degenerate triangles (zero area) contribute nothing. A vertex used by no
+/// valid triangle gets a zero normal.
+///
+/// # Arguments
+/// - `positions` - The vertex positions
+/// - `indices` - Counter-clockwise triangles, front faces seen from outside
+/// - `weighting` - How triangle contributions are weighted
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{vertex_normals, NormalWeighting, Vector3D};
+///
+/// let positions = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0)];
+/// let normals = vertex_normals(&positions, &[[0, 1, 2]], NormalWeighting::Angle);
+/// assert_eq!(normals, vec![Vector3D::new(0.0, 0.0, 1.0); 3]);
+/// ```
+pub fn vertex_normals(positions: &[Vector3D], indices: &[[usize; 3]], weighting: NormalWeighting) -> Vec<Vector3D> {
+    accumulate_normals(positions, indices, weighting).iter().map(unit_or_zero).collect()
+}
+
+/// Returns vertex normals that are shared by every vertex at the same position
+///
+/// Importers split vertices wherever a UV seam runs, which would otherwise
+/// leave a visible crease in the lighting. Contributions are summed over all
+/// vertices with exactly equal positions before normalising. Use
+/// `vertex_normals` where the split is intended as a hard edge.
+///
+/// # Arguments
+/// - `positions` - The vertex positions
+/// - `indices` - Counter-clockwise triangles, front faces seen from outside
+/// - `weighting` - How triangle contributions are weighted
+pub fn vertex_normals_welded(positions: &[Vector3D], indices: &[[usize; 3]], weighting: NormalWeighting) -> Vec<Vector3D> {
+    let sums = accumulate_normals(positions, indices, weighting);
+    let mut groups: HashMap<[u64; 3], Vector3D> = HashMap::new();
+    for (p, n) in positions.iter().zip(&sums) {
+        let sum = groups.entry(position_key(p)).or_default();
+        *sum = *sum + *n;
+    }
+    positions.iter().map(|p| unit_or_zero(&groups[&position_key(p)])).collect()
+}
+
+/// Returns a tangent frame for every vertex from its UVs
+///
+/// Tangents are made perpendicular to the vertex normals with Gram-Schmidt.
+/// Triangles with degenerate positions or UVs contribute nothing, and a
+/// vertex left without any contribution gets an arbitrary tangent
+/// perpendicular to its normal with a sign of +1, so the output is always
+/// usable. Vertices split along a UV seam keep their own tangents.
+///
+/// Mirrored and unmirrored triangles are summed apart, since averaging
+/// across them gives a tangent matching neither side. A vertex shared
+/// across a mirror seam can hold only one frame, so it takes the side
+/// covering the larger angle around it. MikkTSpace splits such vertices
+/// instead, so split them along mirror seams to match it exactly.
+///
+/// # Arguments
+/// - `positions` - The vertex positions
+/// - `normals` - The unit vertex normals
+/// - `uvs` - The texture coordinates, one `[u, v]` per vertex
+/// - `indices` - The triangles
+/// - `mode` - How triangle tangents are combined
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{vertex_tangents, TangentMode, Vector3D};
+///
+/// let positions = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 0.0, 0.0), Vector3D::new(0.0, 2.0, 0.0)];
+/// let normals = [Vector3D::new(0.0, 0.0, 1.0); 3];
+/// let uvs = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+/// let tangents = vertex_tangents(&positions, &normals, &uvs, &[[0, 1, 2]], TangentMode::MikkTSpace);
+/// assert_eq!(tangents[0].tangent, Vector3D::new(1.0, 0.0, 0.0));
+/// assert_eq!(tangents[0].sign, 1.0);
+/// ```
+pub fn vertex_tangents(
+    positions: &[Vector3D],
+    normals: &[Vector3D],
+    uvs: &[[f64; 2]],
+    indices: &[[usize; 3]],
+    mode: TangentMode,
+) -> Vec<Tangent> {
+    // Sums for unmirrored and mirrored corners, in that order
+    let mut tangents = vec![[Vector3D::default(); 2]; positions.len()];
+    let mut bitangents = vec![[Vector3D::default(); 2]; positions.len()];
+    let mut angles = vec![[0.0; 2]; positions.len()];
+
+    for triangle in indices {
+        let Some((t, b)) = triangle_tangent(positions, uvs, triangle) else {
+            continue;
+        };
+        for corner in 0..3 {
+            let i = triangle[corner];
+            let n = normals[i];
+            let angle = corner_angle(positions, triangle, corner);
+            let side = usize::from(n.cross(&t).dot(&b) < 0.0);
+            let (t, b) = match mode {
+                TangentMode::Standard => (t, b),
+                TangentMode::MikkTSpace => (unit_or_zero(&(t - n * n.dot(&t))) * angle, unit_or_zero(&(b - n * n.dot(&b))) * angle),
+            };
+            tangents[i][side] = tangents[i][side] + t;
+            bitangents[i][side] = bitangents[i][side] + b;
+            angles[i][side] += angle;
+        }
+    }
+
+    normals
+        .iter()
+        .enumerate()
+        .map(|(i, n)| {
+            let side = usize::from(angles[i][1] > angles[i][0]);
+            let (t, b) = (&tangents[i][side], &bitangents[i][side]);
+            // Gram-Schmidt against the normal
+            let tangent = *t - *n * n.dot(t);
+            if tangent.magnitude_squared() <= EPSILON * EPSILON {
+                return Tangent { tangent: n.any_perpendicular(), sign: 1.0 };
+            }
+            let tangent = tangent.normalise();
+            let sign = if n.cross(&tangent).dot(b) < 0.0 { -1.0 } else { 1.0 };
+            Tangent { tangent, sign }
+        })
+        .collect()
+}
+
+/// Sums the weighted face normals at each vertex
+fn accumulate_normals(positions: &[Vector3D], indices: &[[usize; 3]], weighting: NormalWeighting) -> Vec<Vector3D> {
+    let mut sums = vec![Vector3D::default(); positions.len()];
+    for triangle in indices {
+        let [a, b, c] = triangle.map(|i| positions[i]);
+        // Twice the area times the unit normal
+        let cross = (b - a).cross(&(c - a));
+        let length = cross.magnitude();
+        if length <= EPSILON * (b - a).magnitude() * (c - a).magnitude() || length == 0.0 {
+            continue;
+        }
+        for corner in 0..3 {
+            let contribution = match weighting {
+                NormalWeighting::Area => cross * 0.5,
+                NormalWeighting::Angle => cross * (corner_angle(positions, triangle, corner) / length),
+            };
+            sums[triangle[corner]] = sums[triangle[corner]] + contribution;
+        }
+    }
+    sums
+}
+
+/// Returns the unit-free tangent and bitangent of a triangle, the
+/// directions of increasing u and v, or None if it is degenerate
+fn triangle_tangent(positions: &[Vector3D], uvs: &[[f64; 2]], triangle: &[usize; 3]) -> Option<(Vector3D, Vector3D)> {
+    let [p0, p1, p2] = triangle.map(|i| positions[i]);
+    let [w0, w1, w2] = triangle.map(|i| uvs[i]);
+    let (e1, e2) = (p1 - p0, p2 - p0);
+    let (s1, t1) = (w1[0] - w0[0], w1[1] - w0[1]);
+    let (s2, t2) = (w2[0] - w0[0], w2[1] - w0[1]);
+
+    let uv_area = s1 * t2 - s2 * t1;
+    let scale = (s1.abs() + t1.abs()) * (s2.abs() + t2.abs());
+    if uv_area.abs() <= EPSILON * scale || uv_area == 0.0 || e1.cross(&e2).magnitude() <= EPSILON * e1.magnitude() * e2.magnitude() {
+        return None;
+    }
+    let r = 1.0 / uv_area;
+    Some(((e1 * t2 - e2 * t1) * r, (e2 * s1 - e1 * s2) * r))
+}
+
+/// Interior angle of a triangle at one of its corners
+fn corner_angle(positions: &[Vector3D], triangle: &[usize; 3], corner: usize) -> f64 {
+    let p = positions[triangle[corner]];
+    let a = positions[triangle[(corner + 1) % 3]] - p;
+    let b = positions[triangle[(corner + 2) % 3]] - p;
+    a.cross(&b).magnitude().atan2(a.dot(&b))
+}
+
+fn unit_or_zero(v: &Vector3D) -> Vector3D {
+    let length = v.magnitude();
+    if length == 0.0 { Vector3D::default() } else { *v / length }
+}
+
+/// Hash key for exact position equality, treating -0.0 as 0.0
+fn position_key(p: &Vector3D) -> [u64; 3] {
+    [p.x, p.y, p.z].map(|c| (c + 0.0).to_bits())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit cube with four vertices per face and a (u, v) box mapping
+    #[allow(clippy::type_complexity)]
+    fn cube() -> (Vec<Vector3D>, Vec<[f64; 2]>, Vec<[usize; 3]>, Vec<(Vector3D, Vector3D)>) {
+        // Face normal and the direction u increases along
+        let faces = [
+            (Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, -1.0)),
+            (Vector3D::new(-1.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 1.0)),
+            (Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)),
+            (Vector3D::new(0.0, -1.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)),
+            (Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(1.0, 0.0, 0.0)),
+            (Vector3D::new(0.0, 0.0, -1.0), Vector3D::new(-1.0, 0.0, 0.0)),
+        ];
+        let (mut positions, mut uvs, mut indices) = (Vec::new(), Vec::new(), Vec::new());
+        for (normal, u) in faces {
+            let v = normal.cross(&u);
+            let base = positions.len();
+            for (su, sv) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                positions.push(normal * 0.5 + u * (su - 0.5) + v * (sv - 0.5));
+                uvs.push([su, sv]);
+            }
+            indices.push([base, base + 1, base + 2]);
+            indices.push([base, base + 2, base + 3]);
+        }
+        (positions, uvs, indices, faces.to_vec())
+    }
+
+    /// Latitude-longitude unit sphere with a seam at longitude zero
+    fn uv_sphere(rings: usize, segments: usize) -> (Vec<Vector3D>, Vec<[f64; 2]>, Vec<[usize; 3]>) {
+        let (mut positions, mut uvs, mut indices) = (Vec::new(), Vec::new(), Vec::new());
+        for ring in 0..=rings {
+            let theta = std::f64::consts::PI * ring as f64 / rings as f64;
+            for segment in 0..=segments {
+                // The seam column repeats the first exactly, as an importer would
+                let phi = std::f64::consts::TAU * (segment % segments) as f64 / segments as f64;
+                positions.push(Vector3D::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), -theta.cos()));
+                uvs.push([segment as f64 / segments as f64, ring as f64 / rings as f64]);
+            }
+        }
+        let row = segments + 1;
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let (a, b) = (ring * row + segment, ring * row + segment + 1);
+                let (c, d) = (a + row, b + row);
+                indices.push([a, b, d]);
+                indices.push([a, d, c]);
+            }
+        }
+        (positions, uvs, indices)
+    }
+
+    #[test]
+    fn test_cube_normals_and_tangents() {
+        let (positions, uvs, indices, faces) = cube();
+        for weighting in [NormalWeighting::Area, NormalWeighting::Angle] {
+            let normals = vertex_normals(&positions, &indices, weighting);
+            for mode in [TangentMode::Standard, TangentMode::MikkTSpace] {
+                let tangents = vertex_tangents(&positions, &normals, &uvs, &indices, mode);
+                for (face, (normal, u)) in faces.iter().enumerate() {
+                    for i in face * 4..face * 4 + 4 {
+                        assert!((normals[i] - *normal).magnitude() < 1e-12);
+                        assert!((tangents[i].tangent - *u).magnitude() < 1e-12);
+                        assert_eq!(tangents[i].sign, 1.0);
+                    }
+                }
+            }
+        }
+
+        // Welding the split corners rounds them off
+        let welded = vertex_normals_welded(&positions, &indices, NormalWeighting::Angle);
+        let corner = 1.0 / 3.0_f64.sqrt();
+        for (p, n) in positions.iter().zip(&welded) {
+            let expected = Vector3D::new(p.x.signum(), p.y.signum(), p.z.signum()) * corner;
+            assert!((*n - expected).magnitude() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_angle_weighting_ignores_triangulation() {
+        // A vertex at the corner of a flat and a sloped face, the flat one
+        // split into many slivers
+        let mut positions = vec![Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(0.0, -1.0, 1.0), Vector3D::new(1.0, 0.0, 0.0)];
+        let mut indices = vec![[0, 1, 2]];
+        let fan = 8;
+        for k in 0..=fan {
+            let angle = std::f64::consts::FRAC_PI_2 * k as f64 / fan as f64;
+            positions.push(Vector3D::new(angle.cos(), angle.sin(), 0.0));
+        }
+        for k in 0..fan {
+            indices.push([0, 3 + k, 4 + k]);
+        }
+        let split = vertex_normals(&positions, &indices, NormalWeighting::Angle)[0];
+
+        let single = [positions[0], positions[1], positions[2], Vector3D::new(0.0, 1.0, 0.0)];
+        let whole = vertex_normals(&single, &[[0, 1, 2], [0, 2, 3]], NormalWeighting::Angle)[0];
+        // The fan covers the quarter circle rather than a triangle, so only
+        // the angle matches exactly, not the area
+        assert!((split - whole).magnitude() < 1e-12);
+        let area = vertex_normals(&positions, &indices, NormalWeighting::Area)[0];
+        assert!((area - whole).magnitude() > 1e-3);
+    }
+
+    #[test]
+    fn test_sphere_tangents() {
+        let (positions, uvs, indices) = uv_sphere(24, 48);
+        // Welded so the seam at longitude zero is smooth
+        let normals = vertex_normals_welded(&positions, &indices, NormalWeighting::Angle);
+        for mode in [TangentMode::Standard, TangentMode::MikkTSpace] {
+            let tangents = vertex_tangents(&positions, &normals, &uvs, &indices, mode);
+            for (i, p) in positions.iter().enumerate() {
+                if p.z.abs() > 0.99 {
+                    continue; // Poles have no longitude direction
+                }
+                assert!((normals[i] - *p).magnitude() < 1e-2);
+                // Seam vertices only see faces on one side, so their tangent
+                // follows the chord of half a segment
+                let seam = i % 49 == 0 || i % 49 == 48;
+                let tolerance = if seam { std::f64::consts::PI / 48.0 } else { 2e-2 };
+                let east = Vector3D::new(-p.y, p.x, 0.0).normalise();
+                assert!((tangents[i].tangent - east).magnitude() < tolerance);
+                assert!(tangents[i].tangent.dot(&normals[i]).abs() < 1e-12);
+                assert_eq!(tangents[i].sign, 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirrored_uvs_and_degenerates() {
+        // Two quads sharing an edge, the second with its UVs mirrored in u
+        let positions = vec![
+            Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 0.0), Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(2.0, 0.0, 0.0), Vector3D::new(2.0, 1.0, 0.0), Vector3D::new(1.0, 1.0, 0.0),
+            // An unused vertex and a collapsed triangle
+            Vector3D::new(5.0, 5.0, 5.0), Vector3D::new(3.0, 0.0, 0.0),
+        ];
+        let uvs = vec![
+            [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0],
+            [1.0, 0.0], [0.0, 0.0], [0.0, 1.0], [1.0, 1.0],
+            [0.0, 0.0], [0.5, 0.5],
+        ];
+        let indices = vec![[0, 1, 2], [0, 2, 3], [4, 5, 6], [4, 6, 7], [9, 9, 5], [5, 9, 9]];
+        let normals = vertex_normals(&positions, &indices, NormalWeighting::Area);
+        assert_eq!(normals[8], Vector3D::default());
+        assert_eq!(normals[9], Vector3D::default());
+        assert_eq!(normals[5], Vector3D::new(0.0, 0.0, 1.0));
+
+        let mut normals = normals;
+        normals[8] = Vector3D::new(0.0, 0.0, 1.0);
+        let tangents = vertex_tangents(&positions, &normals, &uvs, &indices, TangentMode::MikkTSpace);
+        for i in 0..4 {
+            assert_eq!(tangents[i].tangent, Vector3D::new(1.0, 0.0, 0.0));
+            assert_eq!(tangents[i].sign, 1.0);
+            assert_eq!(tangents[i].bitangent(&normals[i]), Vector3D::new(0.0, 1.0, 0.0));
+        }
+        for i in 4..8 {
+            assert_eq!(tangents[i].tangent, Vector3D::new(-1.0, 0.0, 0.0));
+            assert_eq!(tangents[i].sign, -1.0);
+            assert_eq!(tangents[i].bitangent(&normals[i]), Vector3D::new(0.0, 1.0, 0.0));
+        }
+        // Vertices with no usable triangle still get a valid frame
+        let fallback = tangents[8];
+        assert!((fallback.tangent.magnitude() - 1.0).abs() < 1e-12);
+        assert_eq!(fallback.tangent.dot(&normals[8]), 0.0);
+    }
+
+    #[test]
+    fn test_shared_vertex_on_mirror_seam() {
+        // A quad on the left and a fan on the right, sharing vertices 1 and
+        // 2. The fan's UVs are mirrored and sheared, so its tangent runs
+        // diagonally and averaging the two sides would match neither
+        let positions = vec![
+            Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 0.0), Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(1.0, -1.0, 0.0), Vector3D::new(2.0, 0.0, 0.0),
+        ];
+        // u = x, v = y on the left and u = 2 - x, v = x + y - 1 on the right,
+        // which agree along x = 1
+        let uvs = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [1.0, -1.0], [0.0, 1.0]];
+        let indices = vec![[0, 1, 2], [0, 2, 3], [1, 4, 5], [1, 5, 2]];
+        let normals = vec![Vector3D::new(0.0, 0.0, 1.0); positions.len()];
+        let left = Vector3D::new(1.0, 0.0, 0.0);
+        let right = Vector3D::new(-1.0, 1.0, 0.0).normalise();
+
+        for mode in [TangentMode::Standard, TangentMode::MikkTSpace] {
+            let tangents = vertex_tangents(&positions, &normals, &uvs, &indices, mode);
+            // Vertex 1 sees more of the fan around it, vertex 2 more of the quad
+            let expected = [(left, 1.0), (right, -1.0), (left, 1.0), (left, 1.0), (right, -1.0), (right, -1.0)];
+            for (i, (tangent, sign)) in expected.into_iter().enumerate() {
+                assert!((tangents[i].tangent - tangent).magnitude() < 1e-12, "{:?} {}: {:?}", mode, i, tangents[i]);
+                assert_eq!(tangents[i].sign, sign);
+            }
+        }
+    }
+}
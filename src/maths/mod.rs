@@ -1,10 +1,85 @@
 mod vector_3;
 mod matrix_3;
+mod matrix_4;
+mod batch;
+mod parallel;
+mod plane;
+mod line;
+mod bivector_3;
+mod trivector_3;
+mod ray;
+mod aabb;
+mod obb;
+mod sphere;
+mod capsule;
+mod fitting;
+mod support;
+mod gjk;
+mod closest;
+mod frustum;
+mod projection;
+mod camera;
+mod mesh;
+mod hull;
+mod bvh;
+mod spatial;
+mod octree;
+mod spatial_hash;
+mod kdtree;
+mod lerp;
+mod clip;
+mod curves;
+mod arc_length;
+mod frames;
+mod quaternion;
+mod easing;
+mod tween;
 
 pub use vector_3::Vector3D;
 pub use matrix_3::Matrix3D;
+pub use matrix_4::Matrix4D;
+pub use batch::Vector3DBatch;
+pub use parallel::{ParallelBatch, REDUCTION_CHUNK};
+pub use plane::Plane;
+pub use line::Line;
+pub use bivector_3::Bivector3D;
+pub use trivector_3::Trivector3D;
+pub use ray::{Ray, RayHit, TriangleHit};
+pub use aabb::Aabb;
+pub use obb::Obb;
+pub use sphere::Sphere;
+pub use capsule::Capsule;
+pub use fitting::{approximate_minimal_obb, minimal_enclosing_sphere, pca_obb, tight_aabb};
+pub use support::{MinkowskiSum, SupportMap, Transformed};
+pub use gjk::{epa_penetration, gjk_distance, gjk_intersects, Penetration, Separation};
+pub use closest::{
+    closest_point_aabb, closest_point_obb, closest_point_segment, closest_point_triangle, closest_segment_segment,
+    closest_segment_triangle, closest_triangle_triangle, ClosestPoints,
+};
+pub use frustum::{Containment, Frustum};
+pub use projection::{oblique_near_plane, orthographic, perspective, perspective_off_center, DepthRange, Handedness, ProjectionOptions};
+pub use camera::{look_at, view_matrix, Camera, Viewport};
+pub use mesh::{vertex_normals, vertex_normals_welded, vertex_tangents, NormalWeighting, Tangent, TangentMode};
+pub use hull::{ConvexHull, HullError, HullFace};
+pub use bvh::{Bvh, BVH_MAX_LEAF_SIZE};
+pub use spatial::SpatialIndex;
+pub use octree::Octree;
+pub use spatial_hash::SpatialHash;
+pub use kdtree::KdTree;
+pub use lerp::Lerp;
+pub use clip::{clip_polygon, clip_polygon_frustum, clip_polygon_planes, split_polygon, ClipVertex, CLIP_TOLERANCE};
+pub use curves::{BSpline, CatmullRom, CatmullRomKind, CubicBezier, Curve, Hermite, QuadraticBezier};
+pub use arc_length::{arc_length, ArcLengthTable};
+pub use frames::{frenet_frame, frenet_frames, rotation_minimising_frames};
+pub use quaternion::{Quaternion, QuaternionSpline};
+pub use easing::{CubicBezierTiming, EaseCurve, Easing};
+pub use tween::Tween;
 
-#[derive(Debug, PartialEq)]
+/// Tolerance used by geometric queries to treat nearly parallel or nearly
+/// degenerate inputs as exactly parallel or degenerate
+pub const EPSILON: f64 = 1.0e-10;
+
+#[derive(Debug, Default, PartialEq)]
 pub struct Maths;
 impl Maths {
     pub fn new() -> Self {
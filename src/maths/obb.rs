@@ -0,0 +1,347 @@
+use super::{closest::closest_segment_aabb, Aabb, Capsule, Matrix3D, Ray, RayHit, Sphere, Vector3D, EPSILON};
+
+/// Oriented bounding box
+///
+/// The columns of `orientation` are the box's local x, y and z axes and must
+/// be orthonormal. `half_extents` is half the size of the box along each of
+/// those axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+    pub center: Vector3D,
+    pub orientation: Matrix3D,
+    pub half_extents: Vector3D,
+}
+
+impl Obb {
+    /// Creates a new Obb
+    ///
+    /// # Arguments
+    /// - `center` - The centre of the box
+    /// - `orientation` - Rotation whose columns are the box's local axes
+    /// - `half_extents` - Half the size of the box along each local axis
+    pub fn new(center: Vector3D, orientation: Matrix3D, half_extents: Vector3D) -> Self {
+        Self {
+            center,
+            orientation,
+            half_extents,
+        }
+    }
+
+    /// Creates an axis-aligned Obb covering the same space as an Aabb
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self {
+            center: aabb.center(),
+            orientation: Matrix3D::identity(),
+            half_extents: aabb.half_extents(),
+        }
+    }
+
+    /// Returns the local axis `i` (a column of `orientation`)
+    pub fn axis(&self, i: usize) -> Vector3D {
+        self.orientation.column(i)
+    }
+
+    /// Returns `point` in the box's local frame, relative to its centre
+    pub fn to_local(&self, point: &Vector3D) -> Vector3D {
+        self.orientation.transpose() * (*point - self.center)
+    }
+
+    /// Returns the eight corners of the box
+    pub fn corners(&self) -> [Vector3D; 8] {
+        let mut corners = [Vector3D::default(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let local = Vector3D::new(
+                if i & 1 == 0 { -self.half_extents.x } else { self.half_extents.x },
+                if i & 2 == 0 { -self.half_extents.y } else { self.half_extents.y },
+                if i & 4 == 0 { -self.half_extents.z } else { self.half_extents.z },
+            );
+            *corner = self.center + self.orientation * local;
+        }
+        corners
+    }
+
+    /// Returns the volume of the box
+    pub fn volume(&self) -> f64 {
+        8.0 * self.half_extents.x * self.half_extents.y * self.half_extents.z
+    }
+
+    /// Returns the point in the box closest to `point`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Obb, Vector3D};
+    ///
+    /// let obb = Obb::new(Vector3D::default(), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+    /// assert_eq!(obb.closest_point(&Vector3D::new(3.0, 0.5, -2.0)), Vector3D::new(1.0, 0.5, -1.0));
+    /// ```
+    pub fn closest_point(&self, point: &Vector3D) -> Vector3D {
+        let local = self.to_local(point);
+        let clamped = local.max(&-self.half_extents).min(&self.half_extents);
+        self.center + self.orientation * clamped
+    }
+
+    /// Returns true if `point` is inside the box or on its surface
+    pub fn contains_point(&self, point: &Vector3D) -> bool {
+        let local = self.to_local(point);
+        (0..3).all(|i| local[i].abs() <= self.half_extents[i])
+    }
+
+    /// Returns true if `other` lies entirely inside this box
+    pub fn contains_obb(&self, other: &Obb) -> bool {
+        other.corners().iter().all(|c| self.contains_point(c))
+    }
+
+    /// Returns true if the box overlaps a Sphere
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        (self.closest_point(&sphere.center) - sphere.center).magnitude_squared() <= sphere.radius * sphere.radius
+    }
+
+    /// Returns true if the box overlaps a Capsule
+    pub fn intersects_capsule(&self, capsule: &Capsule) -> bool {
+        self.segment_distance_squared(&capsule.a, &capsule.b) <= capsule.radius * capsule.radius
+    }
+
+    /// Returns true if the box overlaps an Aabb
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        !aabb.is_empty() && self.intersects_obb(&Obb::from_aabb(aabb))
+    }
+
+    /// Returns true if the two boxes overlap, using the separating axis theorem
+    ///
+    /// Tests the 3 face axes of each box and the 9 cross products of their
+    /// edges; the boxes are disjoint exactly when one of those 15 axes
+    /// separates their projections.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Obb, Vector3D};
+    ///
+    /// let a = Obb::new(Vector3D::default(), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+    /// let b = Obb::new(Vector3D::new(1.5, 0.0, 0.0), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+    /// let c = Obb::new(Vector3D::new(2.5, 0.0, 0.0), Matrix3D::identity(), Vector3D::new(0.4, 1.0, 1.0));
+    /// assert!(a.intersects_obb(&b));
+    /// assert!(!a.intersects_obb(&c));
+    /// ```
+    pub fn intersects_obb(&self, other: &Obb) -> bool {
+        let ea = self.half_extents;
+        let eb = other.half_extents;
+
+        // Rotation expressing `other` in this box's frame, plus an epsilon on
+        // the absolute values to stop near-parallel edges producing a zero axis
+        let mut r = [[0.0; 3]; 3];
+        let mut abs_r = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                r[i][j] = self.axis(i).dot(&other.axis(j));
+                abs_r[i][j] = r[i][j].abs() + EPSILON;
+            }
+        }
+        let t = self.to_local(&other.center);
+
+        // Face axes of self
+        for i in 0..3 {
+            let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+            if t[i].abs() > ea[i] + rb {
+                return false;
+            }
+        }
+        // Face axes of other
+        for j in 0..3 {
+            let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+            let distance = t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j];
+            if distance.abs() > ra + eb[j] {
+                return false;
+            }
+        }
+        // Edge cross products self.axis(i) x other.axis(j)
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+                let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+                let distance = t[i2] * r[i1][j] - t[i1] * r[i2][j];
+                if distance.abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the tightest Aabb around the box
+    pub fn aabb(&self) -> Aabb {
+        let mut reach = Vector3D::default();
+        for i in 0..3 {
+            for j in 0..3 {
+                reach[i] += self.orientation[(i, j)].abs() * self.half_extents[j];
+            }
+        }
+        Aabb::new(self.center - reach, self.center + reach)
+    }
+
+    /// Returns the box moved by `p -> rotation * p + translation`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Obb, Vector3D};
+    ///
+    /// let rotation = Matrix3D::new(0.0, -1.0, 0.0,
+    ///                              1.0, 0.0, 0.0,
+    ///                              0.0, 0.0, 1.0);
+    /// let obb = Obb::new(Vector3D::new(1.0, 0.0, 0.0), Matrix3D::identity(), Vector3D::new(2.0, 1.0, 1.0));
+    /// let moved = obb.transform(&rotation, &Vector3D::new(0.0, 0.0, 3.0));
+    /// assert_eq!(moved.center, Vector3D::new(0.0, 1.0, 3.0));
+    /// assert!(moved.contains_point(&Vector3D::new(0.0, 2.5, 3.0)));
+    /// ```
+    pub fn transform(&self, rotation: &Matrix3D, translation: &Vector3D) -> Self {
+        Self {
+            center: *rotation * self.center + *translation,
+            orientation: *rotation * self.orientation,
+            half_extents: self.half_extents,
+        }
+    }
+
+    /// Returns a box containing both boxes
+    ///
+    /// Keeps the orientation of the larger box and fits it around all sixteen
+    /// corners. This always encloses but is not the minimal box.
+    pub fn merge(&self, other: &Obb) -> Self {
+        let frame = if self.volume() >= other.volume() { self } else { other };
+        let to_local = frame.orientation.transpose();
+        let local_bounds = Aabb::from_points(
+            self.corners()
+                .into_iter()
+                .chain(other.corners())
+                .map(|c| to_local * c),
+        );
+        Self {
+            center: frame.orientation * local_bounds.center(),
+            orientation: frame.orientation,
+            half_extents: local_bounds.half_extents(),
+        }
+    }
+
+    /// Intersects a ray with the box, see `Ray::intersect_obb`
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<RayHit> {
+        ray.intersect_obb(&self.center, &self.orientation, &self.half_extents)
+    }
+
+    /// Returns the squared distance between the segment `a`-`b` and the box
+    fn segment_distance_squared(&self, a: &Vector3D, b: &Vector3D) -> f64 {
+        // Exact in local space, where the box is an Aabb about the origin
+        let local = Aabb::new(-self.half_extents, self.half_extents);
+        closest_segment_aabb(&self.to_local(a), &self.to_local(b), &local).map_or(f64::INFINITY, |closest| closest.distance_squared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation_z(angle: f64) -> Matrix3D {
+        let (s, c) = angle.sin_cos();
+        Matrix3D::new(c, -s, 0.0,
+                      s, c, 0.0,
+                      0.0, 0.0, 1.0)
+    }
+
+    fn rotation_x(angle: f64) -> Matrix3D {
+        let (s, c) = angle.sin_cos();
+        Matrix3D::new(1.0, 0.0, 0.0,
+                      0.0, c, -s,
+                      0.0, s, c)
+    }
+
+    #[test]
+    fn test_obb_containment() {
+        let obb = Obb::new(Vector3D::new(1.0, 1.0, 0.0), rotation_z(std::f64::consts::FRAC_PI_4), Vector3D::new(2.0, 0.5, 1.0));
+        assert!(obb.contains_point(&obb.center));
+        for corner in obb.corners() {
+            assert!(obb.aabb().contains_point(&corner));
+            assert!((obb.closest_point(&corner) - corner).magnitude() < 1e-12);
+        }
+        // Along the long axis but outside the unrotated box
+        let along = obb.center + obb.axis(0) * 1.9;
+        assert!(obb.contains_point(&along));
+        assert!(!obb.contains_point(&(obb.center + Vector3D::new(1.9, 0.0, 0.0))));
+
+        let inner = Obb::new(obb.center, obb.orientation, Vector3D::new(1.0, 0.25, 0.5));
+        assert!(obb.contains_obb(&inner));
+        assert!(!inner.contains_obb(&obb));
+    }
+
+    #[test]
+    fn test_obb_obb_separating_axes() {
+        let a = Obb::new(Vector3D::default(), Matrix3D::identity(), Vector3D::new(1.0, 1.0, 1.0));
+
+        // Separated along a face axis of `a`
+        let b = Obb::new(Vector3D::new(3.0, 0.0, 0.0), rotation_z(0.3), Vector3D::new(0.5, 0.5, 0.5));
+        assert!(!a.intersects_obb(&b));
+        assert!(!b.intersects_obb(&a));
+
+        // A diamond rotated 45 degrees whose corner pokes into `a`
+        let diamond = Obb::new(Vector3D::new(2.3, 0.0, 0.0), rotation_z(std::f64::consts::FRAC_PI_4), Vector3D::new(1.0, 1.0, 1.0));
+        assert!(a.intersects_obb(&diamond));
+        let diamond_far = Obb::new(Vector3D::new(2.5, 0.0, 0.0), diamond.orientation, diamond.half_extents);
+        assert!(!a.intersects_obb(&diamond_far));
+
+        // Two boxes standing on edge with their edges crossing at right
+        // angles. Between heights 2*sqrt(2) and about 3.83 only the edge-edge
+        // axis (x cross y) separates them
+        let lower = Obb::new(Vector3D::default(), rotation_x(std::f64::consts::FRAC_PI_4), Vector3D::new(1.0, 1.0, 1.0));
+        let rotation_y = Matrix3D::new(0.5_f64.sqrt(), 0.0, 0.5_f64.sqrt(),
+                                       0.0, 1.0, 0.0,
+                                       -(0.5_f64.sqrt()), 0.0, 0.5_f64.sqrt());
+        let crossing = Obb::new(Vector3D::new(0.0, 0.0, 2.7), rotation_y, Vector3D::new(1.0, 1.0, 1.0));
+        let apart = Obb::new(Vector3D::new(0.0, 0.0, 3.0), rotation_y, Vector3D::new(1.0, 1.0, 1.0));
+        assert!(lower.intersects_obb(&crossing));
+        assert!(!lower.intersects_obb(&apart));
+        assert!(!apart.intersects_obb(&lower));
+
+        // Matches a brute force corner containment check in simple cases
+        let inside = Obb::new(Vector3D::new(0.2, 0.0, 0.0), rotation_x(0.7), Vector3D::new(0.1, 0.1, 0.1));
+        assert!(a.contains_obb(&inside) && a.intersects_obb(&inside));
+    }
+
+    #[test]
+    fn test_obb_sphere_capsule_aabb() {
+        let obb = Obb::new(Vector3D::default(), rotation_z(std::f64::consts::FRAC_PI_4), Vector3D::new(2.0, 0.5, 0.5));
+        let diagonal = Vector3D::new(1.0, 1.0, 0.0).normalise();
+
+        assert!(obb.intersects_sphere(&Sphere::new(diagonal * 2.3, 0.5)));
+        assert!(!obb.intersects_sphere(&Sphere::new(diagonal * 2.6, 0.5)));
+        assert!(Sphere::new(diagonal * 2.3, 0.5).intersects_obb(&obb));
+
+        // Capsule crossing the middle, and one passing beside the tip
+        let crossing = Capsule::new(Vector3D::new(-3.0, 3.0, 0.0), Vector3D::new(3.0, -3.0, 0.0), 0.1);
+        assert!(obb.intersects_capsule(&crossing));
+        let tip = obb.center + diagonal * 2.0;
+        let beside = Capsule::new(tip + Vector3D::new(0.0, 0.0, 1.0), tip + Vector3D::new(1.0, 1.0, 1.0), 0.4);
+        assert!(!obb.intersects_capsule(&beside));
+        let touching = Capsule::new(beside.a, beside.b, 0.6);
+        assert!(touching.intersects_obb(&obb));
+
+        let aabb = Aabb::new(Vector3D::new(1.2, 1.2, -1.0), Vector3D::new(2.0, 2.0, 1.0));
+        assert!(obb.intersects_aabb(&aabb));
+        assert!(!obb.intersects_aabb(&Aabb::new(Vector3D::new(1.2, -2.0, -1.0), Vector3D::new(2.0, -1.2, 1.0))));
+        assert!(!obb.intersects_aabb(&Aabb::empty()));
+    }
+
+    #[test]
+    fn test_obb_transform_merge() {
+        let obb = Obb::new(Vector3D::new(1.0, 0.0, 0.0), rotation_x(0.4), Vector3D::new(1.0, 2.0, 0.5));
+        let rotation = rotation_z(1.1);
+        let translation = Vector3D::new(-2.0, 3.0, 1.0);
+        let moved = obb.transform(&rotation, &translation);
+        for (before, after) in obb.corners().iter().zip(moved.corners()) {
+            assert!((rotation * *before + translation - after).magnitude() < 1e-12);
+        }
+
+        let other = Obb::new(Vector3D::new(-3.0, 1.0, 2.0), rotation_z(0.3), Vector3D::new(0.5, 0.5, 0.5));
+        let merged = obb.merge(&other);
+        let slack = Obb::new(merged.center, merged.orientation, merged.half_extents + Vector3D::new(1e-9, 1e-9, 1e-9));
+        assert!(slack.contains_obb(&obb));
+        assert!(slack.contains_obb(&other));
+    }
+}
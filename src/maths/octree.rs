@@ -0,0 +1,269 @@
+use std::{cmp::Reverse, collections::BinaryHeap, collections::HashMap};
+
+use super::{spatial::Candidate, spatial::NearestSet, Aabb, SpatialIndex, Vector3D};
+
+/// How much larger a node's loose bounds are than its cell
+const LOOSENESS: f64 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+struct OctreeNode {
+    center: Vector3D,
+    half_size: f64,
+    depth: usize,
+    items: Vec<usize>,
+    /// Index of the first of eight consecutive children
+    children: Option<usize>,
+}
+
+impl OctreeNode {
+    /// The region any point stored in this node lies in
+    fn loose_bounds(&self) -> Aabb {
+        let half = self.half_size * LOOSENESS;
+        Aabb::from_center_half_extents(self.center, Vector3D::new(half, half, half))
+    }
+
+    /// The child octant the point falls in, numbered by x, y and z bits
+    fn octant(&self, point: &Vector3D) -> usize {
+        (point.x >= self.center.x) as usize | ((point.y >= self.center.y) as usize) << 1 | ((point.z >= self.center.z) as usize) << 2
+    }
+}
+
+/// Loose octree over points
+///
+/// Every node accepts points anywhere inside its cell grown by a factor of
+/// two, so a point that moves a little stays where it is instead of being
+/// reinserted. Points outside the root cell are kept in the root, so the
+/// tree works for any position but is only fast inside its bounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Octree {
+    nodes: Vec<OctreeNode>,
+    /// Per id, the point and the node holding it
+    items: HashMap<usize, (Vector3D, usize)>,
+    capacity: usize,
+    max_depth: usize,
+}
+
+impl Octree {
+    /// Creates an empty Octree over a cube
+    ///
+    /// # Arguments
+    /// - `center` - The centre of the root cell
+    /// - `half_size` - Half the edge length of the root cell
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Octree, SpatialIndex, Vector3D};
+    ///
+    /// let mut octree = Octree::new(Vector3D::default(), 100.0);
+    /// octree.insert(7, Vector3D::new(1.0, 2.0, 3.0));
+    /// octree.insert(9, Vector3D::new(50.0, 0.0, 0.0));
+    /// assert_eq!(octree.query_radius(&Vector3D::default(), 10.0), vec![7]);
+    /// assert_eq!(octree.k_nearest(&Vector3D::new(40.0, 0.0, 0.0), 1), vec![(9, 10.0)]);
+    /// ```
+    pub fn new(center: Vector3D, half_size: f64) -> Self {
+        Self::with_capacity(center, half_size, 16, 10)
+    }
+
+    /// Creates an empty Octree with custom splitting
+    ///
+    /// # Arguments
+    /// - `center` - The centre of the root cell
+    /// - `half_size` - Half the edge length of the root cell
+    /// - `capacity` - The number of points a node holds before it splits
+    /// - `max_depth` - The deepest level nodes split to
+    pub fn with_capacity(center: Vector3D, half_size: f64, capacity: usize, max_depth: usize) -> Self {
+        let root = OctreeNode { center, half_size, depth: 0, items: Vec::new(), children: None };
+        Self { nodes: vec![root], items: HashMap::new(), capacity: capacity.max(1), max_depth }
+    }
+
+    /// Puts `id` in the deepest existing node that holds `position`,
+    /// splitting it if it becomes too full
+    fn place(&mut self, id: usize, position: Vector3D) {
+        let mut node = 0;
+        while let Some(first) = self.nodes[node].children {
+            let child = first + self.nodes[node].octant(&position);
+            if !self.nodes[child].loose_bounds().contains_point(&position) {
+                break;
+            }
+            node = child;
+        }
+        self.nodes[node].items.push(id);
+        self.items.insert(id, (position, node));
+
+        let full = self.nodes[node].items.len() > self.capacity;
+        if full && self.nodes[node].children.is_none() && self.nodes[node].depth < self.max_depth {
+            self.split(node);
+        }
+    }
+
+    fn split(&mut self, node: usize) {
+        let OctreeNode { center, half_size, depth, .. } = self.nodes[node];
+        let first = self.nodes.len();
+        let quarter = half_size * 0.5;
+        for octant in 0..8 {
+            let offset = |bit: usize| if octant & bit != 0 { quarter } else { -quarter };
+            self.nodes.push(OctreeNode {
+                center: center + Vector3D::new(offset(1), offset(2), offset(4)),
+                half_size: quarter,
+                depth: depth + 1,
+                items: Vec::new(),
+                children: None,
+            });
+        }
+        self.nodes[node].children = Some(first);
+
+        // Push down the points the children can hold, the rest stay here
+        let items = std::mem::take(&mut self.nodes[node].items);
+        for id in items {
+            let position = self.items[&id].0;
+            let child = first + self.nodes[node].octant(&position);
+            let target = if self.nodes[child].loose_bounds().contains_point(&position) { child } else { node };
+            self.nodes[target].items.push(id);
+            self.items.insert(id, (position, target));
+        }
+    }
+
+    /// Visits the points of every node whose loose bounds pass `enter`
+    fn visit<E: Fn(&Aabb) -> bool, V: FnMut(usize, &Vector3D)>(&self, enter: E, mut found: V) {
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            // The root also holds points outside its bounds
+            if node.depth > 0 && !enter(&node.loose_bounds()) {
+                continue;
+            }
+            for id in &node.items {
+                found(*id, &self.items[id].0);
+            }
+            if let Some(first) = node.children {
+                stack.extend(first..first + 8);
+            }
+        }
+    }
+}
+
+impl SpatialIndex for Octree {
+    fn insert(&mut self, id: usize, position: Vector3D) {
+        if !self.update(id, position) {
+            self.place(id, position);
+        }
+    }
+
+    fn remove(&mut self, id: usize) -> bool {
+        let Some((_, node)) = self.items.remove(&id) else {
+            return false;
+        };
+        let items = &mut self.nodes[node].items;
+        if let Some(index) = items.iter().position(|&i| i == id) {
+            items.swap_remove(index);
+        }
+        true
+    }
+
+    fn update(&mut self, id: usize, position: Vector3D) -> bool {
+        let Some((stored, node)) = self.items.get_mut(&id) else {
+            return false;
+        };
+        // Still inside the loose bounds, only the position changes. Points
+        // in the root are reinserted so they can move down again.
+        if *node != 0 && self.nodes[*node].loose_bounds().contains_point(&position) {
+            *stored = position;
+            return true;
+        }
+        self.remove(id);
+        self.place(id, position);
+        true
+    }
+
+    fn position(&self, id: usize) -> Option<Vector3D> {
+        self.items.get(&id).map(|(p, _)| *p)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn query_radius(&self, center: &Vector3D, radius: f64) -> Vec<usize> {
+        let radius_squared = radius * radius;
+        let mut found = Vec::new();
+        self.visit(
+            |aabb| aabb.distance_squared(center) <= radius_squared,
+            |id, p| {
+                if (*p - *center).magnitude_squared() <= radius_squared {
+                    found.push(id);
+                }
+            },
+        );
+        found
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(|node| node.intersects(aabb), |id, p| {
+            if aabb.contains_point(p) {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    fn k_nearest(&self, point: &Vector3D, k: usize) -> Vec<(usize, f64)> {
+        let mut nearest = NearestSet::new(k);
+        if k == 0 {
+            return nearest.into_sorted();
+        }
+        // Best first over nodes by the distance to their loose bounds
+        let mut queue = BinaryHeap::from([Reverse(Candidate { id: 0, distance_squared: 0.0 })]);
+        while let Some(Reverse(Candidate { id: node, distance_squared })) = queue.pop() {
+            if distance_squared > nearest.bound() {
+                break;
+            }
+            let node = &self.nodes[node];
+            for id in &node.items {
+                nearest.offer(*id, (self.items[id].0 - *point).magnitude_squared());
+            }
+            if let Some(first) = node.children {
+                for child in first..first + 8 {
+                    let distance_squared = self.nodes[child].loose_bounds().distance_squared(point);
+                    if distance_squared <= nearest.bound() {
+                        queue.push(Reverse(Candidate { id: child, distance_squared }));
+                    }
+                }
+            }
+        }
+        nearest.into_sorted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::exercise;
+
+    #[test]
+    fn test_octree_against_brute_force() {
+        let mut octree = Octree::new(Vector3D::default(), 64.0);
+        exercise(&mut octree, 50.0);
+        assert!(octree.nodes.len() > 1, "the tree split");
+
+        // A tree too small for its points still answers correctly
+        let mut tiny = Octree::with_capacity(Vector3D::new(5.0, 5.0, 5.0), 1.0, 2, 4);
+        exercise(&mut tiny, 50.0);
+    }
+
+    #[test]
+    fn test_octree_loose_update() {
+        let mut octree = Octree::with_capacity(Vector3D::default(), 8.0, 1, 3);
+        octree.insert(0, Vector3D::new(1.0, 1.0, 1.0));
+        octree.insert(1, Vector3D::new(-1.0, -1.0, -1.0));
+        let node = octree.items[&0].1;
+        assert_ne!(node, 0);
+
+        // Crossing the cell's edge but staying in its loose bounds keeps the node
+        octree.update(0, Vector3D::new(-0.5, 1.0, 1.0));
+        assert_eq!(octree.items[&0].1, node);
+        assert_eq!(octree.query_radius(&Vector3D::new(-0.5, 1.0, 1.0), 0.1), vec![0]);
+        octree.update(0, Vector3D::new(-6.0, 1.0, 1.0));
+        assert_ne!(octree.items[&0].1, node);
+    }
+}
@@ -0,0 +1,260 @@
+use std::thread;
+
+use super::{Matrix3D, Vector3D};
+
+/// Number of vectors folded into each partial sum during a reduction.
+///
+/// This is fixed rather than derived from the thread count, so the partial
+/// sums (and the order they are combined in) are the same no matter how many
+/// threads run. That keeps centroid and covariance results bit-for-bit
+/// reproducible across machines.
+pub const REDUCTION_CHUNK: usize = 4096;
+
+/// Inputs smaller than this are processed on the calling thread
+const MIN_PARALLEL_LEN: usize = 2 * REDUCTION_CHUNK;
+
+/// Parallel batch operations over slices of Vector3Ds using scoped threads
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{Matrix3D, ParallelBatch, Vector3D};
+///
+/// let mut vectors = vec![Vector3D::new(1.0, 2.0, 3.0); 10];
+/// let parallel = ParallelBatch::with_threads(4);
+/// parallel.transform(&mut vectors, &Matrix3D::index_test());
+/// assert_eq!(vectors[9], Vector3D::new(8.0, 26.0, 44.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelBatch {
+    threads: usize,
+}
+
+impl Default for ParallelBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParallelBatch {
+    /// Creates a new ParallelBatch using all available hardware threads
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_threads(threads)
+    }
+
+    /// Creates a new ParallelBatch using `threads` worker threads
+    ///
+    /// # Arguments
+    /// - `threads` - Number of threads to split work across (clamped to at least 1)
+    pub fn with_threads(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+        }
+    }
+
+    /// Returns the number of worker threads
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Transforms every vector in place by a Matrix3D (`v = m * v`)
+    pub fn transform(&self, vectors: &mut [Vector3D], m: &Matrix3D) {
+        let m = *m;
+        self.for_each_mut(vectors, move |v| *v = m * *v);
+    }
+
+    /// Normalises every vector in place
+    ///
+    /// Matches `Vector3D::normalise`, so zero length vectors become NaN.
+    pub fn normalise(&self, vectors: &mut [Vector3D]) {
+        self.for_each_mut(vectors, |v| *v = v.normalise());
+    }
+
+    /// Returns the centroid (mean) of the vectors, or `None` if the slice is empty
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{ParallelBatch, Vector3D};
+    ///
+    /// let vectors = vec![Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 4.0, 6.0)];
+    /// let centroid = ParallelBatch::new().centroid(&vectors).unwrap();
+    /// assert_eq!(centroid, Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn centroid(&self, vectors: &[Vector3D]) -> Option<Vector3D> {
+        if vectors.is_empty() {
+            return None;
+        }
+        let partials = self.partials(vectors, |chunk| {
+            chunk
+                .iter()
+                .fold([0.0; 3], |s, v| [s[0] + v.x, s[1] + v.y, s[2] + v.z])
+        });
+        let sum = partials
+            .iter()
+            .fold([0.0; 3], |s, p| [s[0] + p[0], s[1] + p[1], s[2] + p[2]]);
+        Some(Vector3D::from(sum) / vectors.len() as f64)
+    }
+
+    /// Returns the covariance matrix of the vectors about their centroid, or
+    /// `None` if the slice is empty
+    ///
+    /// This is the population covariance (divided by `n`, not `n - 1`).
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{ParallelBatch, Vector3D};
+    ///
+    /// let vectors = vec![Vector3D::new(-1.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)];
+    /// let covariance = ParallelBatch::new().covariance(&vectors).unwrap();
+    /// assert_eq!(covariance[(0, 0)], 1.0);
+    /// assert_eq!(covariance[(1, 1)], 0.0);
+    /// ```
+    pub fn covariance(&self, vectors: &[Vector3D]) -> Option<Matrix3D> {
+        let c = self.centroid(vectors)?;
+        // Upper triangle: xx, xy, xz, yy, yz, zz
+        let partials = self.partials(vectors, |chunk| {
+            chunk.iter().fold([0.0; 6], |s, v| {
+                let d = *v - c;
+                [
+                    s[0] + d.x * d.x,
+                    s[1] + d.x * d.y,
+                    s[2] + d.x * d.z,
+                    s[3] + d.y * d.y,
+                    s[4] + d.y * d.z,
+                    s[5] + d.z * d.z,
+                ]
+            })
+        });
+        let mut sum = [0.0; 6];
+        for p in &partials {
+            for (s, v) in sum.iter_mut().zip(p) {
+                *s += v;
+            }
+        }
+        let n = vectors.len() as f64;
+        let [xx, xy, xz, yy, yz, zz] = sum.map(|s| s / n);
+        Some(Matrix3D::new(xx, xy, xz,
+                           xy, yy, yz,
+                           xz, yz, zz))
+    }
+
+    /// Applies `f` to every vector, splitting the slice into one contiguous
+    /// range per thread
+    fn for_each_mut<F>(&self, vectors: &mut [Vector3D], f: F)
+    where
+        F: Fn(&mut Vector3D) + Sync,
+    {
+        if self.threads == 1 || vectors.len() < MIN_PARALLEL_LEN {
+            vectors.iter_mut().for_each(f);
+            return;
+        }
+        let per_thread = vectors.len().div_ceil(self.threads);
+        let f = &f;
+        thread::scope(|s| {
+            for range in vectors.chunks_mut(per_thread) {
+                s.spawn(move || range.iter_mut().for_each(f));
+            }
+        });
+    }
+
+    /// Computes one partial result per `REDUCTION_CHUNK` sized chunk, in
+    /// chunk order, spreading the chunks across threads
+    fn partials<T, F>(&self, vectors: &[Vector3D], f: F) -> Vec<T>
+    where
+        T: Copy + Default + Send,
+        F: Fn(&[Vector3D]) -> T + Sync,
+    {
+        let chunks: Vec<&[Vector3D]> = vectors.chunks(REDUCTION_CHUNK).collect();
+        let mut partials = vec![T::default(); chunks.len()];
+        if self.threads == 1 || vectors.len() < MIN_PARALLEL_LEN {
+            for (out, chunk) in partials.iter_mut().zip(&chunks) {
+                *out = f(chunk);
+            }
+            return partials;
+        }
+
+        let per_thread = chunks.len().div_ceil(self.threads);
+        let f = &f;
+        thread::scope(|s| {
+            for (outs, group) in partials.chunks_mut(per_thread).zip(chunks.chunks(per_thread)) {
+                s.spawn(move || {
+                    for (out, chunk) in outs.iter_mut().zip(group) {
+                        *out = f(chunk);
+                    }
+                });
+            }
+        });
+        partials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_vectors(n: usize) -> Vec<Vector3D> {
+        (0..n)
+            .map(|i| {
+                let f = i as f64;
+                Vector3D::new((f * 0.37).sin() * 5.0 + 1.0, (f * 0.11).cos() * 2.0, f * 1e-4 - 3.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_transform_normalise() {
+        let vectors = sample_vectors(50_000);
+        let m = Matrix3D::new(0.5, -1.0, 2.0,
+                              3.0, 0.25, -0.5,
+                              1.5, 2.0, 1.0);
+
+        let mut transformed = vectors.clone();
+        ParallelBatch::with_threads(3).transform(&mut transformed, &m);
+        let mut normalised = vectors.clone();
+        ParallelBatch::with_threads(5).normalise(&mut normalised);
+
+        for (i, v) in vectors.iter().enumerate() {
+            assert_eq!(transformed[i], m * *v);
+            assert_eq!(normalised[i], v.normalise());
+        }
+    }
+
+    #[test]
+    fn test_parallel_reduction_is_deterministic() {
+        let vectors = sample_vectors(100_003);
+        let reference = ParallelBatch::with_threads(1);
+        let centroid = reference.centroid(&vectors).unwrap();
+        let covariance = reference.covariance(&vectors).unwrap();
+
+        for threads in [2, 3, 4, 7, 16, 64] {
+            let parallel = ParallelBatch::with_threads(threads);
+            // Bitwise equality, not just "close"
+            assert_eq!(parallel.centroid(&vectors).unwrap(), centroid);
+            assert_eq!(parallel.covariance(&vectors).unwrap(), covariance);
+        }
+    }
+
+    #[test]
+    fn test_parallel_centroid_covariance() {
+        assert_eq!(ParallelBatch::new().centroid(&[]), None);
+        assert_eq!(ParallelBatch::new().covariance(&[]), None);
+
+        // Points along x only, so all variance is in xx
+        let vectors: Vec<Vector3D> = (0..20_000)
+            .map(|i| Vector3D::new(if i % 2 == 0 { -2.0 } else { 2.0 }, 1.0, 5.0))
+            .collect();
+        let parallel = ParallelBatch::with_threads(4);
+        assert_eq!(parallel.centroid(&vectors).unwrap(), Vector3D::new(0.0, 1.0, 5.0));
+        let covariance = parallel.covariance(&vectors).unwrap();
+        assert_eq!(covariance[(0, 0)], 4.0);
+        assert_eq!(covariance[(0, 1)], 0.0);
+        assert_eq!(covariance[(1, 1)], 0.0);
+        assert_eq!(covariance[(2, 2)], 0.0);
+    }
+
+    #[test]
+    fn test_parallel_threads() {
+        assert_eq!(ParallelBatch::with_threads(0).threads(), 1);
+        assert!(ParallelBatch::new().threads() >= 1);
+    }
+}
@@ -0,0 +1,341 @@
+use super::{Matrix3D, Vector3D, EPSILON};
+
+/// Plane stored as a unit normal and a distance
+///
+/// Points `p` on the plane satisfy `normal.dot(p) + distance == 0`, so the
+/// signed distance of any point is `normal.dot(p) + distance`, positive on the
+/// side the normal points to. `distance` is therefore the negated distance of
+/// the plane from the origin along the normal.
+///
+/// ```md
+///         normal
+///           ^
+///           |   (+) front
+/// ----------+----------  plane
+///               (-) back
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3D,
+    pub distance: f64,
+}
+
+impl Plane {
+    /// Creates a new Plane from a normal and a distance
+    ///
+    /// The normal is used as given, call `normalised` if it is not unit length.
+    ///
+    /// # Arguments
+    /// - `normal` - The unit normal of the plane
+    /// - `distance` - The plane constant, so that `normal.dot(p) + distance == 0`
+    pub fn new(normal: Vector3D, distance: f64) -> Self {
+        Self { normal, distance }
+    }
+
+    /// Creates a new Plane through `point` facing along `normal`
+    ///
+    /// # Arguments
+    /// - `point` - Any point on the plane
+    /// - `normal` - The plane normal, does not need to be unit length
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::from_point_normal(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(0.0, 0.0, 5.0));
+    /// assert_eq!(plane.normal, Vector3D::new(0.0, 0.0, 1.0));
+    /// assert_eq!(plane.distance, -2.0);
+    /// ```
+    pub fn from_point_normal(point: Vector3D, normal: Vector3D) -> Self {
+        let normal = normal.normalise();
+        Self {
+            normal,
+            distance: -normal.dot(&point),
+        }
+    }
+
+    /// Creates a new Plane through three points, or `None` if they are collinear
+    ///
+    /// The normal follows the right hand rule, so counter-clockwise points
+    /// (seen from the front) give a normal facing the viewer.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::from_points(
+    ///     Vector3D::new(0.0, 0.0, 1.0),
+    ///     Vector3D::new(1.0, 0.0, 1.0),
+    ///     Vector3D::new(0.0, 1.0, 1.0),
+    /// ).unwrap();
+    /// assert_eq!(plane.normal, Vector3D::new(0.0, 0.0, 1.0));
+    /// assert_eq!(plane.distance, -1.0);
+    /// ```
+    pub fn from_points(a: Vector3D, b: Vector3D, c: Vector3D) -> Option<Self> {
+        let normal = (b - a).cross(&(c - a));
+        let scale = (b - a).magnitude_squared().max((c - a).magnitude_squared());
+        if normal.magnitude_squared() <= EPSILON * EPSILON * scale * scale {
+            return None;
+        }
+        Some(Self::from_point_normal(a, normal))
+    }
+
+    /// Returns the plane rescaled so that its normal is unit length
+    pub fn normalised(&self) -> Self {
+        let magnitude = self.normal.magnitude();
+        Self {
+            normal: self.normal / magnitude,
+            distance: self.distance / magnitude,
+        }
+    }
+
+    /// Returns the same plane facing the opposite way
+    pub fn flipped(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            distance: -self.distance,
+        }
+    }
+
+    /// Returns the signed distance from the plane to `point`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -1.0);
+    /// assert_eq!(plane.signed_distance(&Vector3D::new(5.0, 3.0, 5.0)), 2.0);
+    /// assert_eq!(plane.signed_distance(&Vector3D::new(5.0, 0.0, 5.0)), -1.0);
+    /// ```
+    pub fn signed_distance(&self, point: &Vector3D) -> f64 {
+        self.normal.dot(point) + self.distance
+    }
+
+    /// Returns the closest point on the plane to `point`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -1.0);
+    /// assert_eq!(plane.project_point(&Vector3D::new(5.0, 3.0, 5.0)), Vector3D::new(5.0, 1.0, 5.0));
+    /// ```
+    pub fn project_point(&self, point: &Vector3D) -> Vector3D {
+        *point - self.normal * self.signed_distance(point)
+    }
+
+    /// Returns `point` mirrored across the plane
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -1.0);
+    /// assert_eq!(plane.reflect_point(&Vector3D::new(5.0, 3.0, 5.0)), Vector3D::new(5.0, -1.0, 5.0));
+    /// ```
+    pub fn reflect_point(&self, point: &Vector3D) -> Vector3D {
+        *point - self.normal * (2.0 * self.signed_distance(point))
+    }
+
+    /// Returns the reflection across the plane as an affine transform
+    ///
+    /// The result is `(m, t)` such that a point maps to `m * p + t`. The
+    /// linear part `m = I - 2 n n^T` alone reflects directions.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -1.0);
+    /// let (m, t) = plane.reflection();
+    /// let p = Vector3D::new(5.0, 3.0, 5.0);
+    /// assert_eq!(m * p + t, plane.reflect_point(&p));
+    /// ```
+    pub fn reflection(&self) -> (Matrix3D, Vector3D) {
+        let n = self.normal;
+        let m = Matrix3D::new(1.0 - 2.0 * n.x * n.x, -2.0 * n.x * n.y, -2.0 * n.x * n.z,
+                              -2.0 * n.y * n.x, 1.0 - 2.0 * n.y * n.y, -2.0 * n.y * n.z,
+                              -2.0 * n.z * n.x, -2.0 * n.z * n.y, 1.0 - 2.0 * n.z * n.z);
+        (m, n * (-2.0 * self.distance))
+    }
+
+    /// Returns the line where two planes meet as `(point, direction)`, or
+    /// `None` if the planes are parallel
+    ///
+    /// The point is the closest point on the line to the origin and the
+    /// direction is `self.normal x other.normal` (not normalised).
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let a = Plane::new(Vector3D::new(1.0, 0.0, 0.0), -1.0);
+    /// let b = Plane::new(Vector3D::new(0.0, 1.0, 0.0), -2.0);
+    /// let (point, direction) = a.intersect_plane(&b).unwrap();
+    /// assert_eq!(point, Vector3D::new(1.0, 2.0, 0.0));
+    /// assert_eq!(direction, Vector3D::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn intersect_plane(&self, other: &Plane) -> Option<(Vector3D, Vector3D)> {
+        let direction = self.normal.cross(&other.normal);
+        let length_squared = direction.magnitude_squared();
+        if length_squared <= EPSILON * EPSILON {
+            return None;
+        }
+        let point = (direction.cross(&other.normal) * self.distance
+            + self.normal.cross(&direction) * other.distance)
+            / length_squared;
+        Some((point, direction))
+    }
+
+    /// Returns the single point where three planes meet, or `None` if any two
+    /// are parallel or all three share a line
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Vector3D};
+    ///
+    /// let point = Plane::intersect_planes(
+    ///     &Plane::new(Vector3D::new(1.0, 0.0, 0.0), -1.0),
+    ///     &Plane::new(Vector3D::new(0.0, 1.0, 0.0), -2.0),
+    ///     &Plane::new(Vector3D::new(0.0, 0.0, 1.0), -3.0),
+    /// ).unwrap();
+    /// assert_eq!(point, Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Option<Vector3D> {
+        // Each row is one plane equation: n . p = -d
+        let m = Matrix3D::new(a.normal.x, a.normal.y, a.normal.z,
+                              b.normal.x, b.normal.y, b.normal.z,
+                              c.normal.x, c.normal.y, c.normal.z);
+        m.solve(&Vector3D::new(-a.distance, -b.distance, -c.distance))
+    }
+
+    /// Returns the plane transformed by the affine map `p -> m * p + translation`,
+    /// or `None` if `m` is singular
+    ///
+    /// Normals do not transform like points: the new normal is
+    /// `inverse(m)^T * normal`, which stays perpendicular to the plane under
+    /// non-uniform scale and shear.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Plane, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 0.0, 1.0), -1.0);
+    /// let moved = plane.transform(&Matrix3D::identity(), &Vector3D::new(0.0, 0.0, 2.0)).unwrap();
+    /// assert_eq!(moved.distance, -3.0);
+    /// ```
+    pub fn transform(&self, m: &Matrix3D, translation: &Vector3D) -> Option<Self> {
+        let inverse = m.inverse()?;
+        let normal = inverse.transpose() * self.normal;
+        // Carry a point across to pin down the new distance, the closest
+        // point to the origin, which also works for non-unit normals
+        let point = *m * (self.normal * (-self.distance / self.normal.magnitude_squared())) + *translation;
+        Some(Self::from_point_normal(point, normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!((a - b).magnitude() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_plane_from_points() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(4.0, -1.0, 2.0);
+        let c = Vector3D::new(0.0, 5.0, -2.0);
+        let plane = Plane::from_points(a, b, c).unwrap();
+        for p in [a, b, c] {
+            assert!(plane.signed_distance(&p).abs() < 1e-12);
+        }
+        assert!((plane.normal.magnitude() - 1.0).abs() < 1e-12);
+
+        // Collinear and coincident points have no plane
+        assert!(Plane::from_points(a, a * 2.0, a * 3.0).is_none());
+        assert!(Plane::from_points(a, a, a).is_none());
+    }
+
+    #[test]
+    fn test_plane_projection_reflection() {
+        let plane = Plane::from_point_normal(Vector3D::new(1.0, 1.0, 1.0), Vector3D::new(1.0, 2.0, -2.0));
+        let p = Vector3D::new(3.0, -4.0, 7.0);
+
+        let projected = plane.project_point(&p);
+        assert!(plane.signed_distance(&projected).abs() < 1e-12);
+
+        let reflected = plane.reflect_point(&p);
+        assert!((plane.signed_distance(&reflected) + plane.signed_distance(&p)).abs() < 1e-12);
+
+        let (m, t) = plane.reflection();
+        assert_close(m * p + t, reflected);
+        // Reflecting twice is the identity and the linear part has det -1
+        assert_close(m * (m * p + t) + t, p);
+        assert!((m.determinant() + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_plane_intersections() {
+        let a = Plane::from_point_normal(Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(1.0, 1.0, 0.0));
+        let b = Plane::from_point_normal(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(0.0, 1.0, 1.0));
+        let (point, direction) = a.intersect_plane(&b).unwrap();
+        for t in [-2.0, 0.0, 3.0] {
+            let p = point + direction * t;
+            assert!(a.signed_distance(&p).abs() < 1e-12);
+            assert!(b.signed_distance(&p).abs() < 1e-12);
+        }
+
+        // Parallel planes, in either orientation, do not meet
+        assert!(a.intersect_plane(&a).is_none());
+        assert!(a.intersect_plane(&a.flipped()).is_none());
+
+        let c = Plane::from_point_normal(Vector3D::new(0.0, -1.0, 0.0), Vector3D::new(1.0, -3.0, 0.5));
+        let p = Plane::intersect_planes(&a, &b, &c).unwrap();
+        for plane in [a, b, c] {
+            assert!(plane.signed_distance(&p).abs() < 1e-12);
+        }
+
+        // Three planes through a common line have no unique point
+        let d = Plane::from_point_normal(point, a.normal + b.normal);
+        assert!(Plane::intersect_planes(&a, &b, &d).is_none());
+    }
+
+    #[test]
+    fn test_plane_transform() {
+        let plane = Plane::from_points(
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+        )
+        .unwrap();
+        // Non-uniform scale plus shear, where transforming the normal
+        // directly would be wrong
+        let m = Matrix3D::new(2.0, 0.5, 0.0,
+                              0.0, 1.0, 0.0,
+                              0.0, 0.0, 4.0);
+        let t = Vector3D::new(1.0, -2.0, 3.0);
+        let moved = plane.transform(&m, &t).unwrap();
+
+        for p in [
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+            Vector3D::new(0.5, 0.25, 0.25),
+        ] {
+            assert!(moved.signed_distance(&(m * p + t)).abs() < 1e-12);
+        }
+        assert!((moved.normal.magnitude() - 1.0).abs() < 1e-12);
+
+        assert!(plane.transform(&Matrix3D::default(), &t).is_none());
+
+        // Non-unit normal, as frustum extraction produces: 2z - 4 = 0 is z = 2
+        let plane = Plane::new(Vector3D::new(0.0, 0.0, 2.0), -4.0);
+        let moved = plane.transform(&m, &t).unwrap();
+        for p in [Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(3.0, -1.0, 2.0)] {
+            assert!(moved.signed_distance(&(m * p + t)).abs() < 1e-12);
+        }
+        assert!((moved.distance + 11.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,355 @@
+use super::{Matrix4D, Plane};
+
+/// The range clip-space depth is mapped to after the perspective divide
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthRange {
+    /// Direct3D, Vulkan and Metal: near at 0, far at 1
+    #[default]
+    ZeroToOne,
+    /// OpenGL: near at -1, far at 1
+    NegativeOneToOne,
+}
+
+/// Which way the camera looks down its local z axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Handedness {
+    /// OpenGL and most modelling tools: the camera looks down -z
+    #[default]
+    Right,
+    /// Direct3D convention: the camera looks down +z
+    Left,
+}
+
+/// Clip-space conventions shared by all projection builders
+///
+/// The default is right-handed with depth 0..1 and near mapped to 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProjectionOptions {
+    pub depth: DepthRange,
+    /// Maps near to the far end of the depth range and far to the near
+    /// end, which spreads floating-point depth precision far more evenly
+    pub reverse_z: bool,
+    pub handedness: Handedness,
+}
+
+impl ProjectionOptions {
+    /// Creates a new ProjectionOptions
+    ///
+    /// # Arguments
+    /// - `depth` - The depth range clip space maps to
+    /// - `reverse_z` - Whether near and far swap ends of the range
+    /// - `handedness` - Which way the camera looks
+    pub fn new(depth: DepthRange, reverse_z: bool, handedness: Handedness) -> Self {
+        Self { depth, reverse_z, handedness }
+    }
+
+    /// The NDC depths of the near and far planes
+    pub(crate) fn depths(&self) -> (f64, f64) {
+        let (near, far) = match self.depth {
+            DepthRange::ZeroToOne => (0.0, 1.0),
+            DepthRange::NegativeOneToOne => (-1.0, 1.0),
+        };
+        if self.reverse_z { (far, near) } else { (near, far) }
+    }
+
+    /// Sign taking view-space z to distance in front of the camera
+    pub(crate) fn forward(&self) -> f64 {
+        match self.handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        }
+    }
+}
+
+/// Returns a perspective projection from a vertical field of view
+///
+/// # Arguments
+/// - `fov_y` - The full vertical field of view in radians
+/// - `aspect` - Width divided by height
+/// - `near` - Distance to the near plane, must be positive
+/// - `far` - Distance to the far plane, may be `f64::INFINITY`
+/// - `options` - Depth range, reverse-Z and handedness
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{perspective, ProjectionOptions, Vector3D};
+///
+/// let m = perspective(std::f64::consts::FRAC_PI_2, 1.0, 1.0, 10.0, &ProjectionOptions::default());
+/// let corner = m.project_point(&Vector3D::new(1.0, 1.0, -1.0)).unwrap();
+/// assert!((corner - Vector3D::new(1.0, 1.0, 0.0)).magnitude() < 1e-12);
+/// assert_eq!(m.project_point(&Vector3D::new(0.0, 0.0, -10.0)).unwrap().z, 1.0);
+/// ```
+pub fn perspective(fov_y: f64, aspect: f64, near: f64, far: f64, options: &ProjectionOptions) -> Matrix4D {
+    let top = near * (fov_y * 0.5).tan();
+    let right = top * aspect;
+    perspective_off_center(-right, right, -top, top, near, far, options)
+}
+
+/// Returns a perspective projection from the bounds of the near plane
+///
+/// The bounds need not be symmetric, which gives the sheared frusta used
+/// for stereo rendering and tiled displays.
+///
+/// # Arguments
+/// - `left` - The x coordinate of the left edge at the near plane
+/// - `right` - The x coordinate of the right edge at the near plane
+/// - `bottom` - The y coordinate of the bottom edge at the near plane
+/// - `top` - The y coordinate of the top edge at the near plane
+/// - `near` - Distance to the near plane, must be positive
+/// - `far` - Distance to the far plane, may be `f64::INFINITY`
+/// - `options` - Depth range, reverse-Z and handedness
+#[allow(clippy::too_many_arguments)]
+pub fn perspective_off_center(
+    left: f64,
+    right: f64,
+    bottom: f64,
+    top: f64,
+    near: f64,
+    far: f64,
+    options: &ProjectionOptions,
+) -> Matrix4D {
+    let s = options.forward();
+    let (d_near, d_far) = options.depths();
+    // NDC depth is a + b / distance, pinned to d_near and d_far
+    let b = if far.is_finite() {
+        (d_near - d_far) * near * far / (far - near)
+    } else {
+        (d_near - d_far) * near
+    };
+    let a = if far.is_finite() { d_far - b / far } else { d_far };
+
+    let width = right - left;
+    let height = top - bottom;
+    Matrix4D::new(2.0 * near / width, 0.0, -s * (right + left) / width, 0.0,
+                  0.0, 2.0 * near / height, -s * (top + bottom) / height, 0.0,
+                  0.0, 0.0, s * a, b,
+                  0.0, 0.0, s, 0.0)
+}
+
+/// Returns an orthographic projection of the given view-space box
+///
+/// Depth is linear in an orthographic projection, so unlike `perspective`
+/// there is no infinite far plane. Returns `None` if `far` is not finite.
+///
+/// # Arguments
+/// - `left` - The x coordinate of the left edge
+/// - `right` - The x coordinate of the right edge
+/// - `bottom` - The y coordinate of the bottom edge
+/// - `top` - The y coordinate of the top edge
+/// - `near` - Distance to the near plane, may be zero or negative
+/// - `far` - Distance to the far plane, must be finite
+/// - `options` - Depth range, reverse-Z and handedness
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{orthographic, ProjectionOptions, Vector3D};
+///
+/// let m = orthographic(-4.0, 4.0, -2.0, 2.0, 0.0, 10.0, &ProjectionOptions::default()).unwrap();
+/// assert_eq!(m.project_point(&Vector3D::new(2.0, -2.0, -5.0)), Some(Vector3D::new(0.5, -1.0, 0.5)));
+/// assert!(orthographic(-4.0, 4.0, -2.0, 2.0, 0.0, f64::INFINITY, &ProjectionOptions::default()).is_none());
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn orthographic(
+    left: f64,
+    right: f64,
+    bottom: f64,
+    top: f64,
+    near: f64,
+    far: f64,
+    options: &ProjectionOptions,
+) -> Option<Matrix4D> {
+    if !far.is_finite() {
+        return None;
+    }
+    let s = options.forward();
+    let (d_near, d_far) = options.depths();
+    let width = right - left;
+    let height = top - bottom;
+    let scale = (d_far - d_near) / (far - near);
+    Some(Matrix4D::new(2.0 / width, 0.0, 0.0, -(right + left) / width,
+                       0.0, 2.0 / height, 0.0, -(top + bottom) / height,
+                       0.0, 0.0, s * scale, d_near - scale * near,
+                       0.0, 0.0, 0.0, 1.0))
+}
+
+/// Replaces the near plane of a perspective projection with `clip_plane`
+///
+/// Used for mirrors and portals, where geometry behind the reflecting
+/// surface must not be drawn. Follows Lengyel's oblique near-plane method:
+/// the far plane is tilted so that it still passes through the corner of
+/// the original frustum furthest from the new near plane, keeping as much
+/// depth precision as possible.
+///
+/// Returns `None` if `projection` cannot be inverted.
+///
+/// # Arguments
+/// - `projection` - A projection built with the same `options`
+/// - `clip_plane` - The new near plane in view space, its normal facing
+///   the visible side and away from the camera
+/// - `options` - The options `projection` was built with
+///
+/// # Example
+/// ```
+/// use game_maths::maths::{oblique_near_plane, perspective, Plane, ProjectionOptions, Vector3D};
+///
+/// let options = ProjectionOptions::default();
+/// let m = perspective(1.2, 1.0, 0.1, 100.0, &options);
+/// // A mirror tilted across the view, 5 units away
+/// let mirror = Plane::from_point_normal(Vector3D::new(0.0, 0.0, -5.0), Vector3D::new(0.0, 0.6, -0.8));
+/// let oblique = oblique_near_plane(&m, &mirror, &options).unwrap();
+/// let on_mirror = oblique.project_point(&Vector3D::new(1.0, 2.0, -3.5)).unwrap();
+/// assert!(on_mirror.z.abs() < 1e-9);
+/// ```
+pub fn oblique_near_plane(projection: &Matrix4D, clip_plane: &Plane, options: &ProjectionOptions) -> Option<Matrix4D> {
+    let (d_near, d_far) = options.depths();
+    let c = [clip_plane.normal.x, clip_plane.normal.y, clip_plane.normal.z, clip_plane.distance];
+
+    // The clip-space corner opposite the clip plane, taken back to view space
+    let corner = [c[0].signum(), c[1].signum(), d_far, 1.0];
+    let q = projection.inverse()?.transform_homogeneous(&corner);
+    let c_dot_q: f64 = (0..4).map(|i| c[i] * q[i]).sum();
+    let scale = (d_far - d_near) / c_dot_q;
+
+    let mut m = *projection;
+    for j in 0..4 {
+        m[(2,j)] = d_near * projection[(3,j)] + scale * c[j];
+    }
+    Some(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::Vector3D;
+
+    fn assert_projects(m: &Matrix4D, point: Vector3D, expected: Vector3D) {
+        let ndc = m.project_point(&point).unwrap();
+        assert!((ndc - expected).magnitude() < 1e-9, "{:?} -> {:?}, expected {:?}", point, ndc, expected);
+    }
+
+    const QUARTER: f64 = std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_perspective_depth_ranges() {
+        let cases = [
+            (DepthRange::ZeroToOne, false, 0.0, 1.0),
+            (DepthRange::NegativeOneToOne, false, -1.0, 1.0),
+            (DepthRange::ZeroToOne, true, 1.0, 0.0),
+            (DepthRange::NegativeOneToOne, true, 1.0, -1.0),
+        ];
+        for (depth, reverse_z, near_ndc, far_ndc) in cases {
+            let options = ProjectionOptions::new(depth, reverse_z, Handedness::Right);
+            let m = perspective(QUARTER, 2.0, 1.0, 10.0, &options);
+            assert_projects(&m, Vector3D::new(2.0, 1.0, -1.0), Vector3D::new(1.0, 1.0, near_ndc));
+            assert_projects(&m, Vector3D::new(-20.0, 0.0, -10.0), Vector3D::new(-1.0, 0.0, far_ndc));
+            // Depth is hyperbolic: halfway in distance is well past halfway in NDC
+            let middle = m.project_point(&Vector3D::new(0.0, 0.0, -5.5)).unwrap().z;
+            let fraction = (middle - near_ndc) / (far_ndc - near_ndc);
+            assert!((fraction - 10.0 / 11.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_perspective_handedness() {
+        let right = perspective(QUARTER, 1.0, 1.0, 10.0, &ProjectionOptions::default());
+        let left_options = ProjectionOptions::new(DepthRange::ZeroToOne, false, Handedness::Left);
+        let left = perspective(QUARTER, 1.0, 1.0, 10.0, &left_options);
+        for p in [Vector3D::new(0.5, -1.0, 1.0), Vector3D::new(3.0, 2.0, 7.0)] {
+            let mirrored = Vector3D::new(p.x, p.y, -p.z);
+            assert_eq!(left.project_point(&p), right.project_point(&mirrored));
+        }
+        // Behind the camera the perspective divide flips signs
+        assert!(left.project_point(&Vector3D::new(0.0, 0.0, -5.0)).unwrap().z > 1.0);
+    }
+
+    #[test]
+    fn test_perspective_infinite_far() {
+        for reverse_z in [false, true] {
+            let options = ProjectionOptions::new(DepthRange::ZeroToOne, reverse_z, Handedness::Right);
+            let m = perspective(QUARTER, 1.0, 0.5, f64::INFINITY, &options);
+            let (near_ndc, far_ndc) = if reverse_z { (1.0, 0.0) } else { (0.0, 1.0) };
+            assert_projects(&m, Vector3D::new(0.5, 0.5, -0.5), Vector3D::new(1.0, 1.0, near_ndc));
+            let distant = m.project_point(&Vector3D::new(0.0, 0.0, -1.0e12)).unwrap();
+            assert!((distant.z - far_ndc).abs() < 1e-9);
+            // Reverse-Z stores 0.5 / distance directly
+            if reverse_z {
+                assert_projects(&m, Vector3D::new(0.0, 0.0, -50.0), Vector3D::new(0.0, 0.0, 0.01));
+            }
+        }
+    }
+
+    #[test]
+    fn test_perspective_off_center() {
+        let m = perspective_off_center(-1.0, 3.0, 0.0, 2.0, 2.0, 20.0, &ProjectionOptions::default());
+        assert_projects(&m, Vector3D::new(-1.0, 0.0, -2.0), Vector3D::new(-1.0, -1.0, 0.0));
+        assert_projects(&m, Vector3D::new(30.0, 20.0, -20.0), Vector3D::new(1.0, 1.0, 1.0));
+        assert_projects(&m, Vector3D::new(1.0, 1.0, -2.0), Vector3D::new(0.0, 0.0, 0.0));
+
+        let options = ProjectionOptions::new(DepthRange::NegativeOneToOne, false, Handedness::Left);
+        let m = perspective_off_center(-1.0, 3.0, 0.0, 2.0, 2.0, 20.0, &options);
+        assert_projects(&m, Vector3D::new(3.0, 2.0, 2.0), Vector3D::new(1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_orthographic() {
+        let m = orthographic(-4.0, 4.0, 0.0, 2.0, 1.0, 11.0, &ProjectionOptions::default()).unwrap();
+        assert_projects(&m, Vector3D::new(-4.0, 0.0, -1.0), Vector3D::new(-1.0, -1.0, 0.0));
+        assert_projects(&m, Vector3D::new(4.0, 2.0, -11.0), Vector3D::new(1.0, 1.0, 1.0));
+        assert_projects(&m, Vector3D::new(0.0, 1.0, -6.0), Vector3D::new(0.0, 0.0, 0.5));
+
+        let options = ProjectionOptions::new(DepthRange::NegativeOneToOne, true, Handedness::Left);
+        let m = orthographic(-4.0, 4.0, 0.0, 2.0, 1.0, 11.0, &options).unwrap();
+        assert_projects(&m, Vector3D::new(-4.0, 0.0, 1.0), Vector3D::new(-1.0, -1.0, 1.0));
+        assert_projects(&m, Vector3D::new(0.0, 1.0, 11.0), Vector3D::new(0.0, 0.0, -1.0));
+
+        // There is no infinite far plane with linear depth
+        for far in [f64::INFINITY, f64::NAN] {
+            assert!(orthographic(-4.0, 4.0, 0.0, 2.0, 1.0, far, &options).is_none());
+        }
+    }
+
+    #[test]
+    fn test_oblique_near_plane() {
+        let cases = [
+            (DepthRange::ZeroToOne, false, Handedness::Right),
+            (DepthRange::NegativeOneToOne, false, Handedness::Right),
+            (DepthRange::ZeroToOne, true, Handedness::Right),
+            (DepthRange::NegativeOneToOne, true, Handedness::Right),
+            (DepthRange::ZeroToOne, false, Handedness::Left),
+            (DepthRange::NegativeOneToOne, true, Handedness::Left),
+        ];
+        for (depth, reverse_z, handedness) in cases {
+            let options = ProjectionOptions::new(depth, reverse_z, handedness);
+            let (near_ndc, far_ndc) = options.depths();
+            // The same tilted mirror 5 units in front, whichever way the camera looks
+            let s = options.forward();
+            let mirror = Plane::from_point_normal(Vector3D::new(0.0, 0.0, 5.0 * s), Vector3D::new(0.3, 0.2, s));
+            let on_plane = |x: f64, y: f64| {
+                let z = -(mirror.distance + mirror.normal.x * x + mirror.normal.y * y) / mirror.normal.z;
+                Vector3D::new(x, y, z)
+            };
+            for far in [100.0, f64::INFINITY] {
+                let m = perspective(1.0, 1.5, 0.1, far, &options);
+                let oblique = oblique_near_plane(&m, &mirror, &options).unwrap();
+                for (x, y) in [(0.0, 0.0), (1.0, -1.0), (-2.0, 0.5)] {
+                    let ndc = oblique.project_point(&on_plane(x, y)).unwrap();
+                    assert!((ndc.z - near_ndc).abs() < 1e-9, "{:?} {} {:?} {:?}", depth, reverse_z, handedness, ndc);
+                }
+                // x and y are untouched
+                let p = Vector3D::new(1.0, 2.0, 20.0 * s);
+                let (a, b) = (m.project_point(&p).unwrap(), oblique.project_point(&p).unwrap());
+                assert!((a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12);
+                // Points beyond the mirror stay inside the depth range
+                let inside = (b.z - near_ndc) / (far_ndc - near_ndc);
+                assert!(inside > 0.0 && inside < 1.0);
+            }
+
+            // The far corner of the original frustum opposite the mirror keeps its depth
+            let m = perspective(1.0, 1.5, 0.1, 100.0, &options);
+            let oblique = oblique_near_plane(&m, &mirror, &options).unwrap();
+            let corner = m.inverse().unwrap().project_point(&Vector3D::new(1.0, 1.0, far_ndc)).unwrap();
+            assert!((corner.z - 100.0 * s).abs() < 1e-9);
+            assert_projects(&oblique, corner, Vector3D::new(1.0, 1.0, far_ndc));
+        }
+    }
+}
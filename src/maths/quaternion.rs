@@ -0,0 +1,499 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::{Matrix3D, Vector3D, EPSILON};
+
+/// Cosine of the angle between quaternions above which slerp falls back on
+/// nlerp, as `sin` of the angle is too small to divide by accurately
+const SLERP_THRESHOLD: f64 = 1.0 - 1.0e-6;
+
+/// Quaternion `w + xi + yj + zk`
+///
+/// Unit quaternions represent rotations. `q` and `-q` are the same
+/// rotation, so the interpolation functions pick whichever of the two is
+/// closer to take the shortest path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Hamilton product, `a * b` rotates by `b` then by `a`
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        }
+    }
+}
+
+impl Mul<f64> for Quaternion {
+    type Output = Self;
+
+    fn mul(self, s: f64) -> Self::Output {
+        Self::new(self.x * s, self.y * s, self.z * s, self.w * s)
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z, self.w + rhs.w)
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z, self.w - rhs.w)
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl Quaternion {
+    /// Creates a new Quaternion from its components
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Returns the identity rotation
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Creates a rotation of `angle` radians about `axis`, counter-clockwise
+    /// looking down the axis
+    ///
+    /// # Arguments
+    /// - `axis` - The rotation axis, does not need to be unit length
+    /// - `angle` - The rotation angle in radians
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Quaternion, Vector3D};
+    ///
+    /// let q = Quaternion::from_axis_angle(&Vector3D::new(0.0, 0.0, 2.0), std::f64::consts::FRAC_PI_2);
+    /// let v = q.rotate(&Vector3D::new(1.0, 0.0, 0.0));
+    /// assert!((v - Vector3D::new(0.0, 1.0, 0.0)).magnitude() < 1e-15);
+    /// ```
+    pub fn from_axis_angle(axis: &Vector3D, angle: f64) -> Self {
+        let length = axis.magnitude();
+        if length == 0.0 {
+            return Self::identity();
+        }
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        let v = *axis * (sin / length);
+        Self::new(v.x, v.y, v.z, cos)
+    }
+
+    /// Creates the rotation a rotation matrix performs
+    ///
+    /// Uses Shepperd's method, choosing the largest component to divide by
+    /// so every rotation converts accurately.
+    pub fn from_matrix(m: &Matrix3D) -> Self {
+        let trace = m[(0, 0)] + m[(1, 1)] + m[(2, 2)];
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new((m[(2, 1)] - m[(1, 2)]) / s, (m[(0, 2)] - m[(2, 0)]) / s, (m[(1, 0)] - m[(0, 1)]) / s, 0.25 * s)
+        } else if m[(0, 0)] > m[(1, 1)] && m[(0, 0)] > m[(2, 2)] {
+            let s = (1.0 + m[(0, 0)] - m[(1, 1)] - m[(2, 2)]).sqrt() * 2.0;
+            Self::new(0.25 * s, (m[(0, 1)] + m[(1, 0)]) / s, (m[(0, 2)] + m[(2, 0)]) / s, (m[(2, 1)] - m[(1, 2)]) / s)
+        } else if m[(1, 1)] > m[(2, 2)] {
+            let s = (1.0 + m[(1, 1)] - m[(0, 0)] - m[(2, 2)]).sqrt() * 2.0;
+            Self::new((m[(0, 1)] + m[(1, 0)]) / s, 0.25 * s, (m[(1, 2)] + m[(2, 1)]) / s, (m[(0, 2)] - m[(2, 0)]) / s)
+        } else {
+            let s = (1.0 + m[(2, 2)] - m[(0, 0)] - m[(1, 1)]).sqrt() * 2.0;
+            Self::new((m[(0, 2)] + m[(2, 0)]) / s, (m[(1, 2)] + m[(2, 1)]) / s, 0.25 * s, (m[(1, 0)] - m[(0, 1)]) / s)
+        };
+        q.normalise()
+    }
+
+    /// Returns the rotation matrix of a unit quaternion
+    pub fn to_matrix(&self) -> Matrix3D {
+        let Self { x, y, z, w } = *self;
+        Matrix3D::new(
+            1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y),
+            2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x),
+            2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y),
+        )
+    }
+
+    /// Returns the vector part
+    pub fn vector(&self) -> Vector3D {
+        Vector3D::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the four-dimensional dot product
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Returns the length
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the quaternion scaled to unit length, or the identity if it is zero
+    pub fn normalise(&self) -> Self {
+        let length = self.magnitude();
+        if length == 0.0 {
+            return Self::identity();
+        }
+        *self * (1.0 / length)
+    }
+
+    /// Returns the conjugate, the inverse rotation for unit quaternions
+    pub fn conjugate(&self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the multiplicative inverse, or None for a zero quaternion
+    pub fn inverse(&self) -> Option<Self> {
+        let length_squared = self.dot(self);
+        (length_squared > 0.0).then(|| self.conjugate() * (1.0 / length_squared))
+    }
+
+    /// Rotates a vector by a unit quaternion
+    pub fn rotate(&self, v: &Vector3D) -> Vector3D {
+        // v + 2w(u x v) + 2u x (u x v), without building the matrix
+        let u = self.vector();
+        let t = u.cross(v) * 2.0;
+        *v + t * self.w + u.cross(&t)
+    }
+
+    /// Returns the logarithm of a unit quaternion, a pure quaternion whose
+    /// vector part is the rotation axis times half the angle
+    pub fn log(&self) -> Self {
+        let v = self.vector();
+        let sin = v.magnitude();
+        if sin <= EPSILON {
+            return Self::new(v.x, v.y, v.z, 0.0);
+        }
+        let v = v * (sin.atan2(self.w) / sin);
+        Self::new(v.x, v.y, v.z, 0.0)
+    }
+
+    /// Returns the exponential of a pure quaternion, inverting `log`
+    pub fn exp(&self) -> Self {
+        let v = self.vector();
+        let angle = v.magnitude();
+        let scale = if angle <= EPSILON { 1.0 } else { angle.sin() / angle };
+        let v = v * scale;
+        Self::new(v.x, v.y, v.z, angle.cos()) * self.w.exp()
+    }
+
+    /// Returns the angle in radians of the smallest rotation taking one
+    /// orientation to the other, between 0 and pi
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Quaternion, Vector3D};
+    ///
+    /// let a = Quaternion::from_axis_angle(&Vector3D::new(0.0, 1.0, 0.0), 0.25);
+    /// let b = Quaternion::from_axis_angle(&Vector3D::new(0.0, 1.0, 0.0), 1.0);
+    /// assert!((a.angular_distance(&b) - 0.75).abs() < 1e-15);
+    /// // q and -q are the same orientation
+    /// assert_eq!(b.angular_distance(&-b), 0.0);
+    /// ```
+    pub fn angular_distance(&self, other: &Self) -> f64 {
+        let delta = self.conjugate() * *other;
+        // atan2 keeps full precision for tiny angles, unlike acos
+        2.0 * delta.vector().magnitude().atan2(delta.w.abs())
+    }
+
+    /// Spherical linear interpolation along the shorter arc
+    ///
+    /// Turns at a constant angular velocity. Nearly equal orientations use
+    /// `nlerp` instead, which is indistinguishable there and avoids
+    /// dividing by a vanishing sine.
+    ///
+    /// # Arguments
+    /// - `other` - The orientation at `t == 1`
+    /// - `t` - The interpolation parameter, usually in 0..1
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Quaternion, Vector3D};
+    ///
+    /// let axis = Vector3D::new(1.0, 1.0, 0.0);
+    /// let a = Quaternion::from_axis_angle(&axis, 0.2);
+    /// let b = Quaternion::from_axis_angle(&axis, 1.8);
+    /// let q = a.slerp(&b, 0.25);
+    /// assert!(q.angular_distance(&Quaternion::from_axis_angle(&axis, 0.6)) < 1e-12);
+    /// ```
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let other = if self.dot(other) < 0.0 { -*other } else { *other };
+        slerp_unflipped(self, &other, t)
+    }
+
+    /// Normalised linear interpolation along the shorter arc
+    ///
+    /// Cheaper than `slerp` and follows the same path, but speeds up in the
+    /// middle of wide arcs.
+    pub fn nlerp(&self, other: &Self, t: f64) -> Self {
+        let other = if self.dot(other) < 0.0 { -*other } else { *other };
+        (*self * (1.0 - t) + other * t).normalise()
+    }
+
+    /// Spherical quadrangle interpolation between `self` and `other`
+    ///
+    /// `a` and `b` are inner control points, usually from
+    /// `squad_control_point`, that shape the tangents at the two ends.
+    ///
+    /// # Arguments
+    /// - `other` - The orientation at `t == 1`
+    /// - `a` - The control point after `self`
+    /// - `b` - The control point before `other`
+    /// - `t` - The interpolation parameter in 0..1
+    pub fn squad(&self, other: &Self, a: &Self, b: &Self, t: f64) -> Self {
+        let outer = slerp_unflipped(self, other, t);
+        let inner = slerp_unflipped(a, b, t);
+        slerp_unflipped(&outer, &inner, 2.0 * t * (1.0 - t))
+    }
+
+    /// Returns the squad control point at key `self` between its neighbours
+    ///
+    /// The neighbours should already be on the same hemisphere as `self`.
+    pub fn squad_control_point(&self, previous: &Self, next: &Self) -> Self {
+        let inverse = self.conjugate();
+        let to_next = (inverse * *next).log();
+        let to_previous = (inverse * *previous).log();
+        *self * ((to_next + to_previous) * -0.25).exp()
+    }
+}
+
+/// Slerp that follows whichever arc the inputs are on, needed inside squad
+/// where flipping a control point would break the tangents
+fn slerp_unflipped(a: &Quaternion, b: &Quaternion, t: f64) -> Quaternion {
+    let cos = a.dot(b);
+    if cos.abs() > SLERP_THRESHOLD {
+        return (*a * (1.0 - t) + *b * t).normalise();
+    }
+    let angle = cos.clamp(-1.0, 1.0).acos();
+    let sin = angle.sin();
+    (*a * (((1.0 - t) * angle).sin() / sin) + *b * ((t * angle).sin() / sin)).normalise()
+}
+
+/// Smooth rotation spline through a sequence of orientations using squad
+///
+/// Key `i` is reached at `t == i`. The curve turns smoothly through every
+/// key, with continuous angular velocity when the keys are evenly spaced
+/// in time. The ends use the keys as their own control points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuaternionSpline {
+    keys: Vec<Quaternion>,
+    controls: Vec<Quaternion>,
+}
+
+impl QuaternionSpline {
+    /// Creates a QuaternionSpline, or None without any keys
+    ///
+    /// Keys are normalised and flipped where needed so each is on the same
+    /// hemisphere as the one before, giving the shortest path between them.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Quaternion, QuaternionSpline, Vector3D};
+    ///
+    /// let keys: Vec<Quaternion> = [0.0, 0.5, 1.5, 2.0]
+    ///     .iter()
+    ///     .map(|&angle| Quaternion::from_axis_angle(&Vector3D::new(0.0, 0.0, 1.0), angle))
+    ///     .collect();
+    /// let spline = QuaternionSpline::new(keys.clone()).unwrap();
+    /// assert_eq!(spline.domain(), (0.0, 3.0));
+    /// assert!(spline.evaluate(2.0).angular_distance(&keys[2]) < 1e-12);
+    /// ```
+    pub fn new(keys: Vec<Quaternion>) -> Option<Self> {
+        if keys.is_empty() {
+            return None;
+        }
+        let mut aligned: Vec<Quaternion> = Vec::with_capacity(keys.len());
+        for key in keys {
+            let key = key.normalise();
+            let key = match aligned.last() {
+                Some(previous) if previous.dot(&key) < 0.0 => -key,
+                _ => key,
+            };
+            aligned.push(key);
+        }
+        let last = aligned.len() - 1;
+        let controls = (0..aligned.len())
+            .map(|i| {
+                if i == 0 || i == last {
+                    aligned[i]
+                } else {
+                    aligned[i].squad_control_point(&aligned[i - 1], &aligned[i + 1])
+                }
+            })
+            .collect();
+        Some(Self { keys: aligned, controls })
+    }
+
+    /// Returns the keys after hemisphere alignment
+    pub fn keys(&self) -> &[Quaternion] {
+        &self.keys
+    }
+
+    /// Returns the range of `t` the spline covers
+    pub fn domain(&self) -> (f64, f64) {
+        (0.0, (self.keys.len() - 1) as f64)
+    }
+
+    /// Returns the orientation at `t`, clamped to the domain
+    pub fn evaluate(&self, t: f64) -> Quaternion {
+        let last = self.keys.len() - 1;
+        if last == 0 || t <= 0.0 {
+            return self.keys[0];
+        }
+        if t >= last as f64 {
+            return self.keys[last];
+        }
+        let i = t.floor() as usize;
+        let u = t - i as f64;
+        self.keys[i].squad(&self.keys[i + 1], &self.controls[i], &self.controls[i + 1], u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::TestRng;
+    use std::f64::consts::PI;
+
+    fn random_rotations(count: usize, seed: u64) -> Vec<Quaternion> {
+        let mut rng = TestRng::new(seed);
+        (0..count).map(|_| Quaternion::new(rng.signed(), rng.signed(), rng.signed(), rng.signed()).normalise()).collect()
+    }
+
+    /// Angular velocity in world space from a finite difference
+    fn angular_velocity(a: &Quaternion, b: &Quaternion, dt: f64) -> Vector3D {
+        let b = if a.dot(b) < 0.0 { -*b } else { *b };
+        ((b * a.conjugate()).log().vector()) * (2.0 / dt)
+    }
+
+    #[test]
+    fn test_quaternion_rotation_matrix() {
+        for q in random_rotations(50, 1) {
+            let m = q.to_matrix();
+            assert!((m.determinant() - 1.0).abs() < 1e-12);
+            let v = Vector3D::new(0.3, -1.2, 2.0);
+            assert!((q.rotate(&v) - m * v).magnitude() < 1e-12);
+            let back = Quaternion::from_matrix(&m);
+            assert!(back.angular_distance(&q) < 1e-7);
+            assert!((back.dot(&q).abs() - 1.0).abs() < 1e-12);
+        }
+        let half_turn = Quaternion::from_axis_angle(&Vector3D::new(1.0, 1.0, 0.0), PI);
+        assert!(Quaternion::from_matrix(&half_turn.to_matrix()).angular_distance(&half_turn) < 1e-7);
+
+        let a = Quaternion::from_axis_angle(&Vector3D::new(1.0, 0.0, 0.0), 0.3);
+        let b = Quaternion::from_axis_angle(&Vector3D::new(0.0, 1.0, 0.0), 0.7);
+        let v = Vector3D::new(1.0, 2.0, 3.0);
+        assert!(((a * b).rotate(&v) - a.rotate(&b.rotate(&v))).magnitude() < 1e-12);
+        assert!((a.inverse().unwrap() * a).angular_distance(&Quaternion::identity()) < 1e-12);
+        assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inverse().is_none());
+        let log = b.log();
+        assert!((log.vector() - Vector3D::new(0.0, 0.35, 0.0)).magnitude() < 1e-15);
+        assert!((log.exp() - b).magnitude() < 1e-15);
+    }
+
+    #[test]
+    fn test_quaternion_slerp_constant_velocity() {
+        let rotations = random_rotations(20, 2);
+        for pair in rotations.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let total = a.angular_distance(&b);
+            assert!(a.slerp(&b, 0.0).angular_distance(&a) < 1e-7);
+            assert!(a.slerp(&b, 1.0).angular_distance(&b) < 1e-7);
+            // Same result from either sign of the target: the short way
+            assert!(a.slerp(&-b, 0.3).angular_distance(&a.slerp(&b, 0.3)) < 1e-12);
+
+            let steps = 16;
+            let mut previous = a;
+            for i in 1..=steps {
+                let q = a.slerp(&b, i as f64 / steps as f64);
+                assert!((q.magnitude() - 1.0).abs() < 1e-12);
+                assert!((previous.angular_distance(&q) - total / steps as f64).abs() < 1e-9);
+                assert!((a.angular_distance(&q) - total * i as f64 / steps as f64).abs() < 1e-9);
+                previous = q;
+            }
+
+            // nlerp follows the same arc but not at constant speed
+            let n = a.nlerp(&b, 0.25);
+            assert!((a.angular_distance(&n) + n.angular_distance(&b) - total).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_slerp_small_angles() {
+        let a = Quaternion::from_axis_angle(&Vector3D::new(0.2, 0.5, 1.0), 1.0);
+        for angle in [0.0, 1e-12, 1e-8, 1e-5] {
+            let b = a * Quaternion::from_axis_angle(&Vector3D::new(1.0, 0.0, 0.0), angle);
+            let q = a.slerp(&b, 0.5);
+            assert!(q.x.is_finite() && q.y.is_finite() && q.z.is_finite() && q.w.is_finite());
+            assert!((q.magnitude() - 1.0).abs() < 1e-15);
+            assert!((a.angular_distance(&q) - angle * 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_spline_c1() {
+        // Alternate signs to check the keys are aligned to the short path
+        let keys: Vec<Quaternion> = random_rotations(6, 3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, q)| if i % 2 == 0 { q } else { -q })
+            .collect();
+        let spline = QuaternionSpline::new(keys.clone()).unwrap();
+        for (i, key) in keys.iter().enumerate() {
+            assert!(spline.evaluate(i as f64).angular_distance(key) < 1e-7);
+        }
+        for pair in spline.keys().windows(2) {
+            assert!(pair[0].dot(&pair[1]) >= 0.0);
+        }
+
+        let h = 1e-6;
+        for i in 1..keys.len() - 1 {
+            let t = i as f64;
+            let before = angular_velocity(&spline.evaluate(t - h), &spline.evaluate(t), h);
+            let after = angular_velocity(&spline.evaluate(t), &spline.evaluate(t + h), h);
+            assert!((before - after).magnitude() < 1e-4 * (1.0 + before.magnitude()), "angular velocity jumps at key {}", i);
+        }
+        // Position is continuous everywhere, including inside segments
+        for i in 0..500 {
+            let t = i as f64 * 0.01;
+            assert!(spline.evaluate(t).angular_distance(&spline.evaluate(t + 1e-7)) < 1e-5);
+        }
+
+        // Keys on one great circle at even angles turn at a constant rate
+        let axis = Vector3D::new(0.0, 1.0, 1.0);
+        let even = QuaternionSpline::new((0..5).map(|i| Quaternion::from_axis_angle(&axis, i as f64 * 0.4)).collect()).unwrap();
+        for i in 0..40 {
+            let t = i as f64 * 0.1;
+            let speed = even.evaluate(t).angular_distance(&even.evaluate(t + 0.1)) / 0.1;
+            assert!((speed - 0.4).abs() < 1e-9);
+        }
+        assert!(QuaternionSpline::new(Vec::new()).is_none());
+        assert_eq!(QuaternionSpline::new(vec![keys[0]]).unwrap().evaluate(0.5), keys[0]);
+    }
+}
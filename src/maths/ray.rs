@@ -0,0 +1,625 @@
+use super::{Matrix3D, Plane, Vector3D, EPSILON};
+
+/// Half-line starting at `origin` and running along a unit `direction`
+///
+/// All intersection tests report the first point where the ray crosses the
+/// primitive's surface at `distance >= 0`. If the origin starts inside a solid
+/// (sphere, box, capsule or cylinder) that is the exit point. Normals always
+/// point out of solids; for the flat primitives (planes and triangles) they
+/// are flipped to face back against the ray.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vector3D,
+    pub direction: Vector3D,
+}
+
+/// Result of a ray intersection test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Distance along the ray, so the hit point is `ray.at(distance)`
+    pub distance: f64,
+    /// Unit surface normal at the hit point
+    pub normal: Vector3D,
+}
+
+/// Result of a ray-triangle intersection test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit {
+    /// Distance along the ray, so the hit point is `ray.at(distance)`
+    pub distance: f64,
+    /// Unit triangle normal, facing back against the ray
+    pub normal: Vector3D,
+    /// Barycentric weights of the hit point for vertices `(a, b, c)`
+    pub barycentric: Vector3D,
+}
+
+impl Ray {
+    /// Creates a new Ray, normalising `direction`, or None if `direction`
+    /// is zero or not finite
+    ///
+    /// # Arguments
+    /// - `origin` - The start point of the ray
+    /// - `direction` - The direction of the ray, does not need to be unit length
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 0.0, 5.0)).unwrap();
+    /// assert_eq!(ray.direction, Vector3D::new(0.0, 0.0, 1.0));
+    /// assert_eq!(ray.at(2.0), Vector3D::new(1.0, 0.0, 2.0));
+    /// ```
+    pub fn new(origin: Vector3D, direction: Vector3D) -> Option<Self> {
+        let length = direction.magnitude();
+        (length > 0.0 && length.is_finite()).then(|| Self { origin, direction: direction / length })
+    }
+
+    /// Returns the point at `distance` along the ray
+    pub fn at(&self, distance: f64) -> Vector3D {
+        self.origin + self.direction * distance
+    }
+
+    /// Intersects the ray with a sphere
+    ///
+    /// A ray that just touches the sphere (a grazing hit) counts as a hit.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(0.0, 0.0, -5.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+    /// let hit = ray.intersect_sphere(&Vector3D::default(), 1.0).unwrap();
+    /// assert_eq!(hit.distance, 4.0);
+    /// assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+    /// ```
+    pub fn intersect_sphere(&self, center: &Vector3D, radius: f64) -> Option<RayHit> {
+        let distance = sphere_roots(self, center, radius)?.into_iter().find(|&t| t >= 0.0)?;
+        Some(RayHit {
+            distance,
+            normal: (self.at(distance) - *center) / radius,
+        })
+    }
+
+    /// Intersects the ray with a plane, from either side
+    ///
+    /// Rays parallel to the plane never hit, even if they lie in it.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Plane, Ray, Vector3D};
+    ///
+    /// let plane = Plane::new(Vector3D::new(0.0, 1.0, 0.0), 0.0);
+    /// let ray = Ray::new(Vector3D::new(0.0, 3.0, 0.0), Vector3D::new(0.0, -1.0, 0.0)).unwrap();
+    /// let hit = ray.intersect_plane(&plane).unwrap();
+    /// assert_eq!(hit.distance, 3.0);
+    /// assert_eq!(hit.normal, Vector3D::new(0.0, 1.0, 0.0));
+    /// ```
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<RayHit> {
+        let denominator = plane.normal.dot(&self.direction);
+        if denominator.abs() <= EPSILON {
+            return None;
+        }
+        let distance = -plane.signed_distance(&self.origin) / denominator;
+        if distance < 0.0 {
+            return None;
+        }
+        Some(RayHit {
+            distance,
+            normal: facing(plane.normal, denominator),
+        })
+    }
+
+    /// Intersects the ray with the triangle `(a, b, c)` using Möller–Trumbore
+    ///
+    /// Both faces are hit. Rays through an edge or vertex count as a hit and
+    /// rays parallel to the triangle's plane never hit.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(0.25, 0.25, 1.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+    /// let hit = ray.intersect_triangle(
+    ///     &Vector3D::new(0.0, 0.0, 0.0),
+    ///     &Vector3D::new(1.0, 0.0, 0.0),
+    ///     &Vector3D::new(0.0, 1.0, 0.0),
+    /// ).unwrap();
+    /// assert_eq!(hit.distance, 1.0);
+    /// assert_eq!(hit.barycentric, Vector3D::new(0.5, 0.25, 0.25));
+    /// ```
+    pub fn intersect_triangle(&self, a: &Vector3D, b: &Vector3D, c: &Vector3D) -> Option<TriangleHit> {
+        let edge_1 = *b - *a;
+        let edge_2 = *c - *a;
+        let p = self.direction.cross(&edge_2);
+        let determinant = edge_1.dot(&p);
+        let scale = edge_1.magnitude() * edge_2.magnitude();
+        if determinant.abs() <= EPSILON * scale {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+
+        let s = self.origin - *a;
+        let u = s.dot(&p) * inverse;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&edge_1);
+        let v = self.direction.dot(&q) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let distance = edge_2.dot(&q) * inverse;
+        if distance < 0.0 {
+            return None;
+        }
+
+        let normal = edge_1.cross(&edge_2).normalise();
+        Some(TriangleHit {
+            distance,
+            normal: facing(normal, normal.dot(&self.direction)),
+            barycentric: Vector3D::new(1.0 - u - v, u, v),
+        })
+    }
+
+    /// Intersects the ray with an axis-aligned box using the slab method
+    ///
+    /// Rays that run exactly along a face or edge count as a hit. A box with
+    /// `min` above `max` on any axis is empty, like `Aabb::empty`, and is
+    /// never hit.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(-5.0, 0.5, 0.5), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+    /// let hit = ray.intersect_aabb(&Vector3D::new(0.0, 0.0, 0.0), &Vector3D::new(1.0, 1.0, 1.0)).unwrap();
+    /// assert_eq!(hit.distance, 5.0);
+    /// assert_eq!(hit.normal, Vector3D::new(-1.0, 0.0, 0.0));
+    /// ```
+    pub fn intersect_aabb(&self, min: &Vector3D, max: &Vector3D) -> Option<RayHit> {
+        // Also rejects NaN bounds
+        if !(0..3).all(|axis| min[axis] <= max[axis]) {
+            return None;
+        }
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+        let mut enter_normal = Vector3D::default();
+        let mut exit_normal = Vector3D::default();
+
+        for axis in 0..3 {
+            let origin = self.origin[axis];
+            let direction = self.direction[axis];
+            if direction == 0.0 {
+                // Parallel to this slab, so it is either always inside or never
+                if origin < min[axis] || origin > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inverse = 1.0 / direction;
+            let mut t_near = (min[axis] - origin) * inverse;
+            let mut t_far = (max[axis] - origin) * inverse;
+            let mut near_normal = Vector3D::default();
+            near_normal[axis] = -1.0;
+            if t_near > t_far {
+                std::mem::swap(&mut t_near, &mut t_far);
+                near_normal[axis] = 1.0;
+            }
+            if t_near > t_enter {
+                t_enter = t_near;
+                enter_normal = near_normal;
+            }
+            if t_far < t_exit {
+                t_exit = t_far;
+                exit_normal = -near_normal;
+            }
+            if t_enter > t_exit || t_exit < 0.0 {
+                return None;
+            }
+        }
+
+        if t_enter >= 0.0 {
+            Some(RayHit { distance: t_enter, normal: enter_normal })
+        } else if t_exit.is_finite() {
+            Some(RayHit { distance: t_exit, normal: exit_normal })
+        } else {
+            None
+        }
+    }
+
+    /// Intersects the ray with an oriented box
+    ///
+    /// # Arguments
+    /// - `center` - The centre of the box
+    /// - `orientation` - Rotation whose columns are the box's local axes
+    /// - `half_extents` - Half the size of the box along each local axis
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Matrix3D, Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(0.0, 0.0, -5.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+    /// let hit = ray.intersect_obb(&Vector3D::default(), &Matrix3D::identity(), &Vector3D::new(1.0, 1.0, 1.0)).unwrap();
+    /// assert_eq!(hit.distance, 4.0);
+    /// ```
+    pub fn intersect_obb(&self, center: &Vector3D, orientation: &Matrix3D, half_extents: &Vector3D) -> Option<RayHit> {
+        let to_local = orientation.transpose();
+        let local = Ray {
+            origin: to_local * (self.origin - *center),
+            direction: to_local * self.direction,
+        };
+        let hit = local.intersect_aabb(&-*half_extents, half_extents)?;
+        Some(RayHit {
+            distance: hit.distance,
+            normal: *orientation * hit.normal,
+        })
+    }
+
+    /// Intersects the ray with a capsule, the set of points within `radius`
+    /// of the segment `a`-`b`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// let ray = Ray::new(Vector3D::new(-5.0, 0.0, 1.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+    /// let hit = ray.intersect_capsule(&Vector3D::new(0.0, 0.0, 0.0), &Vector3D::new(0.0, 0.0, 2.0), 0.5).unwrap();
+    /// assert_eq!(hit.distance, 4.5);
+    /// assert_eq!(hit.normal, Vector3D::new(-1.0, 0.0, 0.0));
+    /// ```
+    pub fn intersect_capsule(&self, a: &Vector3D, b: &Vector3D, radius: f64) -> Option<RayHit> {
+        let axis = *b - *a;
+        let length = axis.magnitude();
+        if length <= EPSILON {
+            return self.intersect_sphere(a, radius);
+        }
+        let axis = axis / length;
+
+        let mut best: Option<RayHit> = None;
+        let mut consider = |distance: f64, normal: Vector3D| {
+            if distance >= 0.0 && best.is_none_or(|hit| distance < hit.distance) {
+                best = Some(RayHit { distance, normal });
+            }
+        };
+
+        // Body: only the part of the infinite cylinder between the end caps
+        if let Some(roots) = cylinder_roots(self, a, &axis, radius) {
+            for t in roots {
+                let s = (self.at(t) - *a).dot(&axis);
+                if (0.0..=length).contains(&s) {
+                    consider(t, (self.at(t) - (*a + axis * s)) / radius);
+                }
+            }
+        }
+        // Caps: only the hemisphere of each end sphere facing away from the body
+        for (center, outward) in [(*a, -axis), (*b, axis)] {
+            if let Some(roots) = sphere_roots(self, &center, radius) {
+                for t in roots {
+                    let normal = (self.at(t) - center) / radius;
+                    if normal.dot(&outward) >= 0.0 {
+                        consider(t, normal);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Intersects the ray with a solid capped cylinder around the segment `a`-`b`
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Ray, Vector3D};
+    ///
+    /// // Straight down the axis onto the top cap
+    /// let ray = Ray::new(Vector3D::new(0.0, 0.0, 5.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+    /// let hit = ray.intersect_cylinder(&Vector3D::new(0.0, 0.0, 0.0), &Vector3D::new(0.0, 0.0, 2.0), 0.5).unwrap();
+    /// assert_eq!(hit.distance, 3.0);
+    /// assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn intersect_cylinder(&self, a: &Vector3D, b: &Vector3D, radius: f64) -> Option<RayHit> {
+        let axis = *b - *a;
+        let length = axis.magnitude();
+        if length <= EPSILON {
+            return None;
+        }
+        let axis = axis / length;
+
+        let mut best: Option<RayHit> = None;
+        let mut consider = |distance: f64, normal: Vector3D| {
+            if distance >= 0.0 && best.is_none_or(|hit| distance < hit.distance) {
+                best = Some(RayHit { distance, normal });
+            }
+        };
+
+        if let Some(roots) = cylinder_roots(self, a, &axis, radius) {
+            for t in roots {
+                let s = (self.at(t) - *a).dot(&axis);
+                if (0.0..=length).contains(&s) {
+                    consider(t, (self.at(t) - (*a + axis * s)) / radius);
+                }
+            }
+        }
+        for (center, outward) in [(*a, -axis), (*b, axis)] {
+            let cap = Plane::from_point_normal(center, outward);
+            if let Some(hit) = self.intersect_plane(&cap) {
+                if (self.at(hit.distance) - center).magnitude_squared() <= radius * radius {
+                    consider(hit.distance, outward);
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Flips `normal` if needed so it faces back against a ray with
+/// `normal . direction == along`
+fn facing(normal: Vector3D, along: f64) -> Vector3D {
+    if along > 0.0 {
+        -normal
+    } else {
+        normal
+    }
+}
+
+/// Returns both ray parameters (ascending) where the ray meets a sphere
+fn sphere_roots(ray: &Ray, center: &Vector3D, radius: f64) -> Option<[f64; 2]> {
+    let offset = ray.origin - *center;
+    let b = offset.dot(&ray.direction);
+    let c = offset.magnitude_squared() - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    Some([-b - root, -b + root])
+}
+
+/// Returns both ray parameters (ascending) where the ray meets the infinite
+/// cylinder through `a` along the unit `axis`, or `None` if the ray misses or
+/// runs parallel to the axis
+fn cylinder_roots(ray: &Ray, a: &Vector3D, axis: &Vector3D, radius: f64) -> Option<[f64; 2]> {
+    // Work in the plane perpendicular to the axis, where the cylinder is a circle
+    let offset = ray.origin - *a;
+    let direction = ray.direction - *axis * ray.direction.dot(axis);
+    let offset = offset - *axis * offset.dot(axis);
+    let a2 = direction.magnitude_squared();
+    if a2 <= EPSILON * EPSILON {
+        return None;
+    }
+    let b = offset.dot(&direction);
+    let c = offset.magnitude_squared() - radius * radius;
+    let discriminant = b * b - a2 * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    Some([(-b - root) / a2, (-b + root) / a2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!((a - b).magnitude() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_ray_sphere() {
+        let center = Vector3D::new(1.0, 2.0, 3.0);
+        let ray = Ray::new(Vector3D::new(1.0, 2.0, -7.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        let hit = ray.intersect_sphere(&center, 2.0).unwrap();
+        assert_eq!(hit.distance, 8.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+
+        // From inside, the exit point is reported with an outward normal
+        let inside = Ray::new(center, Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        let hit = inside.intersect_sphere(&center, 2.0).unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_eq!(hit.normal, Vector3D::new(1.0, 0.0, 0.0));
+
+        // Grazing: tangent to the sphere
+        let grazing = Ray::new(Vector3D::new(-5.0, 1.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        let hit = grazing.intersect_sphere(&Vector3D::default(), 1.0).unwrap();
+        assert_eq!(hit.distance, 5.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 1.0, 0.0));
+
+        // Just past the tangent, and pointing away
+        let miss = Ray::new(Vector3D::new(-5.0, 1.0 + 1e-9, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(miss.intersect_sphere(&Vector3D::default(), 1.0).is_none());
+        let away = Ray::new(Vector3D::new(-5.0, 0.0, 0.0), Vector3D::new(-1.0, 0.0, 0.0)).unwrap();
+        assert!(away.intersect_sphere(&Vector3D::default(), 1.0).is_none());
+    }
+
+    #[test]
+    fn test_ray_plane() {
+        let plane = Plane::from_point_normal(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(0.0, 0.0, 1.0));
+
+        // From behind, the normal is flipped to face the ray
+        let below = Ray::new(Vector3D::default(), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        let hit = below.intersect_plane(&plane).unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+
+        // Parallel, both off and in the plane
+        let parallel = Ray::new(Vector3D::default(), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(parallel.intersect_plane(&plane).is_none());
+        let in_plane = Ray::new(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(in_plane.intersect_plane(&plane).is_none());
+
+        // Pointing away
+        let away = Ray::new(Vector3D::default(), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        assert!(away.intersect_plane(&plane).is_none());
+    }
+
+    #[test]
+    fn test_ray_triangle() {
+        let a = Vector3D::new(0.0, 0.0, 0.0);
+        let b = Vector3D::new(2.0, 0.0, 0.0);
+        let c = Vector3D::new(0.0, 2.0, 0.0);
+
+        let ray = Ray::new(Vector3D::new(0.5, 0.5, -3.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        let hit = ray.intersect_triangle(&a, &b, &c).unwrap();
+        assert_eq!(hit.distance, 3.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+        let w = hit.barycentric;
+        assert_close(a * w.x + b * w.y + c * w.z, ray.at(hit.distance));
+        assert!((w.x + w.y + w.z - 1.0).abs() < 1e-12);
+
+        // Through an edge and through a vertex
+        let edge = Ray::new(Vector3D::new(1.0, 0.0, 1.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        assert!(edge.intersect_triangle(&a, &b, &c).is_some());
+        let vertex = Ray::new(Vector3D::new(0.0, 2.0, 1.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        assert_close(vertex.intersect_triangle(&a, &b, &c).unwrap().barycentric, Vector3D::new(0.0, 0.0, 1.0));
+
+        // Outside, parallel in the plane, and behind
+        let outside = Ray::new(Vector3D::new(1.5, 1.5, 1.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        assert!(outside.intersect_triangle(&a, &b, &c).is_none());
+        let parallel = Ray::new(Vector3D::new(-1.0, 0.5, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(parallel.intersect_triangle(&a, &b, &c).is_none());
+        let behind = Ray::new(Vector3D::new(0.5, 0.5, 1.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        assert!(behind.intersect_triangle(&a, &b, &c).is_none());
+
+        // Degenerate triangle never hits
+        assert!(ray.intersect_triangle(&a, &b, &(b * 2.0)).is_none());
+    }
+
+    #[test]
+    fn test_ray_aabb() {
+        let min = Vector3D::new(-1.0, -1.0, -1.0);
+        let max = Vector3D::new(1.0, 1.0, 1.0);
+
+        let ray = Ray::new(Vector3D::new(0.0, 5.0, 0.0), Vector3D::new(0.0, -1.0, 0.0)).unwrap();
+        let hit = ray.intersect_aabb(&min, &max).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 1.0, 0.0));
+
+        // Diagonal ray hits the corner region
+        let diagonal = Ray::new(Vector3D::new(-3.0, -3.0, -3.0), Vector3D::new(1.0, 1.0, 1.0)).unwrap();
+        let hit = diagonal.intersect_aabb(&min, &max).unwrap();
+        assert!((hit.distance - 2.0 * 3.0_f64.sqrt()).abs() < 1e-12);
+
+        // From inside
+        let inside = Ray::new(Vector3D::default(), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        let hit = inside.intersect_aabb(&min, &max).unwrap();
+        assert_eq!(hit.distance, 1.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+
+        // Grazing along a face, and along an edge
+        let face = Ray::new(Vector3D::new(-5.0, 1.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(face.intersect_aabb(&min, &max).unwrap().distance, 4.0);
+        let edge = Ray::new(Vector3D::new(-5.0, 1.0, 1.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(edge.intersect_aabb(&min, &max).unwrap().distance, 4.0);
+
+        // Parallel to a slab but outside it
+        let parallel = Ray::new(Vector3D::new(-5.0, 1.5, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(parallel.intersect_aabb(&min, &max).is_none());
+
+        // Pointing away
+        let away = Ray::new(Vector3D::new(0.0, 5.0, 0.0), Vector3D::new(0.0, 1.0, 0.0)).unwrap();
+        assert!(away.intersect_aabb(&min, &max).is_none());
+
+        // Inverted and empty boxes are never hit
+        let inverted = Vector3D::new(1.0, -1.0, 1.0);
+        assert!(ray.intersect_aabb(&inverted, &Vector3D::new(-1.0, 1.0, -1.0)).is_none());
+        let infinity = Vector3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        assert!(inside.intersect_aabb(&infinity, &-infinity).is_none());
+        assert!(inside.intersect_aabb(&Vector3D::new(f64::NAN, -1.0, -1.0), &max).is_none());
+    }
+
+    #[test]
+    fn test_ray_degenerate_direction() {
+        assert!(Ray::new(Vector3D::new(1.0, 2.0, 3.0), Vector3D::default()).is_none());
+        assert!(Ray::new(Vector3D::default(), Vector3D::new(f64::NAN, 0.0, 1.0)).is_none());
+        assert!(Ray::new(Vector3D::default(), Vector3D::new(f64::INFINITY, 0.0, 0.0)).is_none());
+        let tiny = Ray::new(Vector3D::default(), Vector3D::new(0.0, 1e-100, 0.0)).unwrap();
+        assert_eq!(tiny.direction, Vector3D::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_ray_obb() {
+        // Box rotated 90 degrees about z, so its long x axis now lies along y
+        let orientation = Matrix3D::new(0.0, -1.0, 0.0,
+                                        1.0, 0.0, 0.0,
+                                        0.0, 0.0, 1.0);
+        let center = Vector3D::new(1.0, 1.0, 0.0);
+        let half_extents = Vector3D::new(3.0, 1.0, 1.0);
+
+        let ray = Ray::new(Vector3D::new(1.0, 10.0, 0.0), Vector3D::new(0.0, -1.0, 0.0)).unwrap();
+        let hit = ray.intersect_obb(&center, &orientation, &half_extents).unwrap();
+        assert!((hit.distance - 6.0).abs() < 1e-12);
+        assert_close(hit.normal, Vector3D::new(0.0, 1.0, 0.0));
+
+        // Would hit the unrotated box but misses the rotated one
+        let miss = Ray::new(Vector3D::new(3.5, 10.0, 0.0), Vector3D::new(0.0, -1.0, 0.0)).unwrap();
+        assert!(miss.intersect_obb(&center, &orientation, &half_extents).is_none());
+    }
+
+    #[test]
+    fn test_ray_capsule() {
+        let a = Vector3D::new(0.0, 0.0, 0.0);
+        let b = Vector3D::new(0.0, 0.0, 4.0);
+
+        // Down the axis onto the top cap
+        let down = Ray::new(Vector3D::new(0.0, 0.0, 10.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        let hit = down.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert_eq!(hit.distance, 5.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, 1.0));
+
+        // Parallel to the axis but outside the radius
+        let parallel = Ray::new(Vector3D::new(1.5, 0.0, 10.0), Vector3D::new(0.0, 0.0, -1.0)).unwrap();
+        assert!(parallel.intersect_capsule(&a, &b, 1.0).is_none());
+
+        // Grazing the body
+        let grazing = Ray::new(Vector3D::new(-5.0, 1.0, 2.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        let hit = grazing.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert_eq!(hit.distance, 5.0);
+        assert_close(hit.normal, Vector3D::new(0.0, 1.0, 0.0));
+
+        // Below the segment, the bottom cap is hit rather than the body
+        let low = Ray::new(Vector3D::new(-5.0, 0.0, -0.5), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        let hit = low.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert!((hit.distance - (5.0 - 0.75_f64.sqrt())).abs() < 1e-12);
+        assert!(hit.normal.z < 0.0);
+
+        // From inside
+        let inside = Ray::new(Vector3D::new(0.0, 0.0, 2.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(inside.intersect_capsule(&a, &b, 1.0).unwrap().distance, 1.0);
+
+        // Zero length capsule is a sphere
+        let sphere = Ray::new(Vector3D::new(-5.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(sphere.intersect_capsule(&a, &a, 1.0).unwrap().distance, 4.0);
+    }
+
+    #[test]
+    fn test_ray_cylinder() {
+        let a = Vector3D::new(0.0, 0.0, 0.0);
+        let b = Vector3D::new(0.0, 0.0, 4.0);
+
+        let side = Ray::new(Vector3D::new(-5.0, 0.0, 2.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        let hit = side.intersect_cylinder(&a, &b, 1.0).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Vector3D::new(-1.0, 0.0, 0.0));
+
+        // Parallel to the axis: hits the cap inside the radius, misses outside
+        let up = Ray::new(Vector3D::new(0.5, 0.0, -3.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        let hit = up.intersect_cylinder(&a, &b, 1.0).unwrap();
+        assert_eq!(hit.distance, 3.0);
+        assert_eq!(hit.normal, Vector3D::new(0.0, 0.0, -1.0));
+        let outside = Ray::new(Vector3D::new(1.5, 0.0, -3.0), Vector3D::new(0.0, 0.0, 1.0)).unwrap();
+        assert!(outside.intersect_cylinder(&a, &b, 1.0).is_none());
+
+        // Past the end, where a capsule would still be hit
+        let past = Ray::new(Vector3D::new(-5.0, 0.0, 4.5), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(past.intersect_cylinder(&a, &b, 1.0).is_none());
+        assert!(past.intersect_capsule(&a, &b, 1.0).is_some());
+
+        // Grazing the rim of the cap
+        let rim = Ray::new(Vector3D::new(-5.0, 1.0, 4.0), Vector3D::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(rim.intersect_cylinder(&a, &b, 1.0).is_some());
+
+        // Degenerate cylinder
+        assert!(side.intersect_cylinder(&a, &a, 1.0).is_none());
+    }
+}
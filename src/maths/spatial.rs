@@ -0,0 +1,234 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use super::{Aabb, Vector3D};
+
+/// Common interface of the point partitioning structures
+///
+/// Points are keyed by a caller-chosen id, so the same particle or agent
+/// index can be used with `Octree` and `SpatialHash` and one can be swapped
+/// for the other. Query results are in no particular order except for
+/// `k_nearest`.
+pub trait SpatialIndex {
+    /// Adds a point, or moves it if the id is already present
+    fn insert(&mut self, id: usize, position: Vector3D);
+
+    /// Removes a point, returning false if the id is not present
+    fn remove(&mut self, id: usize) -> bool;
+
+    /// Moves a point, returning false if the id is not present
+    fn update(&mut self, id: usize, position: Vector3D) -> bool;
+
+    /// Returns the stored position of a point
+    fn position(&self, id: usize) -> Option<Vector3D>;
+
+    /// Returns the number of points
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no points
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the ids of every point within `radius` of `center`, inclusive
+    fn query_radius(&self, center: &Vector3D, radius: f64) -> Vec<usize>;
+
+    /// Returns the ids of every point inside `aabb`, inclusive
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<usize>;
+
+    /// Returns up to `k` points closest to `point` as (id, distance), nearest first
+    fn k_nearest(&self, point: &Vector3D, k: usize) -> Vec<(usize, f64)>;
+}
+
+/// A point found by a nearest neighbour search
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Candidate {
+    pub id: usize,
+    pub distance_squared: f64,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_squared.total_cmp(&other.distance_squared).then(self.id.cmp(&other.id))
+    }
+}
+
+/// The `k` closest candidates seen so far, kept as a max-heap so the worst
+/// one can be replaced cheaply
+#[derive(Debug, Clone)]
+pub(crate) struct NearestSet {
+    k: usize,
+    heap: BinaryHeap<Candidate>,
+}
+
+impl NearestSet {
+    pub fn new(k: usize) -> Self {
+        Self { k, heap: BinaryHeap::with_capacity(k + 1) }
+    }
+
+    /// Offers a candidate, keeping it only if it is among the `k` closest
+    pub fn offer(&mut self, id: usize, distance_squared: f64) {
+        if self.k == 0 {
+            return;
+        }
+        self.heap.push(Candidate { id, distance_squared });
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// The squared distance a new candidate must beat, infinite until full
+    pub fn bound(&self) -> f64 {
+        if self.heap.len() < self.k {
+            f64::INFINITY
+        } else {
+            self.heap.peek().map_or(f64::INFINITY, |c| c.distance_squared)
+        }
+    }
+
+    /// Returns the candidates as (id, distance), nearest first
+    pub fn into_sorted(self) -> Vec<(usize, f64)> {
+        self.heap.into_sorted_vec().into_iter().map(|c| (c.id, c.distance_squared.sqrt())).collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+
+    /// Seeded linear congruential generator, so every test module draws
+    /// the same reproducible numbers without a dependency
+    pub struct TestRng(u64);
+
+    impl TestRng {
+        pub fn new(seed: u64) -> Self {
+            Self(seed)
+        }
+
+        /// Returns a number in 0..1
+        pub fn unit(&mut self) -> f64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        /// Returns a number in -1..1
+        pub fn signed(&mut self) -> f64 {
+            self.unit() * 2.0 - 1.0
+        }
+    }
+
+    /// Points with each coordinate in `-scale..scale`
+    pub fn random_points(count: usize, scale: f64, seed: u64) -> Vec<Vector3D> {
+        let mut rng = TestRng::new(seed);
+        (0..count).map(|_| Vector3D::new(rng.signed(), rng.signed(), rng.signed()) * scale).collect()
+    }
+
+    /// Compares every query of `index` with a linear scan over `points`,
+    /// where removed points are None
+    pub fn check_against_brute_force<S: SpatialIndex>(index: &S, points: &[Option<Vector3D>], seed: u64) {
+        assert_eq!(index.len(), points.iter().flatten().count());
+        for (id, point) in points.iter().enumerate() {
+            assert_eq!(index.position(id), *point);
+        }
+        let live = || points.iter().enumerate().filter_map(|(id, p)| p.map(|p| (id, p)));
+        for (query, radius) in random_points(30, 60.0, seed).into_iter().zip([0.0, 1.0, 5.0, 12.0, 40.0].into_iter().cycle()) {
+            let mut found = index.query_radius(&query, radius);
+            found.sort();
+            let expected: Vec<usize> = live().filter(|(_, p)| (*p - query).magnitude_squared() <= radius * radius).map(|(id, _)| id).collect();
+            assert_eq!(found, expected);
+
+            let aabb = Aabb::from_center_half_extents(query, Vector3D::new(radius, radius * 0.5, radius * 2.0));
+            let mut found = index.query_aabb(&aabb);
+            found.sort();
+            let expected: Vec<usize> = live().filter(|(_, p)| aabb.contains_point(p)).map(|(id, _)| id).collect();
+            assert_eq!(found, expected);
+
+            for k in [0, 1, 7, 40] {
+                let nearest = index.k_nearest(&query, k);
+                let mut expected: Vec<f64> = live().map(|(_, p)| (p - query).magnitude()).collect();
+                expected.sort_by(f64::total_cmp);
+                expected.truncate(k);
+                let distances: Vec<f64> = nearest.iter().map(|n| n.1).collect();
+                assert_eq!(distances, expected);
+                for (id, distance) in nearest {
+                    assert_eq!((points[id].unwrap() - query).magnitude(), distance);
+                }
+            }
+        }
+    }
+
+    /// Inserts, moves and removes points and checks the index after each step
+    pub fn exercise<S: SpatialIndex>(index: &mut S, scale: f64) {
+        let mut points: Vec<Option<Vector3D>> = random_points(800, scale, 1).into_iter().map(Some).collect();
+        for (id, p) in points.iter().enumerate() {
+            index.insert(id, p.unwrap());
+        }
+        check_against_brute_force(index, &points, 2);
+
+        // Small jitter, large jumps and moves outside the expected bounds
+        let moves = random_points(800, 1.0, 3);
+        for id in (0..points.len()).step_by(2) {
+            let scale = match id % 3 {
+                0 => 0.5,
+                1 => scale,
+                _ => scale * 3.0,
+            };
+            let target = points[id].unwrap() + moves[id] * scale;
+            assert!(index.update(id, target));
+            points[id] = Some(target);
+        }
+        check_against_brute_force(index, &points, 4);
+
+        for id in (0..points.len()).step_by(5) {
+            assert!(index.remove(id));
+            points[id] = None;
+        }
+        assert!(!index.remove(0));
+        assert!(!index.update(0, Vector3D::default()));
+        check_against_brute_force(index, &points, 5);
+
+        // Reinserting an existing id moves it
+        index.insert(1, Vector3D::new(1.0, 2.0, 3.0));
+        points[1] = Some(Vector3D::new(1.0, 2.0, 3.0));
+        check_against_brute_force(index, &points, 6);
+
+        for id in 0..points.len() {
+            index.remove(id);
+        }
+        assert!(index.is_empty());
+        assert!(index.k_nearest(&Vector3D::default(), 3).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_set() {
+        let mut set = NearestSet::new(3);
+        assert_eq!(set.bound(), f64::INFINITY);
+        for (id, d) in [(0, 9.0), (1, 1.0), (2, 16.0), (3, 4.0), (4, 25.0)] {
+            set.offer(id, d);
+        }
+        assert_eq!(set.bound(), 9.0);
+        assert_eq!(set.into_sorted(), vec![(1, 1.0), (3, 2.0), (0, 3.0)]);
+
+        let mut empty = NearestSet::new(0);
+        empty.offer(0, 1.0);
+        assert!(empty.into_sorted().is_empty());
+    }
+}
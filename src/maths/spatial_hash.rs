@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use super::{spatial::NearestSet, Aabb, SpatialIndex, Vector3D};
+
+type Cell = (i64, i64, i64);
+
+/// Uniform grid of cubic cells stored sparsely in a hash map
+///
+/// Unlike `Octree` it needs no bounds, and inserts and moves are constant
+/// time, but queries much larger than a cell visit many cells. Pick a cell
+/// size close to the usual query radius.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialHash {
+    cell_size: f64,
+    cells: HashMap<Cell, Vec<usize>>,
+    /// Per id, the point and the cell holding it
+    items: HashMap<usize, (Vector3D, Cell)>,
+}
+
+impl SpatialHash {
+    /// Creates an empty SpatialHash, or None unless the cell size is
+    /// positive and finite
+    ///
+    /// # Arguments
+    /// - `cell_size` - The edge length of each cell
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{SpatialHash, SpatialIndex, Vector3D};
+    ///
+    /// let mut hash = SpatialHash::new(5.0).unwrap();
+    /// hash.insert(7, Vector3D::new(1.0, 2.0, 3.0));
+    /// hash.insert(9, Vector3D::new(50.0, 0.0, 0.0));
+    /// assert_eq!(hash.query_radius(&Vector3D::default(), 10.0), vec![7]);
+    /// assert_eq!(hash.k_nearest(&Vector3D::new(40.0, 0.0, 0.0), 1), vec![(9, 10.0)]);
+    /// assert!(SpatialHash::new(0.0).is_none());
+    /// ```
+    pub fn new(cell_size: f64) -> Option<Self> {
+        (cell_size > 0.0 && cell_size.is_finite()).then(|| Self { cell_size, cells: HashMap::new(), items: HashMap::new() })
+    }
+
+    /// Returns the edge length of each cell
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    fn cell(&self, point: &Vector3D) -> Cell {
+        let index = |v: f64| (v / self.cell_size).floor() as i64;
+        (index(point.x), index(point.y), index(point.z))
+    }
+
+    fn unlink(&mut self, id: usize, cell: Cell) {
+        if let Some(ids) = self.cells.get_mut(&cell) {
+            ids.retain(|&i| i != id);
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// Visits every point in the cells overlapping `aabb`, or in all cells
+    /// when that is fewer
+    fn visit<V: FnMut(usize, &Vector3D)>(&self, aabb: &Aabb, mut found: V) {
+        if aabb.is_empty() {
+            return;
+        }
+        let (min, max) = (self.cell(&aabb.min), self.cell(&aabb.max));
+        let span = |a: i64, b: i64| (b as f64 - a as f64) + 1.0;
+        let count = span(min.0, max.0) * span(min.1, max.1) * span(min.2, max.2);
+        if count > self.cells.len() as f64 {
+            for ids in self.cells.values() {
+                for id in ids {
+                    found(*id, &self.items[id].0);
+                }
+            }
+            return;
+        }
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                for z in min.2..=max.2 {
+                    for id in self.cells.get(&(x, y, z)).into_iter().flatten() {
+                        found(*id, &self.items[id].0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SpatialIndex for SpatialHash {
+    fn insert(&mut self, id: usize, position: Vector3D) {
+        if !self.update(id, position) {
+            let cell = self.cell(&position);
+            self.cells.entry(cell).or_default().push(id);
+            self.items.insert(id, (position, cell));
+        }
+    }
+
+    fn remove(&mut self, id: usize) -> bool {
+        let Some((_, cell)) = self.items.remove(&id) else {
+            return false;
+        };
+        self.unlink(id, cell);
+        true
+    }
+
+    fn update(&mut self, id: usize, position: Vector3D) -> bool {
+        let cell = self.cell(&position);
+        let Some((stored, old)) = self.items.get_mut(&id) else {
+            return false;
+        };
+        *stored = position;
+        if *old != cell {
+            let old = std::mem::replace(old, cell);
+            self.unlink(id, old);
+            self.cells.entry(cell).or_default().push(id);
+        }
+        true
+    }
+
+    fn position(&self, id: usize) -> Option<Vector3D> {
+        self.items.get(&id).map(|(p, _)| *p)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn query_radius(&self, center: &Vector3D, radius: f64) -> Vec<usize> {
+        let radius_squared = radius * radius;
+        let bounds = Aabb::from_center_half_extents(*center, Vector3D::new(radius, radius, radius));
+        let mut found = Vec::new();
+        self.visit(&bounds, |id, p| {
+            if (*p - *center).magnitude_squared() <= radius_squared {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(aabb, |id, p| {
+            if aabb.contains_point(p) {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    fn k_nearest(&self, point: &Vector3D, k: usize) -> Vec<(usize, f64)> {
+        let mut nearest = NearestSet::new(k);
+        if k == 0 {
+            return nearest.into_sorted();
+        }
+        let offer_cell = |nearest: &mut NearestSet, ids: &[usize]| {
+            for id in ids {
+                nearest.offer(*id, (self.items[id].0 - *point).magnitude_squared());
+            }
+            ids.len()
+        };
+
+        // Search shells of cells around the point's cell. Anything beyond
+        // shell `ring` is more than `ring` cells away.
+        let (cx, cy, cz) = self.cell(point);
+        let mut visited = 0;
+        let mut ring: i64 = 0;
+        while visited < self.items.len() {
+            let side = (2 * ring + 1) as f64;
+            if side * side * side > self.cells.len() as f64 * 2.0 {
+                // The shell is sparse compared to the grid, finish with a scan
+                let mut all = NearestSet::new(k);
+                for ids in self.cells.values() {
+                    offer_cell(&mut all, ids);
+                }
+                return all.into_sorted();
+            }
+            for dx in -ring..=ring {
+                for dy in -ring..=ring {
+                    let on_edge = dx.abs() == ring || dy.abs() == ring;
+                    let step = if on_edge { 1 } else { (2 * ring).max(1) as usize };
+                    for dz in (-ring..=ring).step_by(step) {
+                        if let Some(ids) = self.cells.get(&(cx + dx, cy + dy, cz + dz)) {
+                            visited += offer_cell(&mut nearest, ids);
+                        }
+                    }
+                }
+            }
+            let reach = ring as f64 * self.cell_size;
+            if nearest.bound() <= reach * reach {
+                break;
+            }
+            ring += 1;
+        }
+        nearest.into_sorted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::spatial::testing::exercise;
+
+    #[test]
+    fn test_spatial_hash_against_brute_force() {
+        for cell_size in [0.7, 4.0, 25.0, 500.0] {
+            let mut hash = SpatialHash::new(cell_size).unwrap();
+            exercise(&mut hash, 50.0);
+            assert!(hash.cells.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_spatial_hash_cells() {
+        let mut hash = SpatialHash::new(2.0).unwrap();
+        hash.insert(0, Vector3D::new(-0.5, 0.5, 3.9));
+        assert_eq!(hash.items[&0].1, (-1, 0, 1));
+        hash.update(0, Vector3D::new(-0.1, 1.9, 2.1));
+        assert_eq!(hash.cells.len(), 1);
+        hash.update(0, Vector3D::new(0.0, 1.9, 2.1));
+        assert_eq!(hash.items[&0].1, (0, 0, 1));
+        assert_eq!(hash.cells.len(), 1);
+        assert_eq!(hash.query_aabb(&Aabb::new(Vector3D::new(0.0, 1.9, 2.1), Vector3D::new(0.0, 1.9, 2.1))), vec![0]);
+    }
+
+    #[test]
+    fn test_spatial_hash_rejects_bad_cell_size() {
+        for cell_size in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(SpatialHash::new(cell_size).is_none());
+        }
+        assert_eq!(SpatialHash::new(1e-3).unwrap().cell_size(), 1e-3);
+    }
+}
@@ -0,0 +1,151 @@
+use super::{Aabb, Capsule, Matrix3D, Obb, Ray, RayHit, Vector3D};
+
+/// Bounding sphere stored as a centre and radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vector3D,
+    pub radius: f64,
+}
+
+impl Sphere {
+    /// Creates a new Sphere
+    ///
+    /// # Arguments
+    /// - `center` - The centre of the sphere
+    /// - `radius` - The radius of the sphere
+    pub fn new(center: Vector3D, radius: f64) -> Self {
+        Self { center, radius }
+    }
+
+    /// Returns true if `point` is inside the sphere or on its surface
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Sphere, Vector3D};
+    ///
+    /// let sphere = Sphere::new(Vector3D::default(), 2.0);
+    /// assert!(sphere.contains_point(&Vector3D::new(0.0, 2.0, 0.0)));
+    /// assert!(!sphere.contains_point(&Vector3D::new(0.0, 2.1, 0.0)));
+    /// ```
+    pub fn contains_point(&self, point: &Vector3D) -> bool {
+        (*point - self.center).magnitude_squared() <= self.radius * self.radius
+    }
+
+    /// Returns true if `other` lies entirely inside this sphere
+    pub fn contains_sphere(&self, other: &Sphere) -> bool {
+        (other.center - self.center).magnitude() + other.radius <= self.radius
+    }
+
+    /// Returns true if the two spheres overlap, touching counts
+    pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+        let radii = self.radius + other.radius;
+        (other.center - self.center).magnitude_squared() <= radii * radii
+    }
+
+    /// Returns true if the sphere overlaps an Aabb
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        aabb.distance_squared(&self.center) <= self.radius * self.radius
+    }
+
+    /// Returns true if the sphere overlaps an Obb
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        obb.intersects_sphere(self)
+    }
+
+    /// Returns true if the sphere overlaps a Capsule
+    pub fn intersects_capsule(&self, capsule: &Capsule) -> bool {
+        capsule.intersects_sphere(self)
+    }
+
+    /// Returns the tightest Aabb around the sphere
+    pub fn aabb(&self) -> Aabb {
+        let r = Vector3D::new(self.radius, self.radius, self.radius);
+        Aabb::new(self.center - r, self.center + r)
+    }
+
+    /// Returns the sphere moved by `p -> rotation * p + translation`
+    ///
+    /// `rotation` is assumed to be a pure rotation, so the radius is unchanged.
+    pub fn transform(&self, rotation: &Matrix3D, translation: &Vector3D) -> Self {
+        Self {
+            center: *rotation * self.center + *translation,
+            radius: self.radius,
+        }
+    }
+
+    /// Returns the smallest sphere containing both spheres
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Sphere, Vector3D};
+    ///
+    /// let a = Sphere::new(Vector3D::new(-2.0, 0.0, 0.0), 1.0);
+    /// let b = Sphere::new(Vector3D::new(2.0, 0.0, 0.0), 1.0);
+    /// assert_eq!(a.merge(&b), Sphere::new(Vector3D::default(), 3.0));
+    /// ```
+    pub fn merge(&self, other: &Sphere) -> Self {
+        let offset = other.center - self.center;
+        let distance = offset.magnitude();
+        if distance + other.radius <= self.radius {
+            return *self;
+        }
+        if distance + self.radius <= other.radius {
+            return *other;
+        }
+        let radius = (distance + self.radius + other.radius) * 0.5;
+        Self {
+            center: self.center + offset * ((radius - self.radius) / distance),
+            radius,
+        }
+    }
+
+    /// Intersects a ray with the sphere, see `Ray::intersect_sphere`
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<RayHit> {
+        ray.intersect_sphere(&self.center, self.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_overlaps() {
+        let a = Sphere::new(Vector3D::new(0.0, 0.0, 0.0), 1.0);
+        let touching = Sphere::new(Vector3D::new(2.0, 0.0, 0.0), 1.0);
+        let apart = Sphere::new(Vector3D::new(2.5, 0.0, 0.0), 1.0);
+        assert!(a.intersects_sphere(&touching));
+        assert!(!a.intersects_sphere(&apart));
+
+        let aabb = Aabb::new(Vector3D::new(1.5, -1.0, -1.0), Vector3D::new(3.0, 1.0, 1.0));
+        assert!(!a.intersects_aabb(&aabb));
+        assert!(touching.intersects_aabb(&aabb));
+        assert!(a.aabb().contains_point(&Vector3D::new(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_sphere_merge_and_containment() {
+        let a = Sphere::new(Vector3D::new(1.0, 2.0, 3.0), 1.5);
+        let b = Sphere::new(Vector3D::new(-2.0, 0.0, 4.0), 0.5);
+        let merged = a.merge(&b);
+        for s in [a, b] {
+            let slack = Sphere::new(merged.center, merged.radius + 1e-12);
+            assert!(slack.contains_sphere(&s));
+        }
+
+        // Merging with a contained sphere returns the container
+        let inner = Sphere::new(Vector3D::new(1.5, 2.0, 3.0), 0.25);
+        assert_eq!(a.merge(&inner), a);
+        assert_eq!(inner.merge(&a), a);
+    }
+
+    #[test]
+    fn test_sphere_transform() {
+        let rotation = Matrix3D::new(0.0, -1.0, 0.0,
+                                     1.0, 0.0, 0.0,
+                                     0.0, 0.0, 1.0);
+        let sphere = Sphere::new(Vector3D::new(1.0, 0.0, 0.0), 2.0);
+        let moved = sphere.transform(&rotation, &Vector3D::new(0.0, 0.0, 5.0));
+        assert_eq!(moved, Sphere::new(Vector3D::new(0.0, 1.0, 5.0), 2.0));
+    }
+}
@@ -0,0 +1,170 @@
+use super::{Aabb, Capsule, Matrix3D, Obb, Sphere, Vector3D};
+
+/// A convex shape described by its support function
+///
+/// GJK and EPA only ever ask a shape for its furthest point in a direction,
+/// so anything that can answer that question can be tested for overlap,
+/// distance and penetration against anything else.
+pub trait SupportMap {
+    /// Returns the point of the shape furthest along `direction`
+    ///
+    /// `direction` need not be normalised. When several points are equally
+    /// far any of them may be returned.
+    ///
+    /// # Arguments
+    /// - `direction` - The direction to search along
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Sphere, SupportMap, Vector3D};
+    ///
+    /// let sphere = Sphere::new(Vector3D::new(1.0, 0.0, 0.0), 2.0);
+    /// assert_eq!(sphere.support(&Vector3D::new(0.0, 5.0, 0.0)), Vector3D::new(1.0, 2.0, 0.0));
+    /// ```
+    fn support(&self, direction: &Vector3D) -> Vector3D;
+}
+
+impl<T: SupportMap + ?Sized> SupportMap for &T {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        (**self).support(direction)
+    }
+}
+
+impl SupportMap for Sphere {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        let length = direction.magnitude();
+        if length == 0.0 {
+            return self.center;
+        }
+        self.center + *direction * (self.radius / length)
+    }
+}
+
+impl SupportMap for Aabb {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        Vector3D::new(
+            if direction.x >= 0.0 { self.max.x } else { self.min.x },
+            if direction.y >= 0.0 { self.max.y } else { self.min.y },
+            if direction.z >= 0.0 { self.max.z } else { self.min.z },
+        )
+    }
+}
+
+impl SupportMap for Obb {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        let local = self.orientation.transpose() * *direction;
+        let corner = Vector3D::new(
+            self.half_extents.x.copysign(local.x),
+            self.half_extents.y.copysign(local.y),
+            self.half_extents.z.copysign(local.z),
+        );
+        self.center + self.orientation * corner
+    }
+}
+
+impl SupportMap for Capsule {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        let end = if direction.dot(&self.a) >= direction.dot(&self.b) { self.a } else { self.b };
+        Sphere::new(end, self.radius).support(direction)
+    }
+}
+
+/// The convex hull of a point set
+///
+/// An empty slice has no points, so the origin is returned.
+impl SupportMap for [Vector3D] {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        self.iter()
+            .copied()
+            .max_by(|p, q| p.dot(direction).total_cmp(&q.dot(direction)))
+            .unwrap_or_default()
+    }
+}
+
+/// A shape moved by `p -> rotation * p + translation`
+///
+/// `rotation` is assumed to be a pure rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transformed<S> {
+    pub shape: S,
+    pub rotation: Matrix3D,
+    pub translation: Vector3D,
+}
+
+impl<S> Transformed<S> {
+    /// Creates a new Transformed shape
+    ///
+    /// # Arguments
+    /// - `shape` - The shape in its local space
+    /// - `rotation` - The rotation from local to world space
+    /// - `translation` - The translation applied after the rotation
+    pub fn new(shape: S, rotation: Matrix3D, translation: Vector3D) -> Self {
+        Self { shape, rotation, translation }
+    }
+}
+
+impl<S: SupportMap> SupportMap for Transformed<S> {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        let local = self.rotation.transpose() * *direction;
+        self.rotation * self.shape.support(&local) + self.translation
+    }
+}
+
+/// The Minkowski sum of two shapes, every `a + b` for `a` in one and `b`
+/// in the other
+///
+/// Summing with a sphere rounds a shape off, e.g. a box plus a sphere is a
+/// rounded box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinkowskiSum<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A, B> MinkowskiSum<A, B> {
+    /// Creates a new MinkowskiSum
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A: SupportMap, B: SupportMap> SupportMap for MinkowskiSum<A, B> {
+    fn support(&self, direction: &Vector3D) -> Vector3D {
+        self.a.support(direction) + self.b.support(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_support_shapes() {
+        let d = Vector3D::new(1.0, -2.0, 0.5);
+        let aabb = Aabb::new(Vector3D::new(-1.0, -2.0, -3.0), Vector3D::new(1.0, 2.0, 3.0));
+        assert_eq!(aabb.support(&d), Vector3D::new(1.0, -2.0, 3.0));
+        assert_eq!(Obb::from_aabb(&aabb).support(&d), aabb.support(&d));
+
+        let capsule = Capsule::new(Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(0.0, -1.0, 0.0), 0.5);
+        assert_eq!(capsule.support(&Vector3D::new(0.0, -3.0, 0.0)), Vector3D::new(0.0, -1.5, 0.0));
+
+        let points = [Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0)];
+        assert_eq!(points[..].support(&Vector3D::new(-1.0, 2.0, 0.0)), points[2]);
+        assert_eq!(Sphere::new(points[1], 1.0).support(&Vector3D::default()), points[1]);
+    }
+
+    #[test]
+    fn test_support_transformed_and_sum() {
+        let rotation = Matrix3D::new(0.0, -1.0, 0.0,
+                                     1.0, 0.0, 0.0,
+                                     0.0, 0.0, 1.0);
+        let aabb = Aabb::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(2.0, 1.0, 1.0));
+        let moved = Transformed::new(aabb, rotation, Vector3D::new(0.0, 0.0, 5.0));
+        // The long local x axis now points along world y
+        assert_eq!(moved.support(&Vector3D::new(0.0, 1.0, 0.0)).y, 2.0);
+        assert_eq!(moved.support(&Vector3D::new(0.0, 0.0, 1.0)).z, 6.0);
+
+        let rounded = MinkowskiSum::new(&aabb, Sphere::new(Vector3D::default(), 0.5));
+        assert_eq!(rounded.support(&Vector3D::new(1.0, 0.0, 0.0)).x, 2.5);
+    }
+}
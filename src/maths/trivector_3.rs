@@ -0,0 +1,163 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::{Bivector3D, Matrix3D};
+
+/// Trivector in 3D Grassmann algebra
+///
+/// A single `e123` component holding a signed volume. It is the antiscalar of
+/// the algebra, so it is the identity element for the antiwedge product.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Trivector3D {
+    pub xyz: f64,
+}
+
+impl Add for Trivector3D {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.xyz + other.xyz)
+    }
+}
+
+impl Sub for Trivector3D {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.xyz - other.xyz)
+    }
+}
+
+impl Neg for Trivector3D {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.xyz)
+    }
+}
+
+impl Mul<f64> for Trivector3D {
+    type Output = Self;
+    fn mul(self, s: f64) -> Self::Output {
+        Self::new(self.xyz * s)
+    }
+}
+
+impl Trivector3D {
+    /// Creates a new Trivector3D
+    ///
+    /// # Arguments
+    /// - `xyz` - The `e123` component
+    pub fn new(xyz: f64) -> Self {
+        Self { xyz }
+    }
+
+    /// Returns the complement, the scalar with the same magnitude
+    pub fn complement(&self) -> f64 {
+        self.xyz
+    }
+
+    /// Returns the antiwedge product of two trivectors
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Trivector3D;
+    ///
+    /// let unit = Trivector3D::new(1.0);
+    /// assert_eq!(unit.antiwedge(&Trivector3D::new(3.0)), Trivector3D::new(3.0));
+    /// ```
+    pub fn antiwedge(&self, other: &Trivector3D) -> Self {
+        Self::new(self.xyz * other.xyz)
+    }
+
+    /// Returns the antiwedge product with a Bivector3D, the bivector scaled
+    /// by this volume
+    pub fn antiwedge_bivector(&self, b: &Bivector3D) -> Bivector3D {
+        *b * self.xyz
+    }
+
+    /// Returns the trivector transformed by a Matrix3D, which scales the
+    /// volume by the determinant
+    pub fn transform(&self, m: &Matrix3D) -> Self {
+        Self::new(self.xyz * m.determinant())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::Vector3D;
+
+    #[test]
+    fn test_trivector_3d_arithmetic() {
+        let a = Trivector3D::new(2.5);
+        let b = Trivector3D::new(-4.0);
+        assert_eq!(a + b, Trivector3D::new(-1.5));
+        assert_eq!(a - b, Trivector3D::new(6.5));
+        assert_eq!(-a, Trivector3D::new(-2.5));
+        assert_eq!(a * 2.0, Trivector3D::new(5.0));
+        assert_eq!(Trivector3D::default(), Trivector3D::new(0.0));
+        assert_eq!(b.complement(), -4.0);
+    }
+
+    #[test]
+    fn test_trivector_3d_orientation_and_volume() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let c = Vector3D::new(0.0, -1.0, 2.0);
+        let volume = |u: &Vector3D, v: &Vector3D, w: &Vector3D| u.wedge(v).wedge(w);
+        let abc = volume(&a, &b, &c);
+
+        // Even permutations keep the sign, odd ones flip it
+        assert_eq!(volume(&b, &c, &a), abc);
+        assert_eq!(volume(&c, &a, &b), abc);
+        for odd in [volume(&b, &a, &c), volume(&a, &c, &b), volume(&c, &b, &a)] {
+            assert!((odd + abc).xyz.abs() < 1e-12);
+        }
+
+        // The magnitude is the parallelepiped volume, unchanged by shear
+        assert!((abc.xyz.abs() - a.dot(&b.cross(&c)).abs()).abs() < 1e-12);
+        assert!((volume(&a, &b, &(c + a * 2.0 - b)) - abc).xyz.abs() < 1e-12);
+        assert_eq!(volume(&(a * 3.0), &b, &c).xyz, abc.xyz * 3.0);
+
+        // A right-handed frame is the positive unit volume, a mirrored one negative
+        let (x, y, z) = (Vector3D::new(1.0, 0.0, 0.0), Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(0.0, 0.0, 1.0));
+        assert_eq!(volume(&x, &y, &z), Trivector3D::new(1.0));
+        assert_eq!(volume(&x, &y, &-z), Trivector3D::new(-1.0));
+    }
+
+    #[test]
+    fn test_trivector_3d_duality() {
+        let a = Vector3D::new(1.0, 2.0, 3.0);
+        let b = Vector3D::new(-2.0, 0.5, 4.0);
+        let c = Vector3D::new(0.0, -1.0, 2.0);
+        let abc = a.wedge(&b).wedge(&c);
+
+        // The complement of a volume is the scalar the antiwedge gives
+        assert!((abc.complement() - a.wedge(&b).antiwedge_vector(&c)).abs() < 1e-12);
+        assert!((abc.complement() - a.wedge(&b).complement().dot(&c)).abs() < 1e-12);
+
+        // Transforming the factors is the same as transforming the volume
+        let m = Matrix3D::new(2.0, 1.0, 0.0,
+                              0.0, 3.0, -1.0,
+                              1.0, 0.0, 0.5);
+        let transformed = (m * a).wedge(&(m * b)).wedge(&(m * c));
+        assert!((transformed - abc.transform(&m)).xyz.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trivector_3d_antiwedge() {
+        let unit = Trivector3D::new(1.0);
+        let volume = Trivector3D::new(3.0);
+        // The unit volume is the antiwedge identity
+        assert_eq!(unit.antiwedge(&volume), volume);
+        assert_eq!(volume.antiwedge(&unit), volume);
+        assert_eq!(volume.antiwedge(&Trivector3D::new(-0.5)), Trivector3D::new(-1.5));
+
+        let plane = Vector3D::new(1.0, 2.0, 3.0).wedge(&Vector3D::new(0.0, 1.0, -1.0));
+        assert_eq!(unit.antiwedge_bivector(&plane), plane);
+        assert_eq!(volume.antiwedge_bivector(&plane), plane * 3.0);
+
+        // Transforming scales the volume by the determinant
+        let m = Matrix3D::new(2.0, 1.0, 0.0,
+                              0.0, 3.0, 0.0,
+                              1.0, 0.0, 0.5);
+        assert!((volume.transform(&m).xyz - 3.0 * m.determinant()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,111 @@
+use super::{Easing, Lerp};
+
+/// Animation of a value from `start` to `end` over `duration`, shaped by
+/// an easing
+///
+/// Works with any `Lerp` type. Time is advanced by the caller, so one
+/// tween can be driven by frame time, a fixed step or scrubbed directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<T: Lerp> {
+    pub start: T,
+    pub end: T,
+    pub duration: f64,
+    pub easing: Easing,
+    elapsed: f64,
+}
+
+impl<T: Lerp> Tween<T> {
+    /// Creates a Tween at its start
+    ///
+    /// # Arguments
+    /// - `start` - The value at the start
+    /// - `end` - The value once finished
+    /// - `duration` - The length of the tween, zero or less finishes at once
+    /// - `easing` - The mapping from elapsed time to progress
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{EaseCurve, Easing, Tween, Vector3D};
+    ///
+    /// let mut tween = Tween::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(4.0, 0.0, 8.0), 2.0, Easing::In(EaseCurve::Quad));
+    /// assert_eq!(tween.advance(1.0), Vector3D::new(1.0, 0.0, 2.0));
+    /// assert!(!tween.is_finished());
+    /// assert_eq!(tween.advance(5.0), Vector3D::new(4.0, 0.0, 8.0));
+    /// assert!(tween.is_finished());
+    /// ```
+    pub fn new(start: T, end: T, duration: f64, easing: Easing) -> Self {
+        Self { start, end, duration, easing, elapsed: 0.0 }
+    }
+
+    /// Returns the time since the start, between 0 and the duration
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Returns the linear progress in 0..1 before easing
+    pub fn progress(&self) -> f64 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Returns true once the tween has reached its end
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Returns the value at the current time
+    pub fn value(&self) -> T {
+        self.start.lerp(&self.end, self.easing.apply(self.progress()))
+    }
+
+    /// Moves the current time by `dt`, clamped to the tween, and returns
+    /// the new value
+    pub fn advance(&mut self, dt: f64) -> T {
+        self.seek(self.elapsed + dt)
+    }
+
+    /// Sets the current time, clamped to the tween, and returns the new value
+    pub fn seek(&mut self, time: f64) -> T {
+        self.elapsed = time.clamp(0.0, self.duration.max(0.0));
+        self.value()
+    }
+
+    /// Returns the tween to its start
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maths::{EaseCurve, Matrix3D, Vector3D};
+
+    #[test]
+    fn test_tween() {
+        let mut tween = Tween::new(10.0, 20.0, 4.0, Easing::Out(EaseCurve::Quad));
+        assert_eq!(tween.value(), 10.0);
+        assert_eq!(tween.advance(2.0), 17.5);
+        assert_eq!(tween.progress(), 0.5);
+        assert_eq!(tween.advance(-10.0), 10.0);
+        assert_eq!(tween.seek(100.0), 20.0);
+        assert_eq!(tween.elapsed(), 4.0);
+        assert!(tween.is_finished());
+        tween.reset();
+        assert_eq!(tween.value(), 10.0);
+
+        let instant = Tween::new(Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 2.0, 3.0), 0.0, Easing::Linear);
+        assert!(instant.is_finished());
+        assert_eq!(instant.value(), Vector3D::new(1.0, 2.0, 3.0));
+
+        let scale = Matrix3D::new(2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0);
+        let mut tween = Tween::new(Matrix3D::identity(), scale, 1.0, Easing::InOut(EaseCurve::Sine));
+        assert!(tween.value() == Matrix3D::identity());
+        let half = tween.seek(0.5);
+        assert!((half[(1, 1)] - 1.5).abs() < 1e-12 && half[(0, 1)] == 0.0);
+        assert!(tween.seek(1.0) == scale);
+    }
+}
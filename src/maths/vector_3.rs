@@ -3,6 +3,8 @@ use std::{
     ops::{Index, IndexMut, Mul, DivAssign, MulAssign, Div, Add, Sub, Neg},
 };
 
+use super::{Bivector3D, Trivector3D};
+
 #[derive(Clone, Copy)]
 pub struct Vector3D {
     pub x: f64,
@@ -63,7 +65,7 @@ impl From<Vector3D> for [f32; 3] {
 
 impl PartialEq for Vector3D {
     fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == self.z
+        self.x == other.x && self.y == other.y && self.z == other.z
     } 
 }
 
@@ -163,7 +165,7 @@ impl Neg for Vector3D {
 /// let vector_3d = Vector3D::new(1.0, 2.0, 3.0);
 /// let vector_3d = vector_3d / 2.0;
 /// assert_eq!(vector_3d, [0.5, 1.0, 1.5].into());
-
+/// ```
 impl Div<f64> for Vector3D {
     type Output = Self;
     fn div(self, s: f64) -> Self::Output {
@@ -298,6 +300,57 @@ impl Vector3D {
         (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
     }
 
+    /// Returns the component-wise minimum of two Vector3Ds
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Vector3D;
+    ///
+    /// let a = Vector3D::new(1.0, 5.0, 3.0);
+    /// let b = Vector3D::new(4.0, 2.0, 6.0);
+    /// assert_eq!(a.min(&b), Vector3D::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    /// Returns the component-wise maximum of two Vector3Ds
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Vector3D;
+    ///
+    /// let a = Vector3D::new(1.0, 5.0, 3.0);
+    /// let b = Vector3D::new(4.0, 2.0, 6.0);
+    /// assert_eq!(a.max(&b), Vector3D::new(4.0, 5.0, 6.0));
+    /// ```
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+
+    /// Returns the squared magnitude of the Vector3D
+    ///
+    /// Cheaper than `magnitude` when only comparing lengths.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Vector3D;
+    ///
+    /// let vector_3d = Vector3D::new(1.0, 2.0, 3.0);
+    /// assert_eq!(vector_3d.magnitude_squared(), 14.0);
+    /// ```
+    pub fn magnitude_squared(&self) -> f64 {
+        self.dot(self)
+    }
+
     /// Returns the normalised Vector3D
     /// 
     /// # Example
@@ -334,6 +387,9 @@ impl Vector3D {
     }
 
     /// Returns the cross product of two Vector3Ds
+    ///
+    /// The result is really a bivector written as a vector, see `wedge` for
+    /// a version that transforms correctly under non-orthogonal matrices.
     /// 
     /// # Example
     /// ```
@@ -350,6 +406,57 @@ impl Vector3D {
             z: self.x * other.y - self.y * other.x,
         }
     }
+
+    /// Returns a unit Vector3D perpendicular to this one, or +x if this is zero
+    ///
+    /// Projects out whichever of the x or y axes is further from parallel,
+    /// so the result is well conditioned for every direction.
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::Vector3D;
+    ///
+    /// let vector_3d = Vector3D::new(1.0, 2.0, 3.0);
+    /// let perpendicular = vector_3d.any_perpendicular();
+    /// assert!(vector_3d.dot(&perpendicular).abs() < 1e-15);
+    /// assert!((perpendicular.magnitude() - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn any_perpendicular(&self) -> Self {
+        let length_squared = self.magnitude_squared();
+        if length_squared == 0.0 {
+            return Self::new(1.0, 0.0, 0.0);
+        }
+        let axis = if self.x * self.x < 0.81 * length_squared { Self::new(1.0, 0.0, 0.0) } else { Self::new(0.0, 1.0, 0.0) };
+        (axis - *self * (self.dot(&axis) / length_squared)).normalise()
+    }
+
+    /// Returns the wedge product of two Vector3Ds, the oriented area they span
+    ///
+    /// # Example
+    /// ```
+    /// use game_maths::maths::{Bivector3D, Vector3D};
+    ///
+    /// let a = Vector3D::new(1.0, 0.0, 0.0);
+    /// let b = Vector3D::new(0.0, 1.0, 0.0);
+    /// assert_eq!(a.wedge(&b), Bivector3D::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn wedge(&self, other: &Self) -> Bivector3D {
+        let c = self.cross(other);
+        Bivector3D::new(c.x, c.y, c.z)
+    }
+
+    /// Returns the wedge product with a Bivector3D, the signed volume they span
+    pub fn wedge_bivector(&self, b: &Bivector3D) -> Trivector3D {
+        b.wedge(self)
+    }
+
+    /// Returns the complement, the Bivector3D for the plane perpendicular to
+    /// this Vector3D
+    ///
+    /// With the Euclidean metric this is also the Hodge dual.
+    pub fn complement(&self) -> Bivector3D {
+        Bivector3D::new(self.x, self.y, self.z)
+    }
 }
 
 #[cfg(test)]
@@ -469,4 +576,19 @@ mod tests {
         let other_vector_3d = Vector3D::new(1.0, 2.0, 3.0);
         assert_eq!(vector_3d.cross(&other_vector_3d), [0.0, 0.0, 0.0].into());
     }
+
+    #[test]
+    fn test_vector_3d_any_perpendicular() {
+        for vector_3d in [
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, -3.0, 0.0),
+            Vector3D::new(1e-8, 2e-8, -5e-9),
+            Vector3D::new(100.0, 1.0, -20.0),
+        ] {
+            let perpendicular = vector_3d.any_perpendicular();
+            assert!(vector_3d.normalise().dot(&perpendicular).abs() < 1e-15);
+            assert!((perpendicular.magnitude() - 1.0).abs() < 1e-15);
+        }
+        assert_eq!(Vector3D::default().any_perpendicular(), Vector3D::new(1.0, 0.0, 0.0));
+    }
 }